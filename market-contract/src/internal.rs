@@ -2,12 +2,98 @@ use crate::*;
 
 #[near_bindgen]
 impl MarketContract {
+    // Check storage deposit của owner_id có cover được thêm new_sales Sales không
+    pub(crate) fn internal_assert_storage_for_new_sales(&self, owner_id: &AccountId, new_sales: u128) {
+        let storage_balance = self.storage_deposit_account.get(owner_id).unwrap_or(0);
+        let storage_minimum_amount = self.storage_minimun_balance().0; // .0 là hàm chuyển từ U128 -> u128
+        let storage_required =
+            (self.get_supply_by_owner_id(owner_id.clone()).0 + new_sales) * storage_minimum_amount;
+
+        assert!(
+            storage_balance >= storage_required,
+            "Storage balance not enough for cover storage staking"
+        );
+    }
+
+    // Thêm sale của 1 NFT vào sales, tokens_by_owner_id, tokens_by_contract_id và các index có thứ tự
+    pub(crate) fn internal_add_sale(&mut self, sale: Sale) {
+        assert_state_migrated();
+        let contract_and_token_id = format!("{}{}{}", sale.nft_contract_id, ".", sale.token_id);
+
+        // Thêm vào tokens_by_owner_id
+        // Nếu chưa tồn tại trong tokens_by_owner_id -> Tạo mới
+        let mut tokens_by_owner_id = self
+            .tokens_by_owner_id
+            .get(&sale.owner_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(
+                    StorageKey::InnerByOwnerIdKey {
+                        account_id_hash: hash_account_id(&sale.owner_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        tokens_by_owner_id.insert(&contract_and_token_id);
+        self.tokens_by_owner_id
+            .insert(&sale.owner_id, &tokens_by_owner_id);
+
+        // Thêm vào tokens_by_contract_id
+        let mut tokens_by_contract_id = self
+            .tokens_by_contract_id
+            .get(&sale.nft_contract_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(
+                    StorageKey::InnerByContractIdKey {
+                        account_id_hash: hash_account_id(&sale.nft_contract_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        tokens_by_contract_id.insert(&sale.token_id);
+        self.tokens_by_contract_id
+            .insert(&sale.nft_contract_id, &tokens_by_contract_id);
+
+        // Thêm vào sales
+        self.sales.insert(&contract_and_token_id, &sale);
+        self.internal_add_sale_to_indexes(&contract_and_token_id, &sale);
+    }
+
+    // Thêm 1 Sale vào sale_ids và sales_by_collection
+    pub(crate) fn internal_add_sale_to_indexes(
+        &mut self,
+        contract_and_token_id: &ContractAndTokenId,
+        sale: &Sale,
+    ) {
+        self.sale_ids.insert(contract_and_token_id, &());
+
+        let mut sales_by_collection = self
+            .sales_by_collection
+            .get(&sale.collection_name)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::SalesByCollectionInnerKey {
+                        collection_name_hash: hash_account_id(&sale.collection_name),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+        sales_by_collection.insert(contract_and_token_id, &());
+        self.sales_by_collection
+            .insert(&sale.collection_name, &sales_by_collection);
+    }
+
     // Xoá sale của 1 NFT
     pub(crate) fn internal_remove_sale(
         &mut self,
         nft_contract_id: AccountId,
         token_id: TokenId,
     ) -> Sale {
+        assert_state_migrated();
         let contract_and_token_id = format!("{}{}{}", nft_contract_id, ".", token_id);
 
         let sale = self
@@ -15,6 +101,20 @@ impl MarketContract {
             .remove(&contract_and_token_id)
             .expect("Not found sale");
 
+        // Xoá sale khỏi các index có thứ tự
+        self.sale_ids.remove(&contract_and_token_id);
+        let mut sales_by_collection = self
+            .sales_by_collection
+            .get(&sale.collection_name)
+            .expect("Not found sale by collection_name");
+        sales_by_collection.remove(&contract_and_token_id);
+        if sales_by_collection.len() == 0 {
+            self.sales_by_collection.remove(&sale.collection_name);
+        } else {
+            self.sales_by_collection
+                .insert(&sale.collection_name, &sales_by_collection);
+        }
+
         // Xoá sale khỏi tokens_by_owner_id
         let mut tokens_by_owner_id = self
             .tokens_by_owner_id
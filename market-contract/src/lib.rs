@@ -1,27 +1,34 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet};
 use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
-    env, ext_contract, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise,
+    env, ext_contract, near_bindgen, AccountId, Balance, CryptoHash, Gas, PanicOnDefault, Promise,
+    PromiseResult,
 };
 
 pub use crate::internal::*;
+pub use crate::migrate::*;
 pub use crate::nft_callback::*;
 pub use crate::sale::*;
 pub use crate::sale_view::*;
 use crate::utils::*;
 
-// Coi như sau mỗi lần bán qua lại thì tăng storage lên 1000 bytes
-const STORAGE_PER_SALE: u128 = 1000 * env::STORAGE_PRICE_PER_BYTE;
+// Coi như sau mỗi lần bán qua lại thì tăng storage lên 2000 bytes (Sale + các index của Sale)
+const STORAGE_PER_SALE: u128 = 2000 * env::STORAGE_PRICE_PER_BYTE;
+const DEFAULT_PAGE_LIMIT: u64 = 10; // Limit mặc định của 1 trang trong các list views
+const MAX_PAGE_LIMIT: u64 = 100; // Limit tối đa của 1 trang trong các list views
 
 mod internal;
+mod migrate;
 mod nft_callback;
 mod sale;
 mod sale_view;
 mod utils;
 
 pub type TokenId = u32;
+// Token id dạng string trong JSON theo chuẩn NEP-171 (NFT Contract gửi sang trong nft_on_approve)
+pub type JsonTokenId = String;
 pub type NFTContractId = String;
 pub type SalePriceInYoctoNear = U128;
 // Để nếu có 2 Contract khác nhau cùng sử dụng market-contract này thì nếu trùng token id cũng ko sao
@@ -51,6 +58,15 @@ pub struct Sale {
     pub sale_conditions: SalePriceInYoctoNear, // Các điều kiện của sales (Giá, ...)
 }
 
+// 1 trang kết quả của các list views (cursor pagination)
+// Trang tiếp theo: gọi lại với cursor = next_cursor (None nếu đã là trang cuối)
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>, // Id (stable) của item cuối cùng của trang
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct MarketContract {
@@ -59,8 +75,12 @@ pub struct MarketContract {
     pub tokens_by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>, // Danh sách các token_id đang được đăng bán của 1 account_id
     pub tokens_by_contract_id: LookupMap<NFTContractId, UnorderedSet<TokenId>>, // Danh sách các token_id đang được đăng bán của 1 nft contract
     pub storage_deposit_account: LookupMap<AccountId, Balance>, // Danh sách lượng deposit của từng account để cover storage
+    // Các index được phân trang là TreeMap (keys có thứ tự) -> 1 trang chỉ đọc các keys sau cursor
+    pub sale_ids: TreeMap<ContractAndTokenId, ()>, // Tất cả các Sales, theo thứ tự contract_and_token_id
+    pub sales_by_collection: LookupMap<CollectionName, TreeMap<ContractAndTokenId, ()>>, // Danh sách các Sales của 1 Collection
 }
 
+// Storage prefix = Borsh index of the variant -> Only add new keys at the end (the deployed state uses the old indexes)
 #[derive(BorshDeserialize, BorshSerialize)]
 pub enum StorageKey {
     SaleKey,
@@ -75,12 +95,20 @@ pub enum StorageKey {
         account_id_hash: CryptoHash,
     },
     StorageDepositKey,
+    SaleIdsKey,
+    SalesByCollectionKey,
+    SalesByCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
 }
 
 #[near_bindgen]
 impl MarketContract {
     #[init]
     pub fn new(owner_id: AccountId) -> Self {
+        // Contract mới luôn dùng layout hiện tại
+        write_state_version();
+
         Self {
             owner_id,
             sales: UnorderedMap::new(StorageKey::SaleKey.try_to_vec().unwrap()),
@@ -91,6 +119,10 @@ impl MarketContract {
             storage_deposit_account: LookupMap::new(
                 StorageKey::StorageDepositKey.try_to_vec().unwrap(),
             ),
+            sale_ids: TreeMap::new(StorageKey::SaleIdsKey.try_to_vec().unwrap()),
+            sales_by_collection: LookupMap::new(
+                StorageKey::SalesByCollectionKey.try_to_vec().unwrap(),
+            ),
         }
     }
 
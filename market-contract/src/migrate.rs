@@ -0,0 +1,209 @@
+// Upgrade the code of the Contract and migrate its state to the new layout
+use crate::*;
+
+const GAS_FOR_UPGRADE: Gas = 20_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
+// Storage key of the state version (the deployed baseline doesn't have this key -> version 1)
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+pub const CURRENT_STATE_VERSION: u32 = 2;
+// Storage key of the V1 -> V2 migration progress (only exists while migrate_state is running)
+const MIGRATION_PROGRESS_KEY: &[u8] = b"MIGRATION_PROGRESS";
+const DEFAULT_MIGRATION_LIMIT: u64 = 100;
+
+// ----------------------------------- Versioned State -----------------------------------
+// Every layout of the Contract state that has been deployed
+/**
+ * - V1: baseline layout (no STATE_VERSION key)
+ * - V2: current layout (+ index có thứ tự của các Sales)
+ * - Khi deploy 1 layout mới: thêm 1 variant mới + routine migrate từ các layout cũ
+ */
+pub enum VersionedMarketContract {
+    V1(MarketContractV1),
+    V2(MarketContract),
+}
+
+impl VersionedMarketContract {
+    // Đọc state hiện tại theo version đã lưu
+    pub fn read() -> Self {
+        match read_state_version() {
+            1 => Self::V1(env::state_read().expect("Contract is not initialized")),
+            CURRENT_STATE_VERSION => {
+                Self::V2(env::state_read().expect("Contract is not initialized"))
+            }
+            version => env::panic(format!("Unknown state version {}", version).as_bytes()),
+        }
+    }
+
+    pub fn into_current(self) -> MarketContract {
+        match self {
+            Self::V1(contract) => contract.migrate(),
+            Self::V2(contract) => contract,
+        }
+    }
+}
+
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| u32::try_from_slice(&version).expect("Invalid state version"))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(
+        STATE_VERSION_KEY,
+        &CURRENT_STATE_VERSION.try_to_vec().unwrap(),
+    );
+}
+
+// ----------------------------------- Migration Progress -----------------------------------
+// Các bước của migrate_state, theo thứ tự
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationStep {
+    Sales, // sale_ids + sales_by_collection
+    Done,
+}
+
+// Tiến độ migrate state V1, lưu ở key riêng tới khi migrate_state xong
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MigrationProgress {
+    pub step: MigrationStep,
+    pub next_index: u64, // Index của Sale tiếp theo trong sales
+    pub sales: u64,      // Số Sales của state V1
+}
+
+pub(crate) fn read_migration_progress() -> Option<MigrationProgress> {
+    env::storage_read(MIGRATION_PROGRESS_KEY).map(|progress| {
+        MigrationProgress::try_from_slice(&progress).expect("Invalid migration progress")
+    })
+}
+
+fn write_migration_progress(progress: &MigrationProgress) {
+    env::storage_write(MIGRATION_PROGRESS_KEY, &progress.try_to_vec().unwrap());
+}
+
+// State V1 chưa migrate xong -> panic (thêm, xoá Sale làm đổi index của các Sales chưa được migrate)
+pub(crate) fn assert_state_migrated() {
+    assert!(
+        !env::storage_has_key(MIGRATION_PROGRESS_KEY),
+        "The contract state is being migrated"
+    );
+}
+
+// ----------------------------------- V1 Layout -----------------------------------
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MarketContractV1 {
+    pub owner_id: AccountId,
+    pub sales: UnorderedMap<ContractAndTokenId, Sale>,
+    pub tokens_by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+    pub tokens_by_contract_id: LookupMap<NFTContractId, UnorderedSet<TokenId>>,
+    pub storage_deposit_account: LookupMap<AccountId, Balance>,
+}
+
+impl MarketContractV1 {
+    // Các field cũ giữ nguyên storage prefix, các index mới được tạo theo từng batch bằng migrate_state
+    // Thêm, xoá, mua Sale bị chặn tới khi migrate_state xong
+    pub fn migrate(self) -> MarketContract {
+        write_migration_progress(&MigrationProgress {
+            step: MigrationStep::Sales,
+            next_index: 0,
+            sales: self.sales.len(),
+        });
+
+        MarketContract {
+            owner_id: self.owner_id,
+            sales: self.sales,
+            tokens_by_owner_id: self.tokens_by_owner_id,
+            tokens_by_contract_id: self.tokens_by_contract_id,
+            storage_deposit_account: self.storage_deposit_account,
+            sale_ids: TreeMap::new(StorageKey::SaleIdsKey.try_to_vec().unwrap()),
+            sales_by_collection: LookupMap::new(
+                StorageKey::SalesByCollectionKey.try_to_vec().unwrap(),
+            ),
+        }
+    }
+}
+
+#[near_bindgen]
+impl MarketContract {
+    // Deploy code mới của Contract rồi gọi migrate trên code mới
+    /**
+     * - Only the Contract's owner can upgrade
+     * - Input của hàm là wasm code (raw bytes, không phải JSON)
+     */
+    pub fn upgrade(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner of this contract can upgrade it"
+        );
+        let code = env::input().expect("Missing contract code");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                b"{}".to_vec(),
+                NO_DEPOSIT,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE,
+            );
+    }
+
+    // Đọc state theo layout cũ, chuyển sang layout hiện tại
+    // Chỉ Contract tự gọi được (qua upgrade), gọi lại khi state đã là layout hiện tại thì không thay đổi gì
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate() -> Self {
+        let contract = VersionedMarketContract::read().into_current();
+        write_state_version();
+        contract
+    }
+
+    // Migrate state V1 theo từng batch sau khi upgrade
+    /**
+     * - Only the Contract's owner can call
+     * - Mỗi lần gọi index tối đa `limit` Sales (default 100), gọi lại cho tới khi return step = done
+     * - Thêm, xoá, mua Sale bị chặn tới khi xong
+     */
+    pub fn migrate_state(&mut self, limit: Option<u64>) -> MigrationProgress {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner of this contract can migrate the state"
+        );
+        let mut progress = read_migration_progress().expect("The state has already been migrated");
+
+        let limit = limit.unwrap_or(DEFAULT_MIGRATION_LIMIT);
+        assert!(limit > 0, "Limit must be greater than 0");
+        let end = progress.sales.min(progress.next_index + limit);
+        for index in progress.next_index..end {
+            let contract_and_token_id = self.sales.keys_as_vector().get(index).unwrap();
+            let sale = self.sales.get(&contract_and_token_id).unwrap();
+            self.internal_add_sale_to_indexes(&contract_and_token_id, &sale);
+        }
+        progress.next_index = end;
+
+        if progress.next_index == progress.sales {
+            progress.step = MigrationStep::Done;
+            env::storage_remove(MIGRATION_PROGRESS_KEY);
+        } else {
+            write_migration_progress(&progress);
+        }
+        progress
+    }
+
+    // Tiến độ migrate state V1 (None: không có state nào đang được migrate)
+    pub fn get_migration_progress(&self) -> Option<MigrationProgress> {
+        read_migration_progress()
+    }
+
+    // Version của layout state hiện tại
+    pub fn get_state_version(&self) -> u32 {
+        read_state_version()
+    }
+}
@@ -1,11 +1,15 @@
 use crate::*;
 
+const GAS_FOR_NFT_TOKEN_DETAIL: Gas = 5_000_000_000_000;
+const GAS_FOR_RESOLVE_OPERATOR_SALES: Gas = 30_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
 // Hàm nft_on_approve để nft market gọi cross-contract call sang
 // Thực hiện cập nhật thông tin trạng thái, dữ liệu sales
 pub trait NonFungibleTokenApprovalReceiver {
     fn nft_on_approve(
         &mut self,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         owner_id: AccountId,
         approval_id: u64,
         token_by_template_id: TokenId, // Stt của NFT trong template nó thuộc vào
@@ -26,6 +30,16 @@ pub trait NonFungibleTokenApprovalReceiver {
         msg: String,
     ) {
     }
+
+    #[allow(unused_variables)]
+    fn nft_on_approve_operator(
+        &mut self,
+        owner_id: AccountId,
+        approval_id: u64,
+        collection_name: Option<CollectionName>,
+        msg: String,
+    ) {
+    }
 }
 
 // Cấu trúc của msg
@@ -35,6 +49,28 @@ pub struct SaleArgs {
     pub sale_conditions: SalePriceInYoctoNear,
 }
 
+// Cấu trúc của msg của nft_on_approve_operator: các NFTs được đăng bán cùng 1 giá
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorSaleArgs {
+    pub token_ids: Vec<JsonTokenId>,
+    pub sale_conditions: SalePriceInYoctoNear,
+}
+
+// Thông tin của NFT trả về bởi nft_token_detail của NFT Contract (chỉ các field cần cho Sale)
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenDetail {
+    pub owner_id: AccountId,
+    pub token_id: TokenId,
+    pub token_by_template_id: TokenId,
+    pub collection_id: CollectionId,
+    pub collection_name: CollectionName,
+    pub schema_id: SchemaId,
+    pub schema_name: SchemaName,
+    pub template_id: TemplateId,
+}
+
 #[near_bindgen]
 impl NonFungibleTokenApprovalReceiver for MarketContract {
     /**
@@ -42,7 +78,7 @@ impl NonFungibleTokenApprovalReceiver for MarketContract {
      */
     fn nft_on_approve(
         &mut self,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         owner_id: AccountId,
         approval_id: u64,
         token_by_template_id: TokenId, // Stt của NFT trong template nó thuộc vào
@@ -66,76 +102,30 @@ impl NonFungibleTokenApprovalReceiver for MarketContract {
         );
         assert_eq!(signer_id, owner_id, "owner_id should be signer_id");
 
+        let token_id: TokenId = token_id.parse().expect("Invalid token id");
+
         // --- Thêm mới Sale vào trong Market Contract ---
         // Check cover storage
-        let storage_balance = self.storage_deposit_account.get(&signer_id).unwrap_or(0);
-        let storage_minimum_amount = self.storage_minimun_balance().0; // .0 là hàm chuyển từ U128 -> u128
-        let storage_required =
-            (self.get_supply_by_owner_id(signer_id.clone()).0 + 1) * storage_minimum_amount;
-
-        assert!(
-            storage_balance >= storage_required,
-            "Storage balance not enough for cover storage staking"
-        );
+        self.internal_assert_storage_for_new_sales(&signer_id, 1);
 
         let SaleArgs { sale_conditions } =
             near_sdk::serde_json::from_str(&msg).expect("Not valid Sale Args"); // Parse msg từ String -> Json
 
-        let contract_and_token_id =
-            format!("{}{}{}", nft_contract_id.clone(), ".", token_id.clone());
-
-        // Thêm vào sales
-        self.sales.insert(
-            &contract_and_token_id,
-            &Sale {
-                owner_id: owner_id.clone(),
-                approval_id,
-                nft_contract_id: nft_contract_id.clone(),
-                token_id: token_id.clone(),
-                token_by_template_id: token_by_template_id.clone(),
-                collection_id: collection_id.clone(),
-                collection_name: collection_name.clone(),
-                schema_id: schema_id.clone(),
-                schema_name: schema_name.clone(),
-                template_id: template_id.clone(),
-                sale_conditions,
-            },
-        );
-
-        // Thêm vào tokens_by_owner_id
-        // Nếu chưa tồn tại trong tokens_by_owner_id -> Tạo mới
-        let mut tokens_by_owner_id = self.tokens_by_owner_id.get(&owner_id).unwrap_or_else(|| {
-            UnorderedSet::new(
-                StorageKey::InnerByOwnerIdKey {
-                    account_id_hash: hash_account_id(&owner_id),
-                }
-                .try_to_vec()
-                .unwrap(),
-            )
+        // Thêm vào sales + tokens_by_owner_id + tokens_by_contract_id
+        self.internal_add_sale(Sale {
+            owner_id,
+            approval_id,
+            nft_contract_id,
+            token_id,
+            token_by_template_id,
+            collection_id,
+            collection_name,
+            schema_id,
+            schema_name,
+            template_id,
+            sale_conditions,
         });
 
-        tokens_by_owner_id.insert(&contract_and_token_id);
-        self.tokens_by_owner_id
-            .insert(&owner_id, &tokens_by_owner_id);
-
-        // Thêm vào tokens_by_contract_id
-        let mut tokens_by_contract_id = self
-            .tokens_by_contract_id
-            .get(&nft_contract_id)
-            .unwrap_or_else(|| {
-                UnorderedSet::new(
-                    StorageKey::InnerByContractIdKey {
-                        account_id_hash: hash_account_id(&nft_contract_id),
-                    }
-                    .try_to_vec()
-                    .unwrap(),
-                )
-            });
-
-        tokens_by_contract_id.insert(&token_id);
-        self.tokens_by_contract_id
-            .insert(&nft_contract_id, &tokens_by_contract_id);
-
         // TODO: Thêm vào collections_by_name
         // TODO: Thêm vào schemas_by_id
         // TODO: Thêm vào templates_by_id
@@ -151,4 +141,122 @@ impl NonFungibleTokenApprovalReceiver for MarketContract {
         msg: String,
     ) {
     }
+
+    /**
+     * msg: {"token_ids": ["1", "2"], "sale_conditions": "100000000000000"}
+     * Operator approval không gửi kèm thông tin của NFTs -> Lấy thông tin từng NFT bằng nft_token_detail
+     * Sales được tạo trong callback resolve_operator_sales
+     */
+    fn nft_on_approve_operator(
+        &mut self,
+        owner_id: AccountId,
+        approval_id: u64,
+        collection_name: Option<CollectionName>,
+        msg: String,
+    ) {
+        // User => NFT Contract => Market Contract
+        let nft_contract_id = env::predecessor_account_id(); // NFT contract id chính là người gọi hàm
+        let signer_id = env::signer_account_id();
+
+        assert_ne!(
+            nft_contract_id, signer_id,
+            "nft_on_approve_operator should only be called via cross contract call"
+        );
+        assert_eq!(signer_id, owner_id, "owner_id should be signer_id");
+
+        let OperatorSaleArgs {
+            token_ids,
+            sale_conditions,
+        } = near_sdk::serde_json::from_str(&msg).expect("Not valid Sale Args"); // Parse msg từ String -> Json
+        assert!(!token_ids.is_empty(), "Sale Args must have at least 1 token");
+
+        // Check cover storage của tất cả Sales mới
+        self.internal_assert_storage_for_new_sales(&owner_id, token_ids.len() as u128);
+
+        // Lấy thông tin của các NFTs (chạy song song) -> Tạo Sales trong resolve_operator_sales
+        let token_details = token_ids
+            .iter()
+            .map(|token_id| {
+                let token_id: TokenId = token_id.parse().expect("Invalid token id");
+                ext_nft_contract::nft_token_detail(
+                    token_id,
+                    &nft_contract_id,
+                    NO_DEPOSIT,
+                    GAS_FOR_NFT_TOKEN_DETAIL,
+                )
+            })
+            .reduce(|promise, token_detail| promise.and(token_detail))
+            .unwrap();
+
+        token_details.then(ext_self::resolve_operator_sales(
+            owner_id,
+            approval_id,
+            collection_name,
+            sale_conditions,
+            nft_contract_id,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_RESOLVE_OPERATOR_SALES,
+        ));
+    }
+}
+
+#[near_bindgen]
+impl MarketContract {
+    // Callback của nft_on_approve_operator: tạo Sale cho các NFTs trong kết quả của nft_token_detail
+    // Bỏ qua NFT không tồn tại, không thuộc owner_id hoặc không thuộc Collection của approval
+    // Return danh sách token ids đã được đăng bán
+    #[private]
+    pub fn resolve_operator_sales(
+        &mut self,
+        owner_id: AccountId,
+        approval_id: u64,
+        collection_name: Option<CollectionName>,
+        sale_conditions: SalePriceInYoctoNear,
+        nft_contract_id: AccountId,
+    ) -> Vec<TokenId> {
+        let mut token_ids = Vec::<TokenId>::new();
+
+        for index in 0..env::promise_results_count() {
+            let token = match env::promise_result(index) {
+                PromiseResult::Successful(value) => {
+                    near_sdk::serde_json::from_slice::<Option<TokenDetail>>(&value)
+                        .ok()
+                        .flatten()
+                }
+                _ => None,
+            };
+            let token = match token {
+                Some(token)
+                    if token.owner_id == owner_id
+                        && collection_name
+                            .as_ref()
+                            .is_none_or(|collection_name| &token.collection_name == collection_name) =>
+                {
+                    token
+                }
+                _ => continue,
+            };
+
+            // User có thể đã rút storage deposit trong lúc chờ callback
+            self.internal_assert_storage_for_new_sales(&owner_id, 1);
+
+            token_ids.push(token.token_id);
+            self.internal_add_sale(Sale {
+                owner_id: owner_id.clone(),
+                approval_id,
+                nft_contract_id: nft_contract_id.clone(),
+                token_id: token.token_id,
+                token_by_template_id: token.token_by_template_id,
+                collection_id: token.collection_id,
+                collection_name: token.collection_name,
+                schema_id: token.schema_id,
+                schema_name: token.schema_name,
+                template_id: token.template_id,
+                sale_conditions,
+            });
+        }
+
+        token_ids
+    }
 }
@@ -10,11 +10,22 @@ pub trait NFTContract {
         balance: U128,
         max_len_payout: u32,
     ) -> Payout;
+
+    fn nft_token_detail(&self, token_id: TokenId) -> Option<TokenDetail>;
 }
 
 #[ext_contract(ext_self)]
 pub trait MarketContract {
     fn resolve_purchase(&mut self, buyer_id: AccountId, price: U128) -> Promise;
+
+    fn resolve_operator_sales(
+        &mut self,
+        owner_id: AccountId,
+        approval_id: u64,
+        collection_name: Option<CollectionName>,
+        sale_conditions: SalePriceInYoctoNear,
+        nft_contract_id: AccountId,
+    ) -> Vec<TokenId>;
 }
 
 #[near_bindgen]
@@ -30,100 +30,75 @@ impl MarketContract {
         }
     }
 
-    // Lấy tất cả thông tin của sale hiện tại (có pagination)
-    pub fn get_sales(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Sale> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        self.sales
-            .values()
-            .skip(start as usize)
-            .take(limit.unwrap_or(0) as usize)
-            .collect()
+    // Lấy tất cả thông tin của sale hiện tại (cursor: contract_and_token_id)
+    pub fn get_sales(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Sale> {
+        paginate_tree_map(&self.sale_ids, cursor, limit, |contract_and_token_id| {
+            self.sales.get(contract_and_token_id)
+        })
     }
 
-    // Lấy tất cả thông tin sale của owner_id (có pagination)
+    // Lấy tất cả thông tin sale của owner_id (cursor: contract_and_token_id)
     pub fn get_sales_by_owner_id(
         &self,
         account_id: AccountId,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Sale> {
+    ) -> Page<Sale> {
         // Lấy tất cả token của account_id
         let tokens_by_owner_id = self.tokens_by_owner_id.get(&account_id);
         let contract_token_ids = if let Some(tokens_by_owner_id) = tokens_by_owner_id {
             tokens_by_owner_id
         } else {
-            return vec![];
+            return Page { items: vec![], next_cursor: None };
         };
 
         // Lấy danh sách thông tin các token đang sale
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        contract_token_ids
-            .as_vector()
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(0) as usize)
-            .map(|contract_token_ids| self.sales.get(&contract_token_ids).unwrap())
-            .collect()
+        paginate_keys(contract_token_ids.iter(), cursor, limit, |contract_and_token_id| {
+            self.sales.get(contract_and_token_id)
+        })
     }
 
-    // Get Sales by Collection Name (with pagination)
+    // Get Sales by Collection Name (cursor: contract_and_token_id)
+    // Đọc index của Collection, nên mỗi trang đủ `limit` Sales (trừ trang cuối)
     pub fn get_sales_by_collection_name(
         &self,
         collection_name: CollectionName,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Sale> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        let mut result = Vec::<Sale>::new();
-
-        let sales_set: Vec<Sale> = self.sales
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(0) as usize)
-            .map(|(_contract_and_token_id, sale)| sale)
-            .collect();
-
-        for sale in sales_set {
-            if sale.collection_name == collection_name {
-                result.push(sale);
-            }
-        }
+    ) -> Page<Sale> {
+        let sales_by_collection = if let Some(sales_by_collection) = self.sales_by_collection.get(&collection_name) {
+            sales_by_collection
+        } else {
+            return Page { items: vec![], next_cursor: None };
+        };
 
-        result
+        paginate_tree_map(&sales_by_collection, cursor, limit, |contract_and_token_id| {
+            self.sales.get(contract_and_token_id)
+        })
     }
 
-    // Lấy tất cả thông tin sale của contract_id (có pagination)
+    // Lấy tất cả thông tin sale của contract_id (cursor: token_id)
     pub fn get_sales_by_contract_id(
         &self,
         contract_id: NFTContractId,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Sale> {
+    ) -> Page<Sale> {
         // Lấy tất cả token của contract_id
         let tokens_by_contract_id = self.tokens_by_contract_id.get(&contract_id);
 
         let token_ids = if let Some(tokens_by_contract_id) = tokens_by_contract_id {
             tokens_by_contract_id
         } else {
-            return vec![];
+            return Page { items: vec![], next_cursor: None };
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        token_ids
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(0) as usize)
-            .map(|token_id| {
-                self.sales
-                    // format để chuyển từ dạng TokenId sang ContractAndTokenId: <contract_id>.<token_id>
-                    // TokenId: ZNG_NFT#01
-                    // ContractTokenId: nft.duongnh.testnet.ZNG_NFT#01
-                    .get(&format!("{}{}{}", contract_id, ".", token_id))
-                    .unwrap()
-            })
-            .collect()
+        paginate_keys(token_ids.iter(), cursor, limit, |token_id| {
+            self.sales
+                // format để chuyển từ dạng TokenId sang ContractAndTokenId: <contract_id>.<token_id>
+                // TokenId: ZNG_NFT#01
+                // ContractTokenId: nft.duongnh.testnet.ZNG_NFT#01
+                .get(&format!("{}{}{}", contract_id, ".", token_id))
+        })
     }
 }
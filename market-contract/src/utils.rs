@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use crate::*;
 
 // Yêu cầu người dùng deposit đúng 1 yoctoNear khi gọi hàm
@@ -16,4 +18,83 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     // We hash the account Id and return it as
     hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
     hash
-}
\ No newline at end of file
+}
+// ----------------------------------- Cursor pagination -----------------------------------
+// Limit của 1 trang: mặc định DEFAULT_PAGE_LIMIT, trong khoảng [1, MAX_PAGE_LIMIT]
+pub(crate) fn page_limit(limit: Option<u64>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT) as usize
+}
+
+// Lấy 1 trang từ các keys đã sort tăng dần, bỏ qua các keys mà get -> None (đã bị xoá hoặc bị lọc)
+fn page_from_sorted_keys<K: ToString, T>(
+    sorted_keys: impl Iterator<Item = K>,
+    limit: Option<u64>,
+    mut get: impl FnMut(&K) -> Option<T>,
+) -> Page<T> {
+    let limit = page_limit(limit);
+    let mut items = Vec::new();
+    let mut last_key: Option<K> = None;
+
+    for key in sorted_keys {
+        if let Some(item) = get(&key) {
+            // Trang đã đủ và vẫn còn item -> Trả về cursor để lấy trang tiếp theo
+            if items.len() == limit {
+                return Page {
+                    items,
+                    next_cursor: last_key.map(|key| key.to_string()),
+                };
+            }
+            items.push(item);
+            last_key = Some(key);
+        }
+    }
+
+    Page {
+        items,
+        next_cursor: None,
+    }
+}
+
+fn parse_cursor<K: FromStr>(cursor: &str) -> K {
+    cursor
+        .parse()
+        .unwrap_or_else(|_| env::panic(b"Invalid cursor"))
+}
+
+// Cursor pagination trên 1 tập keys không có thứ tự (UnorderedSet, keys của UnorderedMap)
+// Sort các keys -> Lấy các keys sau cursor, nên thứ tự không đổi khi có Sale bị xoá (swap-remove)
+// Đọc toàn bộ set ở mỗi trang -> Chỉ dùng cho các set theo owner, theo contract của layout V1
+pub(crate) fn paginate_keys<K: Ord + ToString + FromStr, T>(
+    keys: impl Iterator<Item = K>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&K) -> Option<T>,
+) -> Page<T> {
+    let cursor: Option<K> = cursor.map(|cursor| parse_cursor(&cursor));
+    let mut sorted_keys: Vec<K> = keys
+        .filter(|key| cursor.as_ref().is_none_or(|cursor| key > cursor))
+        .collect();
+    sorted_keys.sort();
+
+    page_from_sorted_keys(sorted_keys.into_iter(), limit, get)
+}
+
+// Cursor pagination trên các keys của 1 TreeMap (đã có thứ tự)
+// Chỉ đọc các keys sau cursor -> 1 trang tốn O(limit) lần tìm key tiếp theo
+pub(crate) fn paginate_tree_map<K, V, T>(
+    map: &TreeMap<K, V>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&K) -> Option<T>,
+) -> Page<T>
+where
+    K: Ord + Clone + ToString + FromStr + BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    let entries: Box<dyn Iterator<Item = (K, V)>> = match cursor {
+        Some(cursor) => Box::new(map.iter_from(parse_cursor(&cursor))),
+        None => Box::new(map.iter()),
+    };
+
+    page_from_sorted_keys(entries.map(|(key, _)| key), limit, get)
+}
@@ -0,0 +1,139 @@
+// Migrate the baseline (V1) state layout to the current layout
+use market_contract::*;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::{LookupMap, UnorderedMap};
+use near_sdk::json_types::{ValidAccountId, U128};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, testing_env, MockedBlockchain};
+use std::convert::TryFrom;
+
+const NFT_CONTRACT: &str = "nft.testnet";
+
+fn set_caller(account_id: &str) {
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(ValidAccountId::try_from("market.testnet").unwrap())
+        .predecessor_account_id(ValidAccountId::try_from(account_id).unwrap());
+    testing_env!(context.build());
+}
+
+// Write a V1 state (no STATE_VERSION key) with 1 storage deposit and alice's Sales of NFTs 1..=sales
+// NFT 2 belongs to "Art", the other NFTs to "Game" (only `sales` is written, migrate_state only reads it)
+fn write_v1_state(sales: TokenId) {
+    set_caller("owner.testnet");
+
+    let mut storage_deposit_account =
+        LookupMap::new(StorageKey::StorageDepositKey.try_to_vec().unwrap());
+    storage_deposit_account.insert(&"alice.testnet".to_string(), &1_000);
+
+    let mut contract = MarketContractV1 {
+        owner_id: "owner.testnet".to_string(),
+        sales: UnorderedMap::new(StorageKey::SaleKey.try_to_vec().unwrap()),
+        tokens_by_owner_id: LookupMap::new(StorageKey::ByOwnerIdKey.try_to_vec().unwrap()),
+        tokens_by_contract_id: LookupMap::new(StorageKey::ByContractIdKey.try_to_vec().unwrap()),
+        storage_deposit_account,
+    };
+
+    for token_id in 1..=sales {
+        let contract_and_token_id = format!("{}.{}", NFT_CONTRACT, token_id);
+        let sale = Sale {
+            owner_id: "alice.testnet".to_string(),
+            approval_id: 0,
+            nft_contract_id: NFT_CONTRACT.to_string(),
+            token_id,
+            token_by_template_id: token_id,
+            collection_id: 0,
+            collection_name: if token_id == 2 { "Art" } else { "Game" }.to_string(),
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            template_id: 0,
+            sale_conditions: U128(1000),
+        };
+        contract.sales.insert(&contract_and_token_id, &sale);
+    }
+    env::state_write(&contract);
+}
+
+fn sale_token_ids(page: &Page<Sale>) -> Vec<TokenId> {
+    page.items.iter().map(|sale| sale.token_id).collect()
+}
+
+#[test]
+fn migrate_keeps_the_v1_state() {
+    write_v1_state(0);
+    set_caller("market.testnet");
+    let contract = MarketContract::migrate();
+
+    assert_eq!(contract.get_state_version(), CURRENT_STATE_VERSION);
+    assert_eq!(contract.owner_id, "owner.testnet");
+    assert_eq!(
+        contract.storage_balance_of(Some("alice.testnet".to_string())),
+        U128(1_000)
+    );
+    assert_eq!(contract.get_supply_sales(), U128(0));
+
+    // No Sales: 1 migrate_state call still clears the progress
+    let mut contract = contract;
+    set_caller("owner.testnet");
+    assert_eq!(contract.migrate_state(None).step, MigrationStep::Done);
+    assert!(contract.get_migration_progress().is_none());
+}
+
+#[test]
+fn migrate_state_indexes_the_v1_sales_in_batches() {
+    write_v1_state(5);
+    set_caller("market.testnet");
+    let mut contract = MarketContract::migrate();
+
+    let progress = contract.get_migration_progress().unwrap();
+    assert_eq!(progress.step, MigrationStep::Sales);
+    assert_eq!(progress.sales, 5);
+
+    set_caller("owner.testnet");
+    let progress = contract.migrate_state(Some(3));
+    assert_eq!(progress.step, MigrationStep::Sales);
+    assert_eq!(progress.next_index, 3);
+    let progress = contract.migrate_state(Some(3));
+    assert_eq!(progress.step, MigrationStep::Done);
+    assert!(contract.get_migration_progress().is_none());
+
+    let page = contract.get_sales(None, Some(4));
+    assert_eq!(sale_token_ids(&page), vec![1, 2, 3, 4]);
+    let page = contract.get_sales(page.next_cursor, Some(4));
+    assert_eq!(sale_token_ids(&page), vec![5]);
+    let page = contract.get_sales_by_collection_name("Art".to_string(), None, None);
+    assert_eq!(sale_token_ids(&page), vec![2]);
+}
+
+#[test]
+#[should_panic(expected = "The contract state is being migrated")]
+fn sales_cannot_be_removed_until_the_state_is_migrated() {
+    write_v1_state(2);
+    set_caller("market.testnet");
+    let mut contract = MarketContract::migrate();
+
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(ValidAccountId::try_from("market.testnet").unwrap())
+        .predecessor_account_id(ValidAccountId::try_from("alice.testnet").unwrap())
+        .attached_deposit(1)
+        .build());
+    contract.remove_sale(NFT_CONTRACT.to_string(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this contract can migrate the state")]
+fn only_the_owner_can_migrate_the_state() {
+    write_v1_state(1);
+    set_caller("market.testnet");
+    let mut contract = MarketContract::migrate();
+
+    set_caller("alice.testnet");
+    contract.migrate_state(None);
+}
+
+#[test]
+fn new_contract_uses_the_current_state_version() {
+    set_caller("owner.testnet");
+    let contract = MarketContract::new("owner.testnet".to_string());
+    assert_eq!(contract.get_state_version(), CURRENT_STATE_VERSION);
+}
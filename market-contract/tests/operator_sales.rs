@@ -0,0 +1,171 @@
+// Sales created from an operator approval: nft_on_approve_operator -> nft_token_detail -> resolve_operator_sales
+use market_contract::*;
+use near_sdk::json_types::{ValidAccountId, U128};
+use near_sdk::serde_json::{self, json};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, Balance, MockedBlockchain, PromiseResult};
+use std::convert::TryFrom;
+
+const NFT_CONTRACT: &str = "nft.testnet";
+const MARKET_CONTRACT: &str = "market.testnet";
+
+fn set_caller_with_results(
+    predecessor_id: &str,
+    signer_id: &str,
+    attached_deposit: Balance,
+    promise_results: Vec<PromiseResult>,
+) {
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(ValidAccountId::try_from(MARKET_CONTRACT).unwrap())
+        .signer_account_id(ValidAccountId::try_from(signer_id).unwrap())
+        .predecessor_account_id(ValidAccountId::try_from(predecessor_id).unwrap())
+        .attached_deposit(attached_deposit);
+    testing_env!(
+        context.build(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        promise_results
+    );
+}
+
+fn set_caller(predecessor_id: &str, signer_id: &str, attached_deposit: Balance) {
+    set_caller_with_results(predecessor_id, signer_id, attached_deposit, vec![]);
+}
+
+// alice deposits storage for `sales` Sales
+fn setup_market(sales: u128) -> MarketContract {
+    set_caller("owner.testnet", "owner.testnet", 0);
+    let mut contract = MarketContract::new("owner.testnet".to_string());
+
+    set_caller(
+        "alice.testnet",
+        "alice.testnet",
+        sales * contract.storage_minimun_balance().0,
+    );
+    contract.storage_deposit(None);
+    contract
+}
+
+// Result of nft_token_detail on the NFT Contract
+fn token_detail(token_id: TokenId, owner_id: &str, collection_name: &str) -> PromiseResult {
+    let detail = json!({
+        "owner_id": owner_id,
+        "token_id": token_id,
+        "token_by_template_id": token_id,
+        "collection_id": 0,
+        "collection_name": collection_name,
+        "schema_id": 0,
+        "schema_name": "Weapon",
+        "template_id": 0,
+        "metadata": {},
+        "approved_account_ids": {},
+    });
+    PromiseResult::Successful(serde_json::to_vec(&detail).unwrap())
+}
+
+fn resolve(contract: &mut MarketContract, collection_name: Option<&str>, results: Vec<PromiseResult>) -> Vec<TokenId> {
+    set_caller_with_results(MARKET_CONTRACT, "alice.testnet", 0, results);
+    contract.resolve_operator_sales(
+        "alice.testnet".to_string(),
+        7,
+        collection_name.map(|name| name.to_string()),
+        U128(1000),
+        NFT_CONTRACT.to_string(),
+    )
+}
+
+#[test]
+fn operator_approval_lists_the_tokens_of_msg() {
+    let mut contract = setup_market(10);
+
+    set_caller(NFT_CONTRACT, "alice.testnet", 0);
+    contract.nft_on_approve_operator(
+        "alice.testnet".to_string(),
+        7,
+        None,
+        r#"{"token_ids": ["1", "2"], "sale_conditions": "1000"}"#.to_string(),
+    );
+
+    let token_ids = resolve(
+        &mut contract,
+        None,
+        vec![token_detail(1, "alice.testnet", "Game"), token_detail(2, "alice.testnet", "Art")],
+    );
+
+    assert_eq!(token_ids, vec![1, 2]);
+    let page = contract.get_sales_by_owner_id("alice.testnet".to_string(), None, None);
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[0].approval_id, 7);
+    assert_eq!(page.items[0].nft_contract_id, NFT_CONTRACT);
+    assert_eq!(page.items[0].sale_conditions, U128(1000));
+    assert_eq!(page.items[1].collection_name, "Art");
+    assert_eq!(
+        contract
+            .get_sales_by_contract_id(NFT_CONTRACT.to_string(), None, None)
+            .items
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn tokens_not_owned_or_missing_are_skipped() {
+    let mut contract = setup_market(10);
+
+    let token_ids = resolve(
+        &mut contract,
+        None,
+        vec![
+            token_detail(1, "bob.testnet", "Game"),
+            PromiseResult::Successful(b"null".to_vec()),
+            PromiseResult::Failed,
+            token_detail(4, "alice.testnet", "Game"),
+        ],
+    );
+
+    assert_eq!(token_ids, vec![4]);
+    assert_eq!(contract.get_supply_by_owner_id("alice.testnet".to_string()), U128(1));
+}
+
+#[test]
+fn collection_scoped_approval_only_lists_tokens_of_the_collection() {
+    let mut contract = setup_market(10);
+
+    let token_ids = resolve(
+        &mut contract,
+        Some("Game"),
+        vec![token_detail(1, "alice.testnet", "Game"), token_detail(2, "alice.testnet", "Art")],
+    );
+
+    assert_eq!(token_ids, vec![1]);
+}
+
+#[test]
+#[should_panic(expected = "Storage balance not enough for cover storage staking")]
+fn operator_approval_requires_storage_for_every_token() {
+    let mut contract = setup_market(1);
+
+    set_caller(NFT_CONTRACT, "alice.testnet", 0);
+    contract.nft_on_approve_operator(
+        "alice.testnet".to_string(),
+        7,
+        None,
+        r#"{"token_ids": ["1", "2"], "sale_conditions": "1000"}"#.to_string(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "nft_on_approve_operator should only be called via cross contract call")]
+fn operator_approval_callback_cannot_be_called_directly() {
+    let mut contract = setup_market(10);
+
+    set_caller("alice.testnet", "alice.testnet", 0);
+    contract.nft_on_approve_operator(
+        "alice.testnet".to_string(),
+        7,
+        None,
+        r#"{"token_ids": ["1"], "sale_conditions": "1000"}"#.to_string(),
+    );
+}
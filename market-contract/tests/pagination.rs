@@ -0,0 +1,137 @@
+// Cursor pagination of the Sale views
+use market_contract::*;
+use near_sdk::json_types::{ValidAccountId, U128};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, Balance, MockedBlockchain};
+use std::convert::TryFrom;
+
+const NFT_CONTRACT: &str = "nft.testnet";
+
+fn set_caller(predecessor_id: &str, signer_id: &str, attached_deposit: Balance) {
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(ValidAccountId::try_from("market.testnet").unwrap())
+        .signer_account_id(ValidAccountId::try_from(signer_id).unwrap())
+        .predecessor_account_id(ValidAccountId::try_from(predecessor_id).unwrap())
+        .attached_deposit(attached_deposit);
+    testing_env!(context.build());
+}
+
+// alice lists NFTs 1..=4 of "Game" and NFT 5 of "Art"
+fn setup_sales() -> MarketContract {
+    set_caller("owner.testnet", "owner.testnet", 0);
+    let mut contract = MarketContract::new("owner.testnet".to_string());
+
+    set_caller(
+        "alice.testnet",
+        "alice.testnet",
+        10 * contract.storage_minimun_balance().0,
+    );
+    contract.storage_deposit(None);
+
+    set_caller(NFT_CONTRACT, "alice.testnet", 0);
+    for token_id in 1..=5 {
+        let collection_name = if token_id == 5 { "Art" } else { "Game" };
+        contract.nft_on_approve(
+            token_id.to_string(),
+            "alice.testnet".to_string(),
+            0,
+            token_id,
+            0,
+            collection_name.to_string(),
+            0,
+            "Weapon".to_string(),
+            0,
+            r#"{"sale_conditions": "1000"}"#.to_string(),
+        );
+    }
+    contract
+}
+
+fn sale_token_ids(page: &Page<Sale>) -> Vec<TokenId> {
+    page.items.iter().map(|sale| sale.token_id).collect()
+}
+
+#[test]
+fn get_sales_uses_the_default_limit() {
+    let contract = setup_sales();
+
+    let page = contract.get_sales(None, None);
+    assert_eq!(sale_token_ids(&page), vec![1, 2, 3, 4, 5]);
+    assert!(page.next_cursor.is_none());
+}
+
+#[test]
+fn cursor_is_stable_when_sales_are_removed() {
+    let mut contract = setup_sales();
+
+    let first_page = contract.get_sales_by_owner_id("alice.testnet".to_string(), None, Some(2));
+    assert_eq!(sale_token_ids(&first_page), vec![1, 2]);
+    assert_eq!(first_page.next_cursor, Some(format!("{}.2", NFT_CONTRACT)));
+
+    // Removing a Sale swaps the last Sale of the owner into its place
+    set_caller("alice.testnet", "alice.testnet", 1);
+    contract.remove_sale(NFT_CONTRACT.to_string(), 1);
+
+    let second_page = contract.get_sales_by_owner_id(
+        "alice.testnet".to_string(),
+        first_page.next_cursor,
+        Some(2),
+    );
+    assert_eq!(sale_token_ids(&second_page), vec![3, 4]);
+
+    let last_page = contract.get_sales_by_owner_id(
+        "alice.testnet".to_string(),
+        second_page.next_cursor,
+        Some(2),
+    );
+    assert_eq!(sale_token_ids(&last_page), vec![5]);
+    assert!(last_page.next_cursor.is_none());
+}
+
+#[test]
+fn sales_by_collection_are_filtered_before_the_limit() {
+    let contract = setup_sales();
+
+    let page = contract.get_sales_by_collection_name("Art".to_string(), None, Some(1));
+    assert_eq!(sale_token_ids(&page), vec![5]);
+    assert!(page.next_cursor.is_none());
+
+    let page = contract.get_sales_by_collection_name("Game".to_string(), None, Some(3));
+    assert_eq!(sale_token_ids(&page), vec![1, 2, 3]);
+    let page = contract.get_sales_by_collection_name("Game".to_string(), page.next_cursor, Some(3));
+    assert_eq!(sale_token_ids(&page), vec![4]);
+}
+
+#[test]
+fn removed_sales_leave_the_collection_index() {
+    let mut contract = setup_sales();
+
+    set_caller("alice.testnet", "alice.testnet", 1);
+    contract.remove_sale(NFT_CONTRACT.to_string(), 5);
+    contract.remove_sale(NFT_CONTRACT.to_string(), 2);
+
+    let page = contract.get_sales_by_collection_name("Art".to_string(), None, None);
+    assert!(page.items.is_empty());
+    assert!(page.next_cursor.is_none());
+    let page = contract.get_sales_by_collection_name("Game".to_string(), None, None);
+    assert_eq!(sale_token_ids(&page), vec![1, 3, 4]);
+    assert_eq!(
+        sale_token_ids(&contract.get_sales(None, None)),
+        vec![1, 3, 4]
+    );
+}
+
+#[test]
+fn sales_by_contract_id_use_token_id_cursors() {
+    let contract = setup_sales();
+
+    let page = contract.get_sales_by_contract_id(NFT_CONTRACT.to_string(), None, Some(4));
+    assert_eq!(sale_token_ids(&page), vec![1, 2, 3, 4]);
+    assert_eq!(page.next_cursor, Some("4".to_string()));
+    assert_eq!(page.items[0].sale_conditions, U128(1000));
+
+    let page =
+        contract.get_sales_by_contract_id(NFT_CONTRACT.to_string(), page.next_cursor, Some(4));
+    assert_eq!(sale_token_ids(&page), vec![5]);
+}
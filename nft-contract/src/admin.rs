@@ -0,0 +1,171 @@
+// Roles (admin, pauser, moderator) và các công tắc pause của Contract
+use crate::*;
+
+// ----------------------------------- Role Enum -----------------------------------
+// The Contract's owner always has the permissions of every role
+/**
+ * - Admin: pause + unpause, moderate Collections
+ * - Pauser: pause only (unpause needs an admin or the owner)
+ * - Moderator: moderate Collections
+ */
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Pauser,
+    Moderator,
+}
+
+// Features that can be paused separately
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFeature {
+    Mint,     // Mint NFTs (nft_mint, airdrops, vouchers, blends, ...)
+    Transfer, // Transfer NFTs (nft_transfer, nft_transfer_call, nft_batch_transfer)
+    Drop,     // Create, update and claim Drop Sales
+    Lootbox,  // Create, mint and open Lootboxes
+}
+
+impl PauseFeature {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseFeature::Mint => "mint",
+            PauseFeature::Transfer => "transfer",
+            PauseFeature::Drop => "drop",
+            PauseFeature::Lootbox => "lootbox",
+        }
+    }
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Owner cấp role cho 1 account
+    #[payable]
+    pub fn grant_role(&mut self, account_id: AccountId, role: Role) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        self.assert_contract_owner();
+
+        let mut roles = self.roles_by_account.get(&account_id).unwrap_or_default();
+        if !roles.contains(&role) {
+            roles.push(role);
+            self.roles_by_account.insert(&account_id, &roles);
+        }
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage - before_storage_usage);
+    }
+
+    // Owner thu hồi role của 1 account
+    #[payable]
+    pub fn revoke_role(&mut self, account_id: AccountId, role: Role) {
+        assert_one_yocto();
+        self.assert_contract_owner();
+
+        if let Some(mut roles) = self.roles_by_account.get(&account_id) {
+            roles.retain(|account_role| account_role != &role);
+            if roles.is_empty() {
+                self.roles_by_account.remove(&account_id);
+            } else {
+                self.roles_by_account.insert(&account_id, &roles);
+            }
+        }
+    }
+
+    // Pause các features (owner, admin hoặc pauser)
+    #[payable]
+    pub fn pause(&mut self, features: Vec<PauseFeature>, memo: Option<String>) {
+        assert_one_yocto();
+        self.assert_has_role(&[Role::Admin, Role::Pauser]);
+
+        for feature in features.iter() {
+            if !self.paused_features.contains(feature) {
+                self.paused_features.push(*feature);
+            }
+        }
+
+        // -------------------------------------------------------------------
+        // CONTRACT PAUSE LOG
+        let contract_pause_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ContractPause(vec![ContractPauseLog {
+                account_id: env::predecessor_account_id(),
+                features: features
+                    .iter()
+                    .map(|feature| feature.as_str().to_string())
+                    .collect(),
+                memo,
+            }]),
+        };
+        env::log(contract_pause_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Unpause các features (owner hoặc admin)
+    #[payable]
+    pub fn unpause(&mut self, features: Vec<PauseFeature>, memo: Option<String>) {
+        assert_one_yocto();
+        self.assert_has_role(&[Role::Admin]);
+
+        self.paused_features
+            .retain(|feature| !features.contains(feature));
+
+        // -------------------------------------------------------------------
+        // CONTRACT UNPAUSE LOG
+        let contract_unpause_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::ContractUnpause(vec![ContractPauseLog {
+                account_id: env::predecessor_account_id(),
+                features: features
+                    .iter()
+                    .map(|feature| feature.as_str().to_string())
+                    .collect(),
+                memo,
+            }]),
+        };
+        env::log(contract_unpause_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    //  -------------------------------------- ENUMERATION --------------------------------------
+    // Lấy danh sách roles của 1 account (không tính owner của Contract)
+    pub fn get_roles_of(&self, account_id: AccountId) -> Vec<Role> {
+        self.roles_by_account.get(&account_id).unwrap_or_default()
+    }
+
+    // Check account có role hay không (owner của Contract có tất cả roles)
+    pub fn has_role(&self, account_id: AccountId, role: Role) -> bool {
+        account_id == self.owner_id || self.get_roles_of(account_id).contains(&role)
+    }
+
+    // Lấy danh sách tất cả accounts có role (cursor: account id)
+    pub fn get_all_role_accounts(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<(AccountId, Vec<Role>)> {
+        paginate_tree_map(&self.roles_by_account, cursor, limit, |account_id| {
+            let roles = self.roles_by_account.get(account_id)?;
+            Some((account_id.clone(), roles))
+        })
+    }
+
+    // Lấy danh sách các features đang bị pause
+    pub fn get_paused_features(&self) -> Vec<PauseFeature> {
+        self.paused_features.clone()
+    }
+
+    // Check 1 feature có đang bị pause không
+    pub fn is_paused(&self, feature: PauseFeature) -> bool {
+        self.paused_features.contains(&feature)
+    }
+}
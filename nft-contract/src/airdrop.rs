@@ -0,0 +1,225 @@
+use crate::*;
+
+// Max number of NFTs minted by 1 airdrop_continue call (to fit in the gas of 1 call)
+// Mỗi NFT thêm 1 node vào các TreeMap index (tokens_per_template, tokens_per_collection, holders) -> ~10-20 TGas
+const AIRDROP_TOKENS_PER_CALL: u32 = 10;
+
+// ----------------------------------- Airdrop Job Struct -----------------------------------
+// 1 big airdrop: the recipients are registered first, then minted in chunks by airdrop_continue
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AirdropJob {
+    pub job_id: AirdropJobId,            // Id of the Airdrop job
+    pub owner_id: AccountId,             // Owner of the Collection that created the job
+    pub collection_name: CollectionName, // Collection of the airdropped NFTs
+    pub template_id: TemplateId,         // Template of the airdropped NFTs
+    pub total_recipients: u32,           // Number of registered recipients
+    pub total_tokens: u32,               // Number of NFTs to mint
+    pub processed_recipients: u32,       // Number of recipients that received their NFTs
+    pub minted_tokens: u32,              // Number of NFTs minted
+    pub storage_balance: U128, // NEAR prepaid for the storage of the NFTs that are not minted yet
+    pub is_finished: bool,     // All the recipients have received their NFTs
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Mint NFTs of 1 Template to many receivers in 1 call, 1 nft_mint event per receiver
+    /**
+     * - Only the Collection's owner can airdrop
+     * - recipients: [(receiver_id, number of NFTs)]
+     * - Yêu cầu user nạp tiền để cover phí lưu trữ, refund lại NEAR deposit thừa
+     */
+    #[payable]
+    pub fn nft_airdrop(
+        &mut self,
+        template_id: TemplateId,
+        recipients: Vec<(AccountId, u32)>,
+    ) -> Vec<TokenId> {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let template = self
+            .templates_by_id
+            .get(&template_id)
+            .expect("Template does not exist");
+        self.assert_airdrop_owner(&template);
+
+        let token_ids = self.internal_airdrop_mint(&template, &recipients);
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage - before_storage_usage);
+
+        token_ids
+    }
+
+    // Register an Airdrop job, the NFTs are minted later by airdrop_continue
+    /**
+     * - Only the Collection's owner can create an Airdrop job
+     * - The deposit covers the storage of the recipients list + the estimated storage of all the NFTs
+     */
+    #[payable]
+    pub fn create_airdrop_job(
+        &mut self,
+        template_id: TemplateId,
+        recipients: Vec<(AccountId, u32)>,
+    ) -> AirdropJob {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let template = self
+            .templates_by_id
+            .get(&template_id)
+            .expect("Template does not exist");
+        self.assert_airdrop_owner(&template);
+
+        let job_id = self.airdrop_job_id_counter;
+        self.airdrop_job_id_counter += 1;
+
+        let mut job = AirdropJob {
+            job_id,
+            owner_id: env::predecessor_account_id(),
+            collection_name: template.collection_name.clone(),
+            template_id,
+            total_recipients: 0,
+            total_tokens: 0,
+            processed_recipients: 0,
+            minted_tokens: 0,
+            storage_balance: U128(0),
+            is_finished: false,
+        };
+        let tokens_storage_cost = self.internal_add_airdrop_recipients(&mut job, recipients);
+        self.airdrop_jobs_by_id.insert(&job_id, &job);
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit_with_price(
+            after_storage_usage - before_storage_usage,
+            tokens_storage_cost,
+        );
+
+        job
+    }
+
+    // Add more recipients to an Airdrop job (when the list doesn't fit in 1 call)
+    // The deposit covers the storage of the new recipients + the estimated storage of their NFTs
+    #[payable]
+    pub fn airdrop_job_add_recipients(
+        &mut self,
+        job_id: AirdropJobId,
+        recipients: Vec<(AccountId, u32)>,
+    ) -> AirdropJob {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let mut job = self
+            .airdrop_jobs_by_id
+            .get(&job_id)
+            .expect("Airdrop job does not exist");
+        assert_eq!(
+            job.owner_id,
+            env::predecessor_account_id(),
+            "Only owner of this Airdrop job can add recipients"
+        );
+        assert!(!job.is_finished, "This Airdrop job has finished");
+
+        let tokens_storage_cost = self.internal_add_airdrop_recipients(&mut job, recipients);
+        self.airdrop_jobs_by_id.insert(&job_id, &job);
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit_with_price(
+            after_storage_usage - before_storage_usage,
+            tokens_storage_cost,
+        );
+
+        job
+    }
+
+    // Mint the NFTs of the next recipients of an Airdrop job (max AIRDROP_TOKENS_PER_CALL NFTs per call)
+    /**
+     * - Anyone can continue a job, the storage is paid by job.storage_balance
+     * - The attached deposit is added to job.storage_balance (if the estimate was too low)
+     * - When all recipients are processed: delete the recipients list, refund the rest of storage_balance to the owner
+     */
+    #[payable]
+    pub fn airdrop_continue(&mut self, job_id: AirdropJobId) -> AirdropJob {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let mut job = self
+            .airdrop_jobs_by_id
+            .get(&job_id)
+            .expect("Airdrop job does not exist");
+        assert!(!job.is_finished, "This Airdrop job has finished");
+
+        let template = self
+            .templates_by_id
+            .get(&job.template_id)
+            .expect("Template does not exist");
+        let mut recipients_list = self
+            .airdrop_recipients_per_job
+            .get(&job_id)
+            .expect("Airdrop job does not have recipients");
+
+        // Lấy chunk recipients tiếp theo: ít nhất 1 recipient, tối đa AIRDROP_TOKENS_PER_CALL NFTs
+        let mut chunk = Vec::<(AccountId, u32)>::new();
+        let mut chunk_tokens = 0;
+        let mut index = job.processed_recipients as u64;
+        while index < recipients_list.len() {
+            let (receiver_id, mint_number) = recipients_list.get(index).unwrap();
+            if !chunk.is_empty() && chunk_tokens + mint_number > AIRDROP_TOKENS_PER_CALL {
+                break;
+            }
+            chunk_tokens += mint_number;
+            chunk.push((receiver_id, mint_number));
+            index += 1;
+        }
+
+        self.internal_airdrop_mint(&template, &chunk);
+        job.processed_recipients += chunk.len() as u32;
+        job.minted_tokens += chunk_tokens;
+
+        let mut storage_balance = u128::from(job.storage_balance) + env::attached_deposit();
+
+        job.is_finished = job.processed_recipients == job.total_recipients;
+        if job.is_finished {
+            // Xoá danh sách recipients
+            recipients_list.clear();
+            self.airdrop_recipients_per_job.remove(&job_id);
+        }
+
+        // Pay the storage used by this call with the prepaid storage_balance
+        let after_storage_usage = env::storage_usage();
+        if after_storage_usage > before_storage_usage {
+            let storage_cost = Balance::from(after_storage_usage - before_storage_usage)
+                * env::storage_byte_cost();
+            assert!(
+                storage_balance >= storage_cost,
+                "Not enough prepaid storage, attach {} yoctoNear to continue",
+                storage_cost - storage_balance
+            );
+            storage_balance -= storage_cost;
+        } else {
+            storage_balance += Balance::from(before_storage_usage - after_storage_usage)
+                * env::storage_byte_cost();
+        }
+
+        if job.is_finished {
+            // Refund the rest of storage_balance to the owner
+            job.storage_balance = U128(0);
+            if storage_balance > 0 {
+                Promise::new(job.owner_id.clone()).transfer(storage_balance);
+            }
+        } else {
+            job.storage_balance = U128(storage_balance);
+        }
+        self.airdrop_jobs_by_id.insert(&job_id, &job);
+
+        job
+    }
+
+    //  -------------------------------------- ENUMERATION --------------------------------------
+    // Lấy Airdrop job theo id (progress: processed_recipients / total_recipients)
+    pub fn get_airdrop_job(&self, job_id: AirdropJobId) -> AirdropJob {
+        self.airdrop_jobs_by_id
+            .get(&job_id)
+            .expect("Airdrop job does not exist")
+    }
+}
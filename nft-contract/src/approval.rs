@@ -8,7 +8,7 @@ const NO_DEPOSIT: Balance = 0;
 
 pub trait NonFungibleTokenApproval {
     // Let other accounts (Marketplace) to transfer token to another
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>);
+    fn nft_approve(&mut self, token_id: JsonTokenId, account_id: AccountId, msg: Option<String>);
     // Let other accounts (Marketplace) to transfer token of 1 Collection to another
     fn nft_approve_for_collection(
         &mut self,
@@ -20,14 +20,42 @@ pub trait NonFungibleTokenApproval {
     // If approve account_id is valid -> return true, else return false
     fn nft_is_approved(
         &self,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         approved_account_id: AccountId,
         approval_id: Option<u64>,
     ) -> bool;
     // Delete approval of 1 account from transfering this token
-    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId);
+    fn nft_revoke(&mut self, token_id: JsonTokenId, account_id: AccountId);
     // Delete approval of all accounts from transfering this token
-    fn nft_revoke_all(&mut self, token_id: TokenId);
+    fn nft_revoke_all(&mut self, token_id: JsonTokenId);
+    // Check if the account has the approval to transfer the Tokens of 1 Collection or not
+    fn nft_is_approved_for_collection(
+        &self,
+        collection_name: CollectionName,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool;
+    // Delete approval of 1 account from transfering the Tokens of 1 Collection
+    fn nft_revoke_for_collection(&mut self, collection_name: CollectionName, account_id: AccountId);
+    // Delete approval of all accounts from transfering the Tokens of 1 Collection
+    fn nft_revoke_all_for_collection(&mut self, collection_name: CollectionName);
+    // Let an operator transfer all the Tokens of the caller (or only the Tokens of 1 Collection)
+    fn nft_approve_operator(
+        &mut self,
+        operator_id: AccountId,
+        collection_name: Option<CollectionName>,
+        msg: Option<String>,
+    );
+    // Delete approval of an operator from transfering the Tokens of the caller
+    fn nft_revoke_operator(&mut self, operator_id: AccountId);
+    // Check if the operator can transfer the Tokens of owner_id (of 1 Collection if collection_name is Some)
+    fn nft_is_approved_operator(
+        &self,
+        owner_id: AccountId,
+        operator_id: AccountId,
+        collection_name: Option<CollectionName>,
+        approval_id: Option<u64>,
+    ) -> bool;
 }
 
 #[ext_contract(ext_non_fungible_token_approval_receiver)]
@@ -38,7 +66,7 @@ pub trait NonFungibleTokenApprovalReceiver {
     // nft_on_approve on Contract A -> A do some actions (sale, update information, ...)
     fn nft_on_approve(
         &mut self,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         owner_id: AccountId,
         approval_id: u64,
         token_by_template_id: TokenId, // Number of NFT belongs to the Template
@@ -57,6 +85,14 @@ pub trait NonFungibleTokenApprovalReceiver {
         approval_id: u64,
         msg: String,
     );
+
+    fn nft_on_approve_operator(
+        &mut self,
+        owner_id: AccountId,
+        approval_id: u64,
+        collection_name: Option<CollectionName>,
+        msg: String,
+    );
 }
 
 #[near_bindgen]
@@ -66,11 +102,14 @@ impl NonFungibleTokenApproval for NFTContract {
     // Note: Because this function will increase the data inside the Contract -> Add payable so the user have to deposit to cover storage
     // Account ID => market contract id
     #[payable]
-    fn nft_approve(&mut self, token_id: TokenId, account_id: AccountId, msg: Option<String>) {
+    fn nft_approve(&mut self, token_id: JsonTokenId, account_id: AccountId, msg: Option<String>) {
         assert_at_least_one_yocto();
+        let token_id = parse_token_id(&token_id);
 
         // Check if the token has exists or not
         let mut token = self.tokens_by_id.get(&token_id).expect("Not found token");
+        // Token đang bị lock (staking) thì không được approve
+        self.assert_token_not_locked(&token_id);
 
         // check if the sender id token's owner or not
         // only owner can add approval to other account
@@ -106,7 +145,7 @@ impl NonFungibleTokenApproval for NFTContract {
         // msg include: price, action, function, ...
         if let Some(msg) = msg {
             ext_non_fungible_token_approval_receiver::nft_on_approve(
-                token_id,
+                token_id.to_string(),
                 token.owner_id,
                 approval_id,
                 token.token_by_template_id.clone(),
@@ -196,19 +235,22 @@ impl NonFungibleTokenApproval for NFTContract {
     // Check if this account is able to transfer the NFT or not
     fn nft_is_approved(
         &self,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         approved_account_id: AccountId,
         approval_id: Option<u64>,
     ) -> bool {
-        let token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        let token = self
+            .tokens_by_id
+            .get(&parse_token_id(&token_id))
+            .expect("Token not found");
         let approval = token.approved_account_ids.get(&approved_account_id);
 
         // If has existed inside the list approved_account_ids -> Check if approval_id is valid or not
+        // approval_id = None -> Only check if the account is approved
         if let Some(approval) = approval {
-            if approval == &approval_id.unwrap() {
-                true
-            } else {
-                false
+            match approval_id {
+                Some(approval_id) => approval == &approval_id,
+                None => true,
             }
         } else {
             false
@@ -217,8 +259,9 @@ impl NonFungibleTokenApproval for NFTContract {
 
     // Note: When deleting 1 account from approved_list_ids -> Refund storage data fee that the user has deposited
     #[payable]
-    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+    fn nft_revoke(&mut self, token_id: JsonTokenId, account_id: AccountId) {
         assert_one_yocto();
+        let token_id = parse_token_id(&token_id);
 
         let mut token = self.tokens_by_id.get(&token_id).expect("Not found token");
         let sender_id = env::predecessor_account_id();
@@ -238,8 +281,9 @@ impl NonFungibleTokenApproval for NFTContract {
     }
 
     #[payable]
-    fn nft_revoke_all(&mut self, token_id: TokenId) {
+    fn nft_revoke_all(&mut self, token_id: JsonTokenId) {
         assert_one_yocto();
+        let token_id = parse_token_id(&token_id);
 
         let mut token = self.tokens_by_id.get(&token_id).expect("Not found token");
         let sender_id = env::predecessor_account_id();
@@ -258,4 +302,192 @@ impl NonFungibleTokenApproval for NFTContract {
             self.tokens_by_id.insert(&token_id, &token);
         }
     }
+
+    // Check if this account is able to transfer the NFTs of the Collection's owner or not
+    fn nft_is_approved_for_collection(
+        &self,
+        collection_name: CollectionName,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Not found collection");
+        let approval = collection.approved_account_ids.get(&approved_account_id);
+
+        // approval_id = None -> Only check if the account is approved
+        if let Some(approval) = approval {
+            match approval_id {
+                Some(approval_id) => approval == &approval_id,
+                None => true,
+            }
+        } else {
+            false
+        }
+    }
+
+    // Note: When deleting 1 account from approved_list_ids -> Refund storage data fee that the user has deposited
+    #[payable]
+    fn nft_revoke_for_collection(
+        &mut self,
+        collection_name: CollectionName,
+        account_id: AccountId,
+    ) {
+        assert_one_yocto();
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Not found collection");
+        let sender_id = env::predecessor_account_id();
+        // Check if the person who call revoke() is the owner of this Collection or not
+        assert_eq!(
+            &sender_id, &collection.owner_id,
+            "Only owner of the Collection can call revoke function"
+        );
+
+        // If revoke success
+        if collection
+            .approved_account_ids
+            .remove(&account_id)
+            .is_some()
+        {
+            // Refund the deposited amount to cover storage of the user before
+            refund_approved_account_ids_iter(sender_id, [account_id].iter());
+            // Update list collections
+            self.collections_by_name
+                .insert(&collection_name, &collection);
+            self.collections_by_id
+                .insert(&collection.collection_id, &collection);
+        }
+    }
+
+    #[payable]
+    fn nft_revoke_all_for_collection(&mut self, collection_name: CollectionName) {
+        assert_one_yocto();
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Not found collection");
+        let sender_id = env::predecessor_account_id();
+        // Check if the person who call revoke() is the owner of this Collection or not
+        assert_eq!(
+            &sender_id, &collection.owner_id,
+            "Only owner of the Collection can call revoke function"
+        );
+
+        if !collection.approved_account_ids.is_empty() {
+            // Refund the deposited amount of everyone when calling revoke_all()
+            refund_approved_account_ids(sender_id, &collection.approved_account_ids);
+            // Delete all approved account of this Collection
+            collection.approved_account_ids.clear();
+            // Update list collections
+            self.collections_by_name
+                .insert(&collection_name, &collection);
+            self.collections_by_id
+                .insert(&collection.collection_id, &collection);
+        }
+    }
+
+    // Add the operator to the operators of the caller
+    // Note: Because this function will increase the data inside the Contract -> Add payable so the user have to deposit to cover storage
+    // Operator ID => market contract id
+    #[payable]
+    fn nft_approve_operator(
+        &mut self,
+        operator_id: AccountId,
+        collection_name: Option<CollectionName>,
+        msg: Option<String>,
+    ) {
+        assert_at_least_one_yocto();
+        let owner_id = env::predecessor_account_id();
+
+        if let Some(collection_name) = &collection_name {
+            assert!(
+                self.collections_by_name.get(collection_name).is_some(),
+                "Not found collection"
+            );
+        }
+
+        // Approving
+        let approval = OperatorApproval {
+            approval_id: self.operator_approval_id_counter,
+            collection_name,
+        };
+        self.operator_approval_id_counter += 1;
+
+        let mut operators = self.operators_per_owner.get(&owner_id).unwrap_or_default();
+        // Approve lại 1 operator cũ -> Chỉ tính phần storage tăng thêm (nếu đổi collection scope)
+        let storage_used = match operators.insert(operator_id.clone(), approval.clone()) {
+            Some(old_approval) => bytes_for_operator_approval(&operator_id, &approval)
+                .saturating_sub(bytes_for_operator_approval(&operator_id, &old_approval)),
+            None => bytes_for_operator_approval(&operator_id, &approval),
+        };
+        self.operators_per_owner.insert(&owner_id, &operators);
+
+        // Refund if user deposit more than needed
+        refund_deposit(storage_used);
+
+        // If attached msg -> Doing Cross Contract Call to Market Contract
+        if let Some(msg) = msg {
+            ext_non_fungible_token_approval_receiver::nft_on_approve_operator(
+                owner_id,
+                approval.approval_id,
+                approval.collection_name,
+                msg,
+                &operator_id,
+                NO_DEPOSIT,
+                env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
+            )
+            .as_return();
+        }
+    }
+
+    // Note: When deleting the operator -> Refund storage data fee that the user has deposited
+    #[payable]
+    fn nft_revoke_operator(&mut self, operator_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+
+        if let Some(mut operators) = self.operators_per_owner.get(&owner_id) {
+            // If revoke success
+            if let Some(approval) = operators.remove(&operator_id) {
+                if operators.is_empty() {
+                    self.operators_per_owner.remove(&owner_id);
+                } else {
+                    self.operators_per_owner.insert(&owner_id, &operators);
+                }
+
+                // Refund the deposited amount to cover storage of the user before
+                let storage_released = bytes_for_operator_approval(&operator_id, &approval);
+                Promise::new(owner_id)
+                    .transfer(Balance::from(storage_released) * env::storage_byte_cost());
+            }
+        }
+    }
+
+    // collection_name = None -> Check if the operator can transfer all the Tokens of owner_id
+    fn nft_is_approved_operator(
+        &self,
+        owner_id: AccountId,
+        operator_id: AccountId,
+        collection_name: Option<CollectionName>,
+        approval_id: Option<u64>,
+    ) -> bool {
+        let approval = self
+            .operators_per_owner
+            .get(&owner_id)
+            .and_then(|operators| operators.get(&operator_id).cloned());
+
+        if let Some(approval) = approval {
+            let is_in_scope =
+                approval.collection_name.is_none() || approval.collection_name == collection_name;
+            // approval_id = None -> Only check if the operator is approved
+            is_in_scope && approval_id.is_none_or(|approval_id| approval.approval_id == approval_id)
+        } else {
+            false
+        }
+    }
 }
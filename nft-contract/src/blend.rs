@@ -0,0 +1,267 @@
+use crate::*;
+
+// ----------------------------------- Blend Struct -----------------------------------
+// Recipe: burn the ingredient NFTs -> mint 1 NFT of each result Template
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlendRecipe {
+    pub blend_id: BlendId,                    // Id of the Blend recipe
+    pub owner_id: AccountId,                  // Owner of the Collection that created the recipe
+    pub collection_name: CollectionName,      // Collection that the recipe belongs to
+    pub ingredients: Vec<BlendIngredient>, // Requirements of the burned NFTs (the n-th token must match the n-th ingredient)
+    pub result_template_ids: Vec<TemplateId>, // Templates of the minted NFTs
+    pub max_uses: Option<u32>, // Max number of times the recipe can be used (None if don't have limit)
+    pub use_count: u32,        // Number of times the recipe has been used
+    pub start_time: Timestamp, // When the users can start blending (0 if the users can blend immediately)
+    pub end_time: Timestamp,   // When the users can't blend anymore (0 if don't have limit time)
+}
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlendIngredient {
+    // NFT of this Template
+    Template {
+        template_id: TemplateId,
+    },
+    // Any NFT of this Schema
+    Schema {
+        schema_id: SchemaId,
+    },
+    // Any NFT of this Schema whose Template has `attribute_name` = `attribute_value`
+    Attribute {
+        schema_id: SchemaId,
+        attribute_name: String,
+        attribute_value: String,
+    },
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Tạo 1 Blend recipe mới thuộc 1 Collection nào đó
+    /**
+     * - Only the Collection's owner can create a Blend recipe
+     * - All the ingredients and results must belong to the Collection
+     * - start_time, end_time: milliseconds
+     */
+    #[payable]
+    pub fn create_blend(
+        &mut self,
+        collection_name: CollectionName,
+        ingredients: Vec<BlendIngredient>,
+        result_template_ids: Vec<TemplateId>,
+        max_uses: Option<u32>,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+    ) -> BlendRecipe {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        // Check signer id is Collection's owner or not
+        assert_eq!(
+            collection.owner_id,
+            env::predecessor_account_id(),
+            "Only owner of this collection can create Blend"
+        );
+
+        assert!(
+            !ingredients.is_empty(),
+            "Blend must have at least 1 ingredient"
+        );
+        assert!(
+            !result_template_ids.is_empty(),
+            "Blend must have at least 1 result"
+        );
+
+        // Check each ingredient belongs to this Collection
+        self.internal_assert_valid_ingredients(&ingredients, &collection_name);
+
+        // Check each result is a normal Template of this Collection
+        for template_id in result_template_ids.iter() {
+            let template = self
+                .templates_by_id
+                .get(template_id)
+                .expect("Template does not exist");
+            assert_eq!(
+                template.collection_name, collection_name,
+                "Result Template does not belong to this collection"
+            );
+            assert!(!template.is_lootbox, "Result Template cannot be a Lootbox");
+        }
+
+        let start_time = start_time.unwrap_or(0);
+        let end_time = end_time.unwrap_or(0);
+        assert!(
+            end_time == 0 || end_time > start_time,
+            "End time must be after start time"
+        );
+
+        let blend_id = self.blend_id_counter;
+        self.blend_id_counter += 1;
+
+        let blend = BlendRecipe {
+            blend_id,
+            owner_id: collection.owner_id,
+            collection_name,
+            ingredients,
+            result_template_ids,
+            max_uses,
+            use_count: 0,
+            start_time,
+            end_time,
+        };
+
+        self.blends_by_id.insert(&blend_id, &blend);
+        // Insert Blend recipe vào index của Collection
+        self.internal_add_blend_to_collection(&blend.collection_name, blend_id);
+
+        // -------------------------------------------------------------------
+        // BLEND CREATE LOG
+        let blend_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::BlendCreate(vec![BlendLog {
+                blend_id: blend_id.to_string(),
+                owner_id: blend.owner_id.to_string(),
+                collection_name: blend.collection_name.clone(),
+                memo: None,
+            }]),
+        };
+        env::log(blend_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage - before_storage_usage);
+
+        blend
+    }
+
+    // Blend: burn the ingredient NFTs of the caller -> mint the result NFTs for the caller
+    /**
+     * - Check the recipe's time window and max uses
+     * - Mint must not be paused and the Collection must not be frozen (checked before burning)
+     * - token_ids[i] must be owned by the caller, burnable and match ingredients[i]
+     * - Burn all the ingredients, then mint 1 NFT of each result Template
+     * - The caller only pays for the extra storage (if minted NFTs use more storage than burned NFTs)
+     */
+    #[payable]
+    pub fn blend(&mut self, blend_id: BlendId, token_ids: Vec<TokenId>) -> Vec<TokenId> {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let owner_id = env::predecessor_account_id();
+        let mut blend = self
+            .blends_by_id
+            .get(&blend_id)
+            .expect("Blend does not exist");
+
+        // --- Check current time is inside the time window of the recipe ---
+        let blend_timestamp = current_time_ms(); // Blend timestamp in milliseconds
+        assert!(
+            blend_timestamp >= blend.start_time,
+            "This Blend has not started yet"
+        );
+        assert!(
+            blend.end_time == 0 || blend_timestamp < blend.end_time,
+            "This Blend has ended"
+        );
+
+        // --- Check max uses ---
+        if let Some(max_uses) = blend.max_uses {
+            assert!(
+                blend.use_count < max_uses,
+                "This Blend has reached its max uses"
+            );
+        }
+
+        // --- Check the results can be minted before burning the ingredients ---
+        self.assert_not_paused(PauseFeature::Mint);
+        self.assert_collection_not_frozen(&blend.collection_name);
+
+        // --- Check ingredients ---
+        self.internal_assert_ingredient_tokens(&blend.ingredients, &token_ids, &owner_id);
+
+        // --- Burn ingredients ---
+        for token_id in token_ids.iter() {
+            self.internal_nft_burn(token_id, Some(format!("{{\"blend_id\":{}}}", blend_id)));
+        }
+
+        // --- Mint results ---
+        let mut minted_token_ids = Vec::<TokenId>::new();
+        for template_id in blend.result_template_ids.iter() {
+            let template = self
+                .templates_by_id
+                .get(template_id)
+                .expect("Template does not exist");
+            minted_token_ids.extend(self.internal_nft_mint(
+                blend.collection_name.clone(),
+                template.schema_id,
+                template.template_id,
+                Some(1),
+                owner_id.clone(),
+            ));
+        }
+
+        blend.use_count += 1;
+        self.blends_by_id.insert(&blend_id, &blend);
+
+        // -------------------------------------------------------------------
+        // NFT BLEND LOG
+        let nft_blend_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBlend(vec![NftBlendLog {
+                blend_id: blend_id.to_string(),
+                owner_id: owner_id.to_string(),
+                burned_token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+                minted_token_ids: minted_token_ids.iter().map(|id| id.to_string()).collect(),
+                memo: None,
+            }]),
+        };
+        env::log(nft_blend_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        // Burned NFTs release storage -> Only charge the extra storage
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+
+        minted_token_ids
+    }
+
+    //  -------------------------------------- ENUMERATION --------------------------------------
+    // Lấy Blend recipe theo id
+    pub fn get_blend_by_id(&self, blend_id: BlendId) -> BlendRecipe {
+        self.blends_by_id
+            .get(&blend_id)
+            .expect("Blend does not exist")
+    }
+
+    // Lấy danh sách Blend recipes của 1 Collection (cursor: blend id)
+    pub fn get_all_blends_by_collection(
+        &self,
+        collection_name: CollectionName,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<BlendRecipe> {
+        // Check collection id có tồn tại không
+        assert!(
+            self.collections_by_name.get(&collection_name).is_some(),
+            "Collection does not exist"
+        );
+
+        // Duyệt các blend ids trong index của Collection -> Trả về Blend recipe
+        paginate_collection_index(
+            &self.blends_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |blend_id| self.blends_by_id.get(blend_id),
+        )
+    }
+}
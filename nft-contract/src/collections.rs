@@ -8,6 +8,7 @@ impl NFTContract {
      * - Yêu cầu user nạp tiền để cover phí lưu trữ
      * - Thêm Collection vào collections_by_name + collections_by_id
      * - Thêm Collection vào danh sách sở hữu bởi owner
+     * - Thêm tên của Collection vào search_index
      * - Refund lại NEAR user deposit thừa
      */
     #[payable]
@@ -38,6 +39,9 @@ impl NFTContract {
             data,
             approved_account_ids: HashMap::default(),
             next_approval_id: 0,
+            voucher_public_key: None,
+            allow_expired_transfer: true,
+            moderation: CollectionModeration::default(),
         };
 
         // Insert collection mới vào collections_per_owner
@@ -66,6 +70,29 @@ impl NFTContract {
         self.collections_by_name
             .insert(&collection_name, &new_collection);
 
+        // Index tên của Collection để search
+        self.internal_update_search_index(
+            SearchKind::Collection,
+            collection_id,
+            &[],
+            &[&collection_name, &new_collection.data.name],
+        );
+
+        // -------------------------------------------------------------------
+        // COLLECTION CREATE LOG
+        let collection_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::CollectionCreate(vec![CollectionCreateLog {
+                owner_id: owner_id.to_string(),
+                collection_id: collection_id.to_string(),
+                collection_name,
+                memo: None,
+            }]),
+        };
+        env::log(collection_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
         // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
         let after_storage_usage = env::storage_usage();
         // Refund NEAR
@@ -74,6 +101,76 @@ impl NFTContract {
         new_collection
     }
 
+    // Collection's owner cập nhật extra data (display name, img) của Collection
+    /**
+     * - Đổi display name -> Cập nhật search_index
+     * - Refund lại NEAR user deposit thừa
+     */
+    #[payable]
+    pub fn update_collection_data(
+        &mut self,
+        collection_name: CollectionName,
+        data: CollectionExtraData,
+    ) {
+        assert_at_least_one_yocto();
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            collection.owner_id,
+            "Only owner of this collection can update its data"
+        );
+
+        self.internal_update_search_index(
+            SearchKind::Collection,
+            collection.collection_id,
+            &[&collection_name, &collection.data.name],
+            &[&collection_name, &data.name],
+        );
+
+        collection.data = data;
+        self.collections_by_name
+            .insert(&collection_name, &collection);
+        self.collections_by_id
+            .insert(&collection.collection_id, &collection);
+
+        // Storage tăng -> Trừ vào deposit, storage giảm -> Chỉ refund deposit
+        let after_storage_usage = env::storage_usage();
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+
+    // Collection's owner chọn cho phép transfer NFT đã hết hạn hay không
+    #[payable]
+    pub fn set_collection_allow_expired_transfer(
+        &mut self,
+        collection_name: CollectionName,
+        allow_expired_transfer: bool,
+    ) {
+        assert_one_yocto();
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            collection.owner_id,
+            "Only owner of this collection can change this option"
+        );
+
+        collection.allow_expired_transfer = allow_expired_transfer;
+        self.collections_by_name
+            .insert(&collection_name, &collection);
+        self.collections_by_id
+            .insert(&collection.collection_id, &collection);
+    }
+
     // Lấy tổng số Collections đang có trong contract
     pub fn collection_total_supply(&self) -> U128 {
         // Đếm tổng số lượng id đang có trong token_metadata_by_id
@@ -91,67 +188,84 @@ impl NFTContract {
         }
     }
 
-    // Lấy danh sách tất cả Collections trong Contract
+    // Lấy thông tin của 1 Collection (kể cả Collection bị hidden) -> gồm trạng thái moderation
+    pub fn get_collection(&self, collection_name: CollectionName) -> Option<Collection> {
+        self.collections_by_name.get(&collection_name)
+    }
+
+    // Lấy danh sách tất cả Collections trong Contract (không gồm Collections bị hidden)
+    // Cursor: collection id
     pub fn get_all_collections(
         &self,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Collection> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        // Duyệt tất cả các keys -> Trả về Collection
-        // self.collections_by_id.values_as_vector().to_vec()
-        self.collections_by_name
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(10) as usize)
-            .map(|(collection_name, _collection)| {
-                self.collections_by_name.get(&collection_name).unwrap()
-            })
-            .collect()
+    ) -> Page<Collection> {
+        // Collection id = thứ tự tạo, Collections không bị xoá
+        let collection_count = self.collections_by_id.len() as u32;
+        paginate_ids(0, collection_count, cursor, limit, |collection_id| {
+            self.collections_by_id
+                .get(collection_id)
+                .filter(|collection| !collection.moderation.hidden)
+        })
     }
 
-    // Lấy danh sách collection của account nào đó (có pagination)
+    // Lấy danh sách collection của account nào đó (cursor: collection name)
     pub fn get_all_collections_for_owner(
         &self,
         account_id: AccountId,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Collection> {
-        let collection_keys = self.collections_per_owner.get(&account_id);
-
-        let keys = if let Some(collection_keys) = collection_keys {
+    ) -> Page<Collection> {
+        let keys = if let Some(collection_keys) = self.collections_per_owner.get(&account_id) {
             collection_keys
         } else {
-            return vec![];
+            return Page::empty();
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
+        // Duyệt các keys sau cursor -> Trả về Collection
+        paginate_keys(keys.iter(), cursor, limit, |collection_name| {
+            self.collections_by_name.get(collection_name)
+        })
+    }
+
+    // Lấy danh sách holders của Collection kèm số NFTs mà mỗi holder sở hữu (cursor: account id)
+    pub fn collection_holders(
+        &self,
+        collection_name: CollectionName,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<CollectionHolder> {
+        let holders = if let Some(holders) = self.holders_per_collection.get(&collection_name) {
+            holders
+        } else {
+            return Page::empty();
+        };
 
-        // Duyệt tất cả các keys -> Trả về Collection
-        keys.as_vector()
-            .iter()
-            .skip(start as usize) // Pagination
-            .take(limit.unwrap_or(10) as usize) // Pagination
-            .map(|collection_name| self.collections_by_name.get(&collection_name).unwrap())
-            .collect()
+        paginate_tree_map(&holders, cursor, limit, |account_id| {
+            Some(CollectionHolder {
+                account_id: account_id.clone(),
+                token_count: holders.get(account_id)?,
+            })
+        })
     }
 
-    // Search Collection theo name
-    // Lấy về tất cả Collection mà tên có chứa ký tự của `search_string`
-    pub fn get_collections_by_name(&self, search_string: String) -> Vec<Collection> {
-        let collections_set: Vec<Collection> = self.collections_by_name.values().collect();
-
-        let mut result = Vec::<Collection>::new();
-
-        for collection in collections_set {
-            if collection
-                .collection_name
-                .to_lowercase()
-                .contains(&search_string.to_lowercase())
-            {
-                result.push(collection);
-            }
-        }
-        result
+    // Search Collection theo name (cursor pagination như `search`)
+    // Lấy về các Collection mà mỗi từ của `search_string` là prefix của 1 từ trong tên (không gồm Collections bị hidden)
+    pub fn get_collections_by_name(
+        &self,
+        search_string: String,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<Collection> {
+        self.search(
+            search_string,
+            Some(vec![SearchKind::Collection]),
+            cursor,
+            limit,
+        )
+        .filter_map(|result| match result {
+            SearchResult::Collection(collection) => Some(collection),
+            _ => None,
+        })
     }
 }
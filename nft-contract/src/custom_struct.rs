@@ -1,5 +1,32 @@
 use crate::*;
 
+// ----------------------------------- Page Struct -----------------------------------
+// 1 trang kết quả của các list views (cursor pagination)
+// Trang tiếp theo: gọi lại với cursor = next_cursor (None nếu đã là trang cuối)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>, // Id (stable) của item cuối cùng của trang
+}
+
+impl<T> Page<T> {
+    pub fn empty() -> Self {
+        Page {
+            items: vec![],
+            next_cursor: None,
+        }
+    }
+
+    // Đổi kiểu của items (bỏ các items mà f -> None), giữ nguyên next_cursor
+    pub fn filter_map<U>(self, f: impl FnMut(T) -> Option<U>) -> Page<U> {
+        Page {
+            items: self.items.into_iter().filter_map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
 // ----------------------------------- Collection Struct -----------------------------------
 #[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -12,6 +39,9 @@ pub struct Collection {
 
     pub approved_account_ids: HashMap<AccountId, u64>, // Danh sách các accounts được approved để transfer Token thuộc Collection này
     pub next_approval_id: u64,                         // Id của approve tiếp theo
+    pub voucher_public_key: Option<Base58PublicKey>, // Public key that signs the Mint Vouchers of this Collection
+    pub allow_expired_transfer: bool, // Expired NFTs (metadata.expires_at) của Collection có được transfer không
+    pub moderation: CollectionModeration, // Trạng thái moderate của Collection bởi platform
 }
 
 #[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
@@ -21,6 +51,34 @@ pub struct CollectionExtraData {
     pub img: Option<String>, // Mã hash của ảnh của Collection
 }
 
+// Set by the moderators of the platform (owner, admin or moderator of the Contract)
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionModeration {
+    pub frozen: bool, // Frozen -> không được mint, tạo/claim Drop Sales và Lootboxes
+    pub frozen_reason: Option<String>, // Lý do freeze
+    pub hidden: bool, // Hidden -> không hiện trong get_all_collections và search
+    pub hidden_reason: Option<String>, // Lý do hide
+    pub verified: bool, // Badge verified của Collection
+}
+
+// 1 holder của Collection và số NFTs của Collection mà holder đang sở hữu
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionHolder {
+    pub account_id: AccountId, // Holder
+    pub token_count: u32,      // Số NFTs của Collection mà holder sở hữu
+}
+
+// ----------------------------------- Operator Struct -----------------------------------
+// Approval of an operator to transfer all the NFTs of 1 owner
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorApproval {
+    pub approval_id: u64,                        // Id của approve
+    pub collection_name: Option<CollectionName>, // Only the NFTs of this Collection (None if all the NFTs of the owner)
+}
+
 // ----------------------------------- Schema Struct -----------------------------------
 #[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -44,16 +102,18 @@ pub struct SchemaFormat {
 #[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Template {
-    pub template_id: TemplateId,       // Id của Template
-    pub collection_id: CollectionId,   // Id của Collection mà Template thuộc vào
-    pub collection_name: String,       // Tên Collection mà Template thuộc vào
-    pub schema_id: SchemaId,           // Id của Schema mà Template thuộc vào
-    pub schema_name: String,           // Tên Schema mà Template thuộc vào
-    pub transferable: bool,            // NFT có thể bị transfer không
-    pub burnable: bool,                // NFT có thể bị burn không
-    pub max_supply: u32,               // Tổng số NFT cung cấp
-    pub issued_supply: u32,            // Số NFT đã cung cấp
-    pub immutable_data: ImmutableData, // Những attribute trong Schema mà được fixed sẵn giá trị
+    pub template_id: TemplateId,        // Id của Template
+    pub collection_id: CollectionId,    // Id của Collection mà Template thuộc vào
+    pub collection_name: String,        // Tên Collection mà Template thuộc vào
+    pub schema_id: SchemaId,            // Id của Schema mà Template thuộc vào
+    pub schema_name: String,            // Tên Schema mà Template thuộc vào
+    pub transferable: bool,             // NFT có thể bị transfer không
+    pub burnable: bool,                 // NFT có thể bị burn không
+    pub max_supply: u32,                // Tổng số NFT cung cấp
+    pub issued_supply: u32,             // Số NFT đã cung cấp
+    pub burned_supply: u32,             // Số NFT đã bị burn
+    pub immutable_data: ImmutableData,  // Những attribute trong Schema mà được fixed sẵn giá trị
+    pub validity_duration: Option<u64>, // Thời hạn sử dụng của NFT tính từ lúc mint (milliseconds), None nếu không có hạn
 
     // Extra data for Lootbox Template
     pub is_lootbox: bool,               // Seperate Lootbot vs normal Template
@@ -61,13 +121,29 @@ pub struct Template {
     pub config: Option<LootboxConfig>,  // Config độ random để ra các loại NFT
 }
 
+// Config của 1 Template mới (create_template)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TemplateArgs {
+    pub schema_id: SchemaId,           // Id của Schema mà Template thuộc vào
+    pub transferable: bool,            // NFT có thể bị transfer không
+    pub burnable: bool,                // NFT có thể bị burn không
+    pub max_supply: u32,               // Tổng số NFT cung cấp
+    pub immutable_data: ImmutableData, // Những attribute trong Schema mà được fixed sẵn giá trị
+    #[serde(default)]
+    pub validity_duration: Option<u64>, // Thời hạn sử dụng của NFT (milliseconds), None nếu không có hạn
+}
+
 #[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ImmutableData {
     pub name: String,                         // Tên của Template
-    pub img: Option<String>,                  // Link tới ảnh của Template
+    pub img: Option<String>,                  // Link tới ảnh của Template (URL, hoặc CID tương đối với base_uri của Contract)
     pub rarity: Option<String>,               // Rarity of the Template
     pub extra_immutable_data: Option<String>, // Stringified JSON chứa các thông tin fix sẵn dựa theo Schema gửi từ Front-end lên
+    pub img_hash: Option<Base64VecU8>,        // Base64-encoded sha256 của ảnh -> media_hash của NFT
+    pub reference: Option<String>,            // Link tới file JSON chứa thêm thông tin của Template
+    pub reference_hash: Option<Base64VecU8>,  // Base64-encoded sha256 của file JSON, bắt buộc nếu có reference
 }
 
 // // ----------------------------------- Lootbox Struct -----------------------------------
@@ -19,6 +19,151 @@ pub struct DropSale {
     pub display_data: Option<String>, // Display data of the Drop Sale: Name, ...
     pub approved_account_ids: HashMap<AccountId, u64>, // Danh sách các accounts được approved để mua Drop Sale này
     pub next_approval_id: u64,                         // Id của approve tiếp theo
+    pub pricing_mode: DropPricingMode, // How the price of the Drop Sale changes over time
+    pub last_claim_price: U128, // Unit price paid by the latest claim (lowest price paid so far)
+    pub distribution_mode: DropDistributionMode, // Which templates 1 claimed unit becomes
+    pub is_paused: bool,        // Paused Drop Sales cannot be claimed
+    pub claim_mode: DropClaimMode, // How the Drop Sale is claimed: purchase or claim codes
+    pub storage_balance: U128,  // Storage prepaid by the owner for the NFTs minted with claim codes
+    pub access_rule: Option<DropAccessRule>, // Tokens that the claimer must hold to claim the Drop Sale
+}
+
+// Config of a new Drop Sale (create_drop)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropSaleArgs {
+    pub template_ids: Vec<TemplateId>, // Array of template_id that contains inside the Drop Sale
+    pub price: U128,                   // Price of the Drop Sale
+    pub price_type: String,            // Price Unit: (USDT | NEAR)
+    pub is_public: bool,               // Decide the Drop Sale is public for everyone or not
+    pub max_supply: u32,               // Max issued NFTs of the Drop
+    pub account_limit: u32,            // The limit of how many NFTs can 1 account buy at a time
+    #[serde(default)]
+    pub account_limit_cooldown: Timestamp, // The cooldown time between each buy of 1 account
+    #[serde(default)]
+    pub start_time: Timestamp, // 0 if the user can buy immediate after Drop Sale created
+    #[serde(default)]
+    pub end_time: Timestamp, // 0 if don't have limit time
+    #[serde(default)]
+    pub display_data: Option<String>, // Display data of the Drop Sale: Name, ...
+    #[serde(default)]
+    pub pricing_mode: Option<DropPricingMode>, // None -> Fixed
+    #[serde(default)]
+    pub distribution_mode: Option<DropDistributionMode>, // None -> AllTemplates
+    #[serde(default)]
+    pub claim_mode: Option<DropClaimMode>, // None -> Purchase
+    #[serde(default)]
+    pub access_rule: Option<DropAccessRule>, // None -> Everyone can claim
+}
+
+// Changes of a Drop Sale (update_drop): only the fields that are passed are changed
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct DropSaleUpdateArgs {
+    pub template_ids: Option<Vec<TemplateId>>,
+    pub price: Option<U128>,
+    pub price_type: Option<String>,
+    pub is_public: Option<bool>,
+    pub max_supply: Option<u32>,
+    pub account_limit: Option<u32>,
+    pub account_limit_cooldown: Option<Timestamp>,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    pub display_data: Option<String>,
+    pub pricing_mode: Option<DropPricingMode>,
+    pub distribution_mode: Option<DropDistributionMode>,
+    pub claim_mode: Option<DropClaimMode>,
+    // Not passed -> unchanged, null -> remove the token-gating rule, rule -> replace it
+    #[serde(deserialize_with = "deserialize_some")]
+    pub access_rule: Option<Option<DropAccessRule>>,
+}
+
+// Token-gating rule of a Drop Sale: the claimer must hold `min_amount` tokens matching `requirement`
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropAccessRule {
+    pub requirement: DropTokenRequirement, // Which tokens of this Contract are accepted
+    pub min_amount: u32,                   // Number of matching tokens the claimer must hold
+    pub is_single_use: bool,               // Each holding token can only be used for 1 claimed unit
+}
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DropTokenRequirement {
+    Template { template_id: TemplateId },
+    Schema { schema_id: SchemaId },
+    Collection { collection_name: CollectionName },
+}
+
+// Claim mode of a Drop Sale
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum DropClaimMode {
+    Purchase,  // Claimed with claim_drop by paying the price
+    ClaimCode, // Claimed for free with claim_drop_with_code, the owner prepays the storage
+}
+
+// Pricing mode of a Drop Sale
+// Dutch auctions start at `DropSale.price` and fall to `floor_price` between `start_time` and `end_time` (milliseconds)
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DropPricingMode {
+    Fixed, // Always `DropSale.price`
+    LinearDutchAuction {
+        floor_price: U128,     // Lowest price of the auction
+        start_time: Timestamp, // When the price starts falling
+        end_time: Timestamp,   // When the price reaches floor_price
+        rebate: bool,          // Early buyers can claim back the difference with the clearing price
+    },
+    SteppedDutchAuction {
+        floor_price: U128,        // Lowest price of the auction
+        start_time: Timestamp,    // When the price starts falling
+        end_time: Timestamp,      // When the price reaches floor_price
+        step_duration: Timestamp, // The price only drops once every step_duration
+        rebate: bool, // Early buyers can claim back the difference with the clearing price
+    },
+}
+
+// Distribution mode of a Drop Sale: what 1 claimed unit becomes
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DropDistributionMode {
+    AllTemplates, // 1 NFT of every template in template_ids
+    RandomWeighted {
+        weights: Option<Vec<u32>>, // Weight of each template in template_ids (None -> weighted by remaining supply)
+    },
+}
+
+// Receipt of a claim: which template each claimed unit became
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropClaimReceipt {
+    pub drop_id: DropId,
+    pub owner_id: AccountId, // Account that receives the NFTs
+    pub unit_price: U128,    // Price paid for each unit
+    pub storage_cost: U128,  // Storage paid for the minted NFTs
+    pub claimed_tokens: Vec<DropClaimedToken>, // NFTs minted by the claim
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropClaimedToken {
+    pub unit: u32,               // Index of the claimed unit
+    pub template_id: TemplateId, // Template that the unit became
+    pub token_id: TokenId,       // Id of the minted NFT
+}
+
+// How much an account has paid for a Dutch auction Drop Sale (used to compute the rebate)
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropPayment {
+    pub claimed_amount: u32, // Number of units claimed by the account
+    pub paid: U128,          // Total amount of NEAR paid by the account
 }
 
 #[near_bindgen]
@@ -30,24 +175,29 @@ impl NFTContract {
      * - Refund lại NEAR user deposit thừa
      */
     #[payable]
-    pub fn create_drop(
-        &mut self,
-        collection_name: CollectionName,
-        template_ids: Vec<TemplateId>,
-        price: U128,
-        price_type: String,
-        is_public: bool,
-        max_supply: u32,
-        account_limit: u32,
-        account_limit_cooldown: Timestamp,
-        start_time: Timestamp,
-        end_time: Timestamp,
-        display_data: Option<String>,
-    ) -> DropSale {
+    pub fn create_drop(&mut self, collection_name: CollectionName, args: DropSaleArgs) -> DropSale {
+        self.assert_not_paused(PauseFeature::Drop);
         let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
 
+        let DropSaleArgs {
+            template_ids,
+            price,
+            price_type,
+            is_public,
+            max_supply,
+            account_limit,
+            account_limit_cooldown,
+            start_time,
+            end_time,
+            display_data,
+            pricing_mode,
+            distribution_mode,
+            claim_mode,
+            access_rule,
+        } = args;
         let account_id = env::predecessor_account_id();
-        let drop_id = self.drops_by_id.len() as u32;
+        let drop_id = self.drop_id_counter;
+        self.drop_id_counter += 1;
 
         // Check drop_id đã tồn tại chưa
         assert!(
@@ -73,6 +223,18 @@ impl NFTContract {
             account_id, collection.owner_id,
             "Only owner of this collection can create a Sale Drop"
         );
+        // Collection bị freeze thì không được tạo Drop Sale
+        self.assert_collection_not_frozen(&collection_name);
+
+        let pricing_mode = pricing_mode.unwrap_or(DropPricingMode::Fixed);
+        assert_valid_drop_pricing_mode(&pricing_mode, price);
+
+        let distribution_mode = distribution_mode.unwrap_or(DropDistributionMode::AllTemplates);
+        assert_valid_drop_distribution_mode(&distribution_mode, &template_ids);
+
+        if let Some(access_rule) = &access_rule {
+            self.assert_valid_drop_access_rule(access_rule);
+        }
 
         let new_drop = DropSale {
             drop_id,
@@ -91,10 +253,39 @@ impl NFTContract {
             display_data: display_data.clone(),
             approved_account_ids: HashMap::default(),
             next_approval_id: 0,
+            pricing_mode,
+            last_claim_price: price,
+            distribution_mode,
+            is_paused: false,
+            claim_mode: claim_mode.unwrap_or(DropClaimMode::Purchase),
+            storage_balance: U128(0),
+            access_rule,
         };
 
         // Insert new created drop into drops_by_id
         self.drops_by_id.insert(&drop_id, &new_drop);
+        // Insert drop mới vào index của Collection
+        self.internal_add_drop_to_collection(&new_drop.collection_name, drop_id);
+
+        // -------------------------------------------------------------------
+        // DROP CREATE LOG
+        let drop_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropCreate(vec![DropCreateLog {
+                drop_id: drop_id.to_string(),
+                owner_id: new_drop.owner_id.to_string(),
+                collection_name,
+                template_ids: template_ids
+                    .iter()
+                    .map(|template_id| template_id.to_string())
+                    .collect(),
+                price: price.0.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
 
         // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
         let after_storage_usage = env::storage_usage();
@@ -104,11 +295,300 @@ impl NFTContract {
         new_drop
     }
 
+    // Update a Drop Sale, only the fields that are passed are changed
+    /**
+     * - Only the owner of the Drop Sale can update it
+     * - Fields that affect buyers (price, templates, pricing/distribution mode) are locked after the first claim
+     * - Yêu cầu user nạp tiền để cover phí lưu trữ, refund lại NEAR user deposit thừa
+     */
+    #[payable]
+    pub fn update_drop(&mut self, drop_id: DropId, args: DropSaleUpdateArgs) -> DropSale {
+        self.assert_not_paused(PauseFeature::Drop);
+        assert_at_least_one_yocto();
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let DropSaleUpdateArgs {
+            template_ids,
+            price,
+            price_type,
+            is_public,
+            max_supply,
+            account_limit,
+            account_limit_cooldown,
+            start_time,
+            end_time,
+            display_data,
+            pricing_mode,
+            distribution_mode,
+            claim_mode,
+            access_rule,
+        } = args;
+
+        let mut drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            drop.owner_id,
+            "Only owner of the Drop Sale can update it"
+        );
+        // Collection bị freeze thì không được update Drop Sale
+        self.assert_collection_not_frozen(&drop.collection_name);
+
+        // --- Fields that affect buyers: locked after the first claim ---
+        let is_claimed = drop.issued_supply > 0;
+        if template_ids.is_some()
+            || price.is_some()
+            || price_type.is_some()
+            || pricing_mode.is_some()
+            || distribution_mode.is_some()
+            || claim_mode.is_some()
+            || access_rule.is_some()
+        {
+            assert!(
+                !is_claimed,
+                "Cannot change price or templates after the Drop Sale has been claimed"
+            );
+        }
+
+        if let Some(template_ids) = template_ids {
+            for template_id in template_ids.iter() {
+                assert!(
+                    self.templates_by_id.get(template_id).is_some(),
+                    "Template id inside this lootbox does not exists"
+                );
+            }
+            drop.template_ids = template_ids;
+        }
+        if let Some(price) = price {
+            drop.price = price;
+            drop.last_claim_price = price;
+        }
+        if let Some(price_type) = price_type {
+            drop.price_type = price_type;
+        }
+        if let Some(pricing_mode) = pricing_mode {
+            drop.pricing_mode = pricing_mode;
+        }
+        if let Some(distribution_mode) = distribution_mode {
+            drop.distribution_mode = distribution_mode;
+        }
+        if let Some(claim_mode) = claim_mode {
+            drop.claim_mode = claim_mode;
+        }
+        if let Some(access_rule) = access_rule {
+            if let Some(access_rule) = &access_rule {
+                self.assert_valid_drop_access_rule(access_rule);
+            }
+            drop.access_rule = access_rule;
+        }
+        assert_valid_drop_pricing_mode(&drop.pricing_mode, drop.price);
+        assert_valid_drop_distribution_mode(&drop.distribution_mode, &drop.template_ids);
+
+        // --- Fields that can be changed at any time ---
+        if let Some(is_public) = is_public {
+            drop.is_public = is_public;
+        }
+        if let Some(max_supply) = max_supply {
+            assert!(
+                max_supply >= drop.issued_supply,
+                "Max supply cannot be lower than the issued supply: {}",
+                drop.issued_supply
+            );
+            // Sold out sets the clearing price of a Dutch auction -> The owner cannot force it by lowering the supply
+            if is_claimed && !matches!(drop.pricing_mode, DropPricingMode::Fixed) {
+                assert!(
+                    max_supply >= drop.max_supply,
+                    "Cannot lower the max supply of a Dutch auction Drop Sale after it has been claimed"
+                );
+            }
+            drop.max_supply = max_supply;
+        }
+        if let Some(account_limit) = account_limit {
+            drop.account_limit = account_limit;
+        }
+        if let Some(account_limit_cooldown) = account_limit_cooldown {
+            drop.account_limit_cooldown = account_limit_cooldown;
+        }
+        if let Some(start_time) = start_time {
+            drop.start_time = start_time;
+        }
+        if let Some(end_time) = end_time {
+            drop.end_time = end_time;
+        }
+        if display_data.is_some() {
+            drop.display_data = display_data;
+        }
+
+        self.drops_by_id.insert(&drop_id, &drop);
+
+        // -------------------------------------------------------------------
+        // DROP UPDATE LOG
+        let drop_update_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropUpdate(vec![DropLog {
+                drop_id: drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_update_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        // Refund NEAR
+        let after_storage_usage = env::storage_usage();
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+
+        drop
+    }
+
+    // Pause a Drop Sale -> Nobody can claim it until it is resumed
+    // Not blocked by PauseFeature::Drop: the owner can still stop the Drop Sale during an incident
+    #[payable]
+    pub fn pause_drop(&mut self, drop_id: DropId) {
+        assert_one_yocto();
+
+        let mut drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            drop.owner_id,
+            "Only owner of the Drop Sale can pause it"
+        );
+        assert!(!drop.is_paused, "Drop Sale is already paused");
+
+        drop.is_paused = true;
+        self.drops_by_id.insert(&drop_id, &drop);
+
+        // -------------------------------------------------------------------
+        // DROP PAUSE LOG
+        let drop_pause_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropPause(vec![DropLog {
+                drop_id: drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_pause_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Resume a paused Drop Sale
+    #[payable]
+    pub fn resume_drop(&mut self, drop_id: DropId) {
+        assert_one_yocto();
+
+        let mut drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            drop.owner_id,
+            "Only owner of the Drop Sale can resume it"
+        );
+        assert!(drop.is_paused, "Drop Sale is not paused");
+
+        drop.is_paused = false;
+        self.drops_by_id.insert(&drop_id, &drop);
+
+        // -------------------------------------------------------------------
+        // DROP RESUME LOG
+        let drop_resume_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropResume(vec![DropLog {
+                drop_id: drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_resume_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Cancel a Drop Sale
+    /**
+     * - Xoá DropSale khỏi drops_by_id (bao gồm cả whitelist)
+     * - Refund phí lưu trữ của DropSale cho owner
+     * - Not blocked by PauseFeature::Drop (same as pause_drop, resume_drop)
+     * - Blocked while the V1 state is being migrated (migrate_state)
+     */
+    #[payable]
+    pub fn cancel_drop(&mut self, drop_id: DropId) {
+        assert_one_yocto();
+        assert_state_migrated();
+        let before_storage_usage = env::storage_usage();
+
+        let drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            drop.owner_id,
+            "Only owner of the Drop Sale can cancel it"
+        );
+
+        // Buyers of a Dutch auction with rebate need the Drop Sale to claim their rebate
+        assert!(
+            !is_drop_rebate(&drop.pricing_mode) || drop.issued_supply == 0,
+            "Cannot cancel a Dutch auction Drop Sale with rebate after it has been claimed"
+        );
+
+        self.drops_by_id.remove(&drop_id);
+        self.internal_remove_drop_from_collection(&drop.collection_name, drop_id);
+
+        // Delete the claim codes of the Drop Sale
+        if let Some(mut claim_codes) = self.claim_codes_per_drop.remove(&drop_id) {
+            claim_codes.clear();
+        }
+
+        // Delete the holding tokens used to claim the Drop Sale
+        if let Some(mut used_tokens) = self.used_tokens_per_drop.remove(&drop_id) {
+            used_tokens.clear();
+        }
+
+        // Refund the storage released by the Drop Sale + the storage prepaid for claim codes
+        let after_storage_usage = env::storage_usage();
+        let storage_released = before_storage_usage.saturating_sub(after_storage_usage);
+        let refund_amount =
+            Balance::from(storage_released) * env::storage_byte_cost() + drop.storage_balance.0;
+        if refund_amount > 0 {
+            Promise::new(drop.owner_id.clone()).transfer(refund_amount);
+        }
+
+        // -------------------------------------------------------------------
+        // DROP CANCEL LOG
+        let drop_cancel_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropCancel(vec![DropLog {
+                drop_id: drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_cancel_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
     // Add accounts to Drop Sale's approved_account_ids -> They can purchase the Drop Sale
     // Only the owner of the Collection can add
     // Only applied for non-public Drop Sale
     #[payable]
     pub fn drop_add_whitelist_account(&mut self, drop_id: DropId, account_ids: Vec<AccountId>) {
+        self.assert_not_paused(PauseFeature::Drop);
         assert_at_least_one_yocto();
 
         let mut drop = self
@@ -159,6 +639,21 @@ impl NFTContract {
             // Refund if user deposit more NEAR than needed
             refund_deposit(storage_used);
         }
+
+        // -------------------------------------------------------------------
+        // DROP WHITELIST ADD LOG
+        let drop_whitelist_add_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropWhitelistAdd(vec![DropWhitelistLog {
+                drop_id: drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                account_ids,
+                memo: None,
+            }]),
+        };
+        env::log(drop_whitelist_add_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
     }
 
     // Kiểm tra account có tồn tại trong list approve để mua Drop Sale ko
@@ -194,6 +689,7 @@ impl NFTContract {
     // Note: Khi xoá 1 account khỏi approved_list_ids -> Refund phí lưu trữ data mà user đã trả trước đó
     #[payable]
     pub fn drop_revoke(&mut self, drop_id: DropId, account_id: AccountId) {
+        self.assert_not_paused(PauseFeature::Drop);
         assert_one_yocto();
 
         let mut drop = self.drops_by_id.get(&drop_id).expect("Not found Drop");
@@ -213,9 +709,11 @@ impl NFTContract {
         // Nếu xoá quyền thành công
         if drop.approved_account_ids.remove(&account_id).is_some() {
             // Refund lại số tiền đã deposit để lưu trữ data của user
-            refund_approved_account_ids_iter(sender_id, [account_id].iter());
+            refund_approved_account_ids_iter(sender_id, [account_id.clone()].iter());
             // Cập nhật lại danh sách drops
             self.drops_by_id.insert(&drop_id, &drop);
+
+            Self::internal_log_drop_whitelist_remove(&drop, vec![account_id]);
         }
     }
 
@@ -223,6 +721,7 @@ impl NFTContract {
     // Only applied for non-public Drop Sale
     #[payable]
     pub fn drop_revoke_all(&mut self, drop_id: DropId) {
+        self.assert_not_paused(PauseFeature::Drop);
         assert_one_yocto();
 
         let mut drop = self.drops_by_id.get(&drop_id).expect("Not found Drop Sale");
@@ -244,9 +743,16 @@ impl NFTContract {
             // Refund lại số tiền mọi người đã deposit khi gọi hàm revoke_all()
             refund_approved_account_ids(sender_id, &drop.approved_account_ids);
             // Xoá toàn bộ list account đã approved cho token
-            drop.approved_account_ids.clear();
+            let mut account_ids: Vec<AccountId> = drop
+                .approved_account_ids
+                .drain()
+                .map(|(account_id, _)| account_id)
+                .collect();
+            account_ids.sort();
             // Cập nhật lại danh sách drops
             self.drops_by_id.insert(&drop_id, &drop);
+
+            Self::internal_log_drop_whitelist_remove(&drop, account_ids);
         }
     }
 
@@ -265,78 +771,38 @@ impl NFTContract {
             "Collection does not exist"
         );
 
-        let mut count = 0;
-
-        let drops_set_by_collection: Vec<DropSale> = self
-            .drops_by_id
-            .keys()
-            .map(|drop_id| self.drops_by_id.get(&drop_id).unwrap())
-            .collect();
-
-        for drop in drops_set_by_collection {
-            if drop.collection_name == collection_name {
-                count += 1;
-            }
-        }
-
-        U128(count)
+        U128(collection_index_len(&self.drops_per_collection, &collection_name) as u128)
     }
 
-    // Lấy danh sách tất cả Drop Sale trong Contract
-    pub fn get_all_drops(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<DropSale> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        // Duyệt tất cả các keys -> Trả về Drop
-        self.drops_by_id
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(10) as usize)
-            .map(|(drop_id, _drop)| self.drops_by_id.get(&drop_id).unwrap())
-            .collect()
+    // Lấy danh sách tất cả Drop Sale trong Contract (cursor: drop id)
+    pub fn get_all_drops(&self, cursor: Option<String>, limit: Option<u64>) -> Page<DropSale> {
+        // Drop id tăng dần theo drop_id_counter, bỏ qua các Drop Sale đã bị cancel
+        paginate_ids(0, self.drop_id_counter, cursor, limit, |drop_id| {
+            self.drops_by_id.get(drop_id)
+        })
     }
 
-    // Lấy danh sách Drop Sale của Collection nào đó (có pagination)
+    // Lấy danh sách Drop Sale của Collection nào đó (cursor: drop id)
     pub fn get_all_drops_by_collection(
         &self,
         collection_name: CollectionName,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<DropSale> {
-        let mut count = 0;
-
+    ) -> Page<DropSale> {
         // Check collection id có tồn tại không
         assert!(
             self.collections_by_name.get(&collection_name).is_some(),
             "Collection does not exist"
         );
 
-        let mut result = Vec::<DropSale>::new();
-
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        // Duyệt tất cả các keys -> Trả về Collection
-        let drops_set_for_owner: Vec<DropSale> = self
-            .drops_by_id
-            .keys()
-            .skip(start as usize) // Pagination
-            .take(limit.unwrap_or(10) as usize) // Pagination
-            .map(|drop_id| self.drops_by_id.get(&drop_id).unwrap())
-            .collect();
-
-        // If limit = 0 -> Return empty Array
-        if limit.is_some() && limit.unwrap() == 0 {
-            return result;
-        }
-
-        for drop in drops_set_for_owner {
-            if drop.collection_name == collection_name {
-                result.push(drop);
-                count += 1;
-            }
-            if count == limit.unwrap_or(10) {
-                break;
-            }
-        }
-        result
+        // Duyệt các drop ids trong index của Collection -> Trả về Drop Sale
+        paginate_collection_index(
+            &self.drops_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |drop_id| self.drops_by_id.get(drop_id),
+        )
     }
     // Lấy Drop Sale theo id
     pub fn get_drop_by_id(&self, drop_id: DropId) -> DropSale {
@@ -352,6 +818,8 @@ impl NFTContract {
     ) -> bool {
         let drop = self.drops_by_id.get(&drop_id).expect("Drop does not exist");
 
+        assert!(!drop.is_paused, "This Drop Sale is paused");
+
         // --- Check if the Drop Sale is public or not ---
         if drop.is_public == false {
             // Check if the claimer is in approved_account_ids?
@@ -401,6 +869,22 @@ impl NFTContract {
             drop.account_limit
         );
 
+        // --- Check if the claimer holds the required tokens ---
+        if let Some(access_rule) = &drop.access_rule {
+            let holding_tokens = self.internal_drop_holding_tokens(&drop, &claimer_account);
+            // Single use -> Each claimed unit uses min_amount tokens
+            let required_amount = if access_rule.is_single_use {
+                access_rule.min_amount * claim_amount
+            } else {
+                access_rule.min_amount
+            };
+            assert!(
+                holding_tokens.len() as u32 >= required_amount,
+                "Claimer must hold {} tokens required by this Drop Sale",
+                required_amount
+            );
+        }
+
         // TODO
         // Check cooldown_time (drop.account_limit_cooldown)
 
@@ -409,45 +893,402 @@ impl NFTContract {
 
     // Let user to claim NFTs from a Drop Sale
     #[payable]
-    pub fn claim_drop(&mut self, drop_id: DropId, claim_amount: u32) {
+    /**
+     * - Claimer pays the Drop Sale price + the storage of the minted NFTs
+     * - Refund lại NEAR user deposit thừa
+     */
+    pub fn claim_drop(&mut self, drop_id: DropId, claim_amount: u32) -> DropClaimReceipt {
+        self.assert_not_paused(PauseFeature::Drop);
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
         let claimer_account = env::predecessor_account_id();
         let mut drop = self
             .drops_by_id
             .get(&drop_id)
             .expect("Drop id doesn't exists");
 
+        assert!(
+            drop.claim_mode == DropClaimMode::Purchase,
+            "This Drop Sale can only be claimed with a claim code"
+        );
+
         // --- Check if an account can claim this Drop Sale or not ---
         self.is_able_to_claim_drop(claimer_account.clone(), drop_id, claim_amount);
+        self.internal_use_drop_holding_tokens(&drop, &claimer_account, claim_amount);
 
         // --- Claim the Drop Sale ---
         let deposit = env::attached_deposit();
         log!("Deposited: {}", deposit);
-        assert!(deposit > 0, "Attached deposit must be greater than 0");
 
+        // The price of a Dutch auction Drop Sale depends on the current time
+        let unit_price = self.internal_drop_current_price(&drop);
+        let total_price = unit_price * claim_amount as u128;
         assert!(
-            deposit == drop.price.0 * claim_amount as u128,
-            "Attached deposit must be equal to current Drop Sale price: {}",
-            drop.price.0 * claim_amount as u128
+            deposit >= total_price,
+            "Attached deposit must be greater than or equal to current Drop Sale price: {}",
+            total_price
         );
 
-        // --- Mint the NFTs -> Transfer to claimer ---
-        for i in 0..drop.template_ids.len() {
-            let template = self.templates_by_id.get(&drop.template_ids[i]).unwrap();
-            for _j in 0..claim_amount {
-                self.internal_nft_mint(
-                    drop.collection_name.clone(),
-                    template.schema_id,
-                    template.template_id,
-                    // metadata.clone(),
-                    Some(1),
-                    claimer_account.clone(),
-                );
-            }
+        // Keep track of what the claimer paid so they can claim the rebate after the auction
+        if is_drop_rebate(&drop.pricing_mode) {
+            let drop_and_account_id = format!("{}{}{}", drop_id, ".", claimer_account);
+            let mut payment = self
+                .drop_payment_by_account
+                .get(&drop_and_account_id)
+                .unwrap_or(DropPayment {
+                    claimed_amount: 0,
+                    paid: U128(0),
+                });
+            payment.claimed_amount += claim_amount;
+            payment.paid = U128(payment.paid.0 + total_price);
+            self.drop_payment_by_account
+                .insert(&drop_and_account_id, &payment);
         }
+        drop.last_claim_price = U128(unit_price);
+
+        // --- Mint the NFTs -> Transfer to claimer ---
+        let claimed_tokens = self.internal_drop_mint(&drop, &claimer_account, claim_amount);
 
         // Increase the drop.issued_supply
         drop.issued_supply += claim_amount;
         // Update data of Drop Sale
         self.drops_by_id.insert(&drop_id, &drop);
+
+        // Claimer pays the storage of the minted NFTs on top of the price
+        // Refund if user deposit more NEAR than needed
+        let storage_used = env::storage_usage() - before_storage_usage;
+        refund_deposit_with_price(storage_used, total_price);
+
+        Self::internal_log_drop_claim(drop_id, &claimer_account, &claimed_tokens, unit_price);
+
+        DropClaimReceipt {
+            drop_id,
+            owner_id: claimer_account,
+            unit_price: U128(unit_price),
+            storage_cost: U128(Balance::from(storage_used) * env::storage_byte_cost()),
+            claimed_tokens,
+        }
+    }
+
+    // Add claim codes to a claim-code Drop Sale (sha256 hashes of the secret codes)
+    /**
+     * - Only the owner of the Drop Sale can add claim codes
+     * - Owner pays the storage of the codes + prepays the storage of the NFTs that the codes will mint
+     * - Refund lại NEAR user deposit thừa
+     */
+    #[payable]
+    pub fn drop_add_claim_codes(&mut self, drop_id: DropId, code_hashes: Vec<Base64VecU8>) {
+        self.assert_not_paused(PauseFeature::Drop);
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let mut drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            drop.owner_id,
+            "Only owner can add claim codes to this Drop Sale"
+        );
+        assert!(
+            drop.claim_mode == DropClaimMode::ClaimCode,
+            "This Drop Sale does not use claim codes"
+        );
+
+        let mut claim_codes = self.claim_codes_per_drop.get(&drop_id).unwrap_or_else(|| {
+            UnorderedMap::new(
+                StorageKey::ClaimCodesPerDropInnerKey { drop_id }
+                    .try_to_vec()
+                    .unwrap(),
+            )
+        });
+
+        for code_hash in code_hashes.iter() {
+            let code_hash: CryptoHash = code_hash
+                .0
+                .as_slice()
+                .try_into()
+                .expect("Claim code hash must be a sha256 hash");
+            // false -> The code has not been used yet
+            assert!(
+                claim_codes.insert(&code_hash, &false).is_none(),
+                "Claim code already exists"
+            );
+        }
+        self.claim_codes_per_drop.insert(&drop_id, &claim_codes);
+
+        // Prepay the storage of the NFTs that the codes will mint
+        // The receivers are not known yet -> Each code is paid as a claim of 1 unit by a new owner
+        let prepaid_storage =
+            self.internal_estimate_drop_claim_storage(&drop, 1, None) * code_hashes.len() as u64;
+        let prepaid_amount = Balance::from(prepaid_storage) * env::storage_byte_cost();
+        drop.storage_balance = U128(drop.storage_balance.0 + prepaid_amount);
+        self.drops_by_id.insert(&drop_id, &drop);
+
+        // Refund NEAR
+        let after_storage_usage = env::storage_usage();
+        refund_deposit_with_price(after_storage_usage - before_storage_usage, prepaid_amount);
+    }
+
+    // Check if a claim code of a Drop Sale has been used (None if the code does not exist)
+    pub fn drop_claim_code_spent(&self, drop_id: DropId, code_hash: Base64VecU8) -> Option<bool> {
+        let code_hash: CryptoHash = code_hash.0.as_slice().try_into().ok()?;
+
+        self.claim_codes_per_drop
+            .get(&drop_id)
+            .and_then(|claim_codes| claim_codes.get(&code_hash))
+    }
+
+    // Claim 1 unit of a claim-code Drop Sale for free (linkdrop-style)
+    /**
+     * - sha256(code) must be an unused claim code of the Drop Sale -> Mark the code as spent
+     * - Mint the NFTs to receiver_id
+     * - Storage of the NFTs is paid from the storage prepaid by the owner -> The claimer doesn't need NEAR
+     */
+    pub fn claim_drop_with_code(
+        &mut self,
+        drop_id: DropId,
+        code: String,
+        receiver_id: AccountId,
+    ) -> DropClaimReceipt {
+        self.assert_not_paused(PauseFeature::Drop);
+        let before_storage_usage = env::storage_usage();
+
+        let mut drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id doesn't exists");
+
+        assert!(
+            drop.claim_mode == DropClaimMode::ClaimCode,
+            "This Drop Sale does not use claim codes"
+        );
+
+        // --- Check if the receiver can claim this Drop Sale or not ---
+        self.is_able_to_claim_drop(receiver_id.clone(), drop_id, 1);
+        self.internal_use_drop_holding_tokens(&drop, &receiver_id, 1);
+
+        // --- Check the claim code -> Mark it as spent ---
+        let mut claim_codes = self
+            .claim_codes_per_drop
+            .get(&drop_id)
+            .expect("Invalid claim code");
+        let code_hash: CryptoHash = env::sha256(code.as_bytes()).try_into().unwrap();
+        let is_spent = claim_codes.get(&code_hash).expect("Invalid claim code");
+        assert!(!is_spent, "Claim code has already been used");
+
+        claim_codes.insert(&code_hash, &true);
+        self.claim_codes_per_drop.insert(&drop_id, &claim_codes);
+
+        // --- Mint the NFTs -> Transfer to receiver ---
+        let claimed_tokens = self.internal_drop_mint(&drop, &receiver_id, 1);
+
+        // Pay the storage of the minted NFTs from the storage prepaid by the owner
+        let storage_used = env::storage_usage().saturating_sub(before_storage_usage);
+        let storage_cost = Balance::from(storage_used) * env::storage_byte_cost();
+        assert!(
+            drop.storage_balance.0 >= storage_cost,
+            "Not enough prepaid storage in this Drop Sale"
+        );
+        drop.storage_balance = U128(drop.storage_balance.0 - storage_cost);
+
+        // Increase the drop.issued_supply
+        drop.issued_supply += 1;
+        // Update data of Drop Sale
+        self.drops_by_id.insert(&drop_id, &drop);
+
+        Self::internal_log_drop_claim(drop_id, &receiver_id, &claimed_tokens, 0);
+
+        DropClaimReceipt {
+            drop_id,
+            owner_id: receiver_id,
+            unit_price: U128(0),
+            storage_cost: U128(storage_cost),
+            claimed_tokens,
+        }
+    }
+
+    // Check if a holding token has already been used to claim a token-gated Drop Sale
+    pub fn drop_is_token_used(&self, drop_id: DropId, token_id: TokenId) -> bool {
+        self.used_tokens_per_drop
+            .get(&drop_id)
+            .is_some_and(|used_tokens| used_tokens.contains(&token_id))
+    }
+
+    // Get the current unit price of a Drop Sale (depends on the time for Dutch auctions)
+    pub fn drop_current_price(&self, drop_id: DropId) -> U128 {
+        let drop = self.drops_by_id.get(&drop_id).expect("Drop does not exist");
+
+        U128(self.internal_drop_current_price(&drop))
+    }
+
+    // Get the full cost of claiming `claim_amount` units of a Drop Sale: price + storage
+    /**
+     * - account_id: the claimer -> Exact storage of its claim (the same amount claim_drop charges)
+     * - None -> Storage of a new owner with the longest account id (upper bound)
+     * - RandomWeighted Drop Sales: storage of the Template that uses the most storage (upper bound)
+     */
+    pub fn drop_claim_cost(
+        &self,
+        drop_id: DropId,
+        claim_amount: u32,
+        account_id: Option<AccountId>,
+    ) -> U128 {
+        let drop = self.drops_by_id.get(&drop_id).expect("Drop does not exist");
+
+        let unit_price = self.internal_drop_current_price(&drop);
+
+        // Storage of the NFTs + indexes created by the claim
+        let claim_storage =
+            self.internal_estimate_drop_claim_storage(&drop, claim_amount, account_id.as_ref());
+
+        // Dutch auction with rebate also stores the payment of the claimer (first claim only)
+        let payment_storage = if is_drop_rebate(&drop.pricing_mode) {
+            let account_id_len = account_id
+                .as_ref()
+                .map_or(MAX_ACCOUNT_ID_LEN, |account_id| account_id.len() as u64);
+            let is_new_payment = account_id.as_ref().is_none_or(|account_id| {
+                !self
+                    .drop_payment_by_account
+                    .contains_key(&format!("{}{}{}", drop_id, ".", account_id))
+            });
+            if is_new_payment {
+                let payment = DropPayment {
+                    claimed_amount: 0,
+                    paid: U128(0),
+                };
+                bytes_for_lookup_map_entry(
+                    bytes_for_storage_key(StorageKey::DropPaymentByAccountKey),
+                    bytes_for_string(drop_id.to_string().len() as u64 + 1 + account_id_len),
+                    payment.try_to_vec().unwrap().len() as u64,
+                )
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+
+        let storage_cost =
+            Balance::from(claim_storage + payment_storage) * env::storage_byte_cost();
+        U128(unit_price * claim_amount as u128 + storage_cost)
+    }
+
+    // Let early buyers of a Dutch auction Drop Sale claim back what they paid above the clearing price
+    /**
+     * - The auction must be finished: sold out or after its end_time
+     * - Clearing price = price of the last claim if sold out, otherwise floor_price
+     * - Refund paid - claimed_amount * clearing price to the claimer
+     */
+    pub fn claim_rebate(&mut self, drop_id: DropId) -> U128 {
+        self.assert_not_paused(PauseFeature::Drop);
+        let claimer_account = env::predecessor_account_id();
+        let drop = self
+            .drops_by_id
+            .get(&drop_id)
+            .expect("Drop id doesn't exists");
+
+        let (floor_price, end_time) = match drop.pricing_mode {
+            DropPricingMode::LinearDutchAuction {
+                floor_price,
+                end_time,
+                rebate: true,
+                ..
+            }
+            | DropPricingMode::SteppedDutchAuction {
+                floor_price,
+                end_time,
+                rebate: true,
+                ..
+            } => (floor_price, end_time),
+            _ => env::panic("This Drop Sale does not have rebate".as_bytes()),
+        };
+
+        // Sold out -> the last claim sets the clearing price
+        // Otherwise -> anyone can still buy at floor_price after the auction ends
+        let clearing_price = if drop.issued_supply == drop.max_supply {
+            drop.last_claim_price.0
+        } else {
+            assert!(
+                current_time_ms() >= end_time,
+                "The auction of this Drop Sale has not finished yet"
+            );
+            floor_price.0
+        };
+
+        let drop_and_account_id = format!("{}{}{}", drop_id, ".", claimer_account);
+        let payment = self
+            .drop_payment_by_account
+            .remove(&drop_and_account_id)
+            .expect("Nothing to rebate for this account");
+
+        let rebate_amount = payment.paid.0 - clearing_price * payment.claimed_amount as u128;
+        if rebate_amount > 0 {
+            Promise::new(claimer_account).transfer(rebate_amount);
+        }
+
+        U128(rebate_amount)
+    }
+}
+
+// Check the Dutch auction config of a Drop Sale
+pub(crate) fn assert_valid_drop_pricing_mode(pricing_mode: &DropPricingMode, price: U128) {
+    match pricing_mode {
+        DropPricingMode::Fixed => {}
+        DropPricingMode::LinearDutchAuction {
+            floor_price,
+            start_time,
+            end_time,
+            ..
+        }
+        | DropPricingMode::SteppedDutchAuction {
+            floor_price,
+            start_time,
+            end_time,
+            ..
+        } => {
+            assert!(
+                floor_price.0 <= price.0,
+                "Floor price must be lower than the start price"
+            );
+            assert!(
+                end_time > start_time,
+                "Auction end time must be after auction start time"
+            );
+        }
+    }
+    if let DropPricingMode::SteppedDutchAuction { step_duration, .. } = pricing_mode {
+        assert!(*step_duration > 0, "Step duration must be greater than 0");
+    }
+}
+
+// Check the weights of the random templates of a Drop Sale
+pub(crate) fn assert_valid_drop_distribution_mode(
+    distribution_mode: &DropDistributionMode,
+    template_ids: &[TemplateId],
+) {
+    if let DropDistributionMode::RandomWeighted {
+        weights: Some(weights),
+    } = distribution_mode
+    {
+        assert_eq!(
+            weights.len(),
+            template_ids.len(),
+            "Must have 1 weight for each template of the Drop Sale"
+        );
+        assert!(
+            weights.iter().any(|weight| *weight > 0),
+            "At least 1 weight must be greater than 0"
+        );
+    }
+}
+
+// Check if the buyers of a Drop Sale can claim a rebate after the auction
+pub(crate) fn is_drop_rebate(pricing_mode: &DropPricingMode) -> bool {
+    match pricing_mode {
+        DropPricingMode::Fixed => false,
+        DropPricingMode::LinearDutchAuction { rebate, .. } => *rebate,
+        DropPricingMode::SteppedDutchAuction { rebate, .. } => *rebate,
     }
 }
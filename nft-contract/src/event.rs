@@ -11,7 +11,9 @@ use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::serde_json;
 
 /// Enum that represents the data type of the EventLog.
-/// The enum can either be an NftMint or an NftTransfer.
+/// The enum can either be an NftMint, an NftTransfer or an NftBurn (standard `nep171`),
+/// or a Collection / Schema / Template / Lootbox / Drop Sale / Blend / Upgrade / Pause / Moderation event (standard `nearhub`).
+/// The JSON schema of the `nearhub` events is in `nearhub-events.schema.json`.
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
@@ -20,6 +22,25 @@ use near_sdk::serde_json;
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftBurn(Vec<NftBurnLog>),
+    DropUpdate(Vec<DropLog>),
+    DropPause(Vec<DropLog>),
+    DropResume(Vec<DropLog>),
+    DropCancel(Vec<DropLog>),
+    BlendCreate(Vec<BlendLog>),
+    NftBlend(Vec<NftBlendLog>),
+    NftUpgrade(Vec<NftUpgradeLog>),
+    ContractPause(Vec<ContractPauseLog>),
+    ContractUnpause(Vec<ContractPauseLog>),
+    CollectionModerate(Vec<CollectionModerateLog>),
+    CollectionCreate(Vec<CollectionCreateLog>),
+    SchemaCreate(Vec<SchemaCreateLog>),
+    TemplateCreate(Vec<TemplateCreateLog>),
+    LootboxCreate(Vec<TemplateCreateLog>),
+    DropCreate(Vec<DropCreateLog>),
+    DropWhitelistAdd(Vec<DropWhitelistLog>),
+    DropWhitelistRemove(Vec<DropWhitelistLog>),
+    DropClaim(Vec<DropClaimLog>),
 }
 
 /// Interface to capture data about an event
@@ -85,3 +106,256 @@ pub struct NftTransferLog {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 }
+
+/// An event log to capture token burning
+///
+/// Arguments
+/// * `owner_id`: owner of tokens to burn
+/// * `authorized_id`: approved account_id to burn, if applicable
+/// * `token_ids`: ["1","2"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurnLog {
+    pub owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the lifecycle of a Drop Sale (update, pause, resume, cancel)
+///
+/// Arguments
+/// * `drop_id`: "1"
+/// * `owner_id`: "owner.near"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropLog {
+    pub drop_id: String,
+    pub owner_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the creation of a Blend recipe
+///
+/// Arguments
+/// * `blend_id`: "1"
+/// * `owner_id`: "owner.near"
+/// * `collection_name`: "Game"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlendLog {
+    pub blend_id: String,
+    pub owner_id: String,
+    pub collection_name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a Blend (ingredients burned, results minted)
+///
+/// Arguments
+/// * `blend_id`: "1"
+/// * `owner_id`: "blender.near"
+/// * `burned_token_ids`: ["1", "2"]
+/// * `minted_token_ids`: ["3"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBlendLog {
+    pub blend_id: String,
+    pub owner_id: String,
+    pub burned_token_ids: Vec<String>,
+    pub minted_token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a token upgrade (the token switches to another template)
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `token_id`: "1"
+/// * `rule_id`: "1"
+/// * `from_template_id`: "1"
+/// * `to_template_id`: "2"
+/// * `burned_token_ids`: ["3", "4"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftUpgradeLog {
+    pub owner_id: String,
+    pub token_id: String,
+    pub rule_id: String,
+    pub from_template_id: String,
+    pub to_template_id: String,
+    pub burned_token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a pause or an unpause of some features of the contract
+///
+/// Arguments
+/// * `account_id`: "pauser.near"
+/// * `features`: ["mint", "transfer"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractPauseLog {
+    pub account_id: String,
+    pub features: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a moderation action on a collection
+///
+/// Arguments
+/// * `moderator_id`: "moderator.near"
+/// * `collection_name`: "Game"
+/// * `action`: "freeze" | "unfreeze" | "hide" | "unhide" | "verify" | "unverify"
+/// * `reason`: optional reason of the action
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionModerateLog {
+    pub moderator_id: String,
+    pub collection_name: String,
+    pub action: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture the creation of a collection
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `collection_id`: "1"
+/// * `collection_name`: "Game"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionCreateLog {
+    pub owner_id: String,
+    pub collection_id: String,
+    pub collection_name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the creation of a schema
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `collection_name`: "Game"
+/// * `schema_id`: "1"
+/// * `schema_name`: "Weapon"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SchemaCreateLog {
+    pub owner_id: String,
+    pub collection_name: String,
+    pub schema_id: String,
+    pub schema_name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the creation of a template or a lootbox
+///
+/// Arguments
+/// * `owner_id`: "owner.near"
+/// * `collection_name`: "Game"
+/// * `schema_id`: "1"
+/// * `template_id`: "2"
+/// * `max_supply`: 100
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TemplateCreateLog {
+    pub owner_id: String,
+    pub collection_name: String,
+    pub schema_id: String,
+    pub template_id: String,
+    pub max_supply: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture the creation of a Drop Sale
+///
+/// Arguments
+/// * `drop_id`: "1"
+/// * `owner_id`: "owner.near"
+/// * `collection_name`: "Game"
+/// * `template_ids`: ["1", "2"]
+/// * `price`: "1000000000000000000000000" (yoctoNEAR)
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropCreateLog {
+    pub drop_id: String,
+    pub owner_id: String,
+    pub collection_name: String,
+    pub template_ids: Vec<String>,
+    pub price: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture accounts added to or removed from the whitelist of a Drop Sale
+///
+/// Arguments
+/// * `drop_id`: "1"
+/// * `owner_id`: "owner.near"
+/// * `account_ids`: ["alice.near", "bob.near"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropWhitelistLog {
+    pub drop_id: String,
+    pub owner_id: String,
+    pub account_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// An event log to capture a claim of a Drop Sale
+///
+/// Arguments
+/// * `drop_id`: "1"
+/// * `owner_id`: "claimer.near" (receiver of the NFTs)
+/// * `token_ids`: ["1", "2"]
+/// * `unit_price`: "1000000000000000000000000" (yoctoNEAR, "0" for a claim code)
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DropClaimLog {
+    pub drop_id: String,
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+    pub unit_price: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use std::mem::size_of;
 
 #[near_bindgen]
 impl NFTContract {
@@ -81,6 +82,7 @@ impl NFTContract {
      * - sender_id và receiver_id trùng nhau (gửi cho chính mình) không?
      * - Xoá token khỏi owner cũ
      * - Thêm token cho receiver_id
+     * - Emit nft_transfer event
      */
     pub(crate) fn internal_transfer(
         &mut self,
@@ -90,24 +92,101 @@ impl NFTContract {
         approval_id: Option<u64>,
         memo: Option<String>,
     ) -> Token {
+        let token =
+            self.internal_transfer_without_log(sender_id, receiver_id, token_id, approval_id);
+
+        // Nếu có memo thì in ra memo
+        if let Some(memo) = memo.as_ref() {
+            log!("Memo: {}", memo);
+        }
+
+        // -------------------------------------------------------------------
+        // NFT TRANSFER LOG
+        let mut authorized_id = None;
+        // Nếu người gửi không phải owner -> authorized_id chính là người gửi NFT (approved account)
+        if sender_id != &token.owner_id {
+            authorized_id = Some(sender_id.to_string());
+        }
+
+        let nft_transfer_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
+                authorized_id,
+                old_owner_id: token.owner_id.to_string(),
+                new_owner_id: receiver_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                memo,
+            }]),
+        };
+
+        env::log(&nft_transfer_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        // Return token cũ
+        token
+    }
+
+    // Giống internal_transfer nhưng không emit event (caller tự emit, VD: nft_batch_transfer gộp event)
+    pub(crate) fn internal_transfer_without_log(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+    ) -> Token {
+        // Transfer đang bị pause thì không được transfer
+        self.assert_not_paused(PauseFeature::Transfer);
         // Kiểm tra token_id có tồn tại không?
         let token = self.tokens_by_id.get(token_id).expect("Not found token");
+        // Token đang bị lock (staking) thì không được transfer
+        self.assert_token_not_locked(token_id);
+        // Token đã hết hạn chỉ được transfer nếu Collection cho phép
+        let collection = self
+            .collections_by_name
+            .get(&token.collection_name)
+            .expect("Collection does not exist");
+        if !collection.allow_expired_transfer {
+            let metadata = self
+                .token_metadata_by_id
+                .get(token_id)
+                .expect("Token metadata does not exist");
+            assert!(
+                !is_token_expired(&metadata, current_time_ms()),
+                "Token has expired"
+            );
+        }
         // sender_id có phải là owner của token hay không?
         // Nếu sender_id không phải owner của token -> Check xem sender_id có phải approval_id không (có quyền transfer token thay owner không)
         // Chỉ có owner của Token HOẶC người được approved mới có quyền transfer Token
+        // Approval của Collection chỉ có hiệu lực với các token mà Collection's owner đang sở hữu
         if sender_id != &token.owner_id {
-            if !token.approved_account_ids.contains_key(sender_id) {
-                env::panic("Sender must be the token owner or the approved account".as_bytes());
-            }
+            // Sau đó là operator của owner (approve toàn bộ NFTs của owner)
+            let actual_approval_id = token
+                .approved_account_ids
+                .get(sender_id)
+                .or_else(|| {
+                    if token.owner_id == collection.owner_id {
+                        collection.approved_account_ids.get(sender_id)
+                    } else {
+                        None
+                    }
+                })
+                .copied()
+                .or_else(|| {
+                    self.internal_operator_approval_id(
+                        &token.owner_id,
+                        sender_id,
+                        &token.collection_name,
+                    )
+                })
+                .unwrap_or_else(|| {
+                    env::panic("Sender must be the token owner or the approved account".as_bytes())
+                });
 
             if let Some(enforced_approval_id) = approval_id {
-                let actual_approval_id = token
-                    .approved_account_ids
-                    .get(sender_id)
-                    .expect("Sender is not approved to transfer token");
-
                 assert_eq!(
-                    actual_approval_id, &enforced_approval_id,
+                    actual_approval_id, enforced_approval_id,
                     "The actual approval id {} is different from the given approval id {}",
                     actual_approval_id, enforced_approval_id
                 );
@@ -123,6 +202,8 @@ impl NFTContract {
         self.internal_remove_token_from_owner(&token_id, &token.owner_id);
         // Thêm token cho receiver_id
         self.internal_add_token_to_owner(&token_id, receiver_id);
+        // Chuyển số NFTs của Collection từ owner cũ sang receiver_id
+        self.internal_move_collection_holder(&token, &token.owner_id, receiver_id);
 
         let new_token = Token {
             owner_id: receiver_id.clone(),
@@ -140,47 +221,77 @@ impl NFTContract {
         // Thêm token mới vào list tất cả tokens
         self.tokens_by_id.insert(token_id, &new_token);
 
-        // Nếu có memo thì in ra memo
-        if let Some(memo) = memo.as_ref() {
-            log!("Memo: {}", memo);
-        }
+        // Return token cũ
+        token
+    }
 
-        // -------------------------------------------------------------------
-        // NFT TRANSFER LOG
-        let mut authorized_id = None;
-        // Nếu có approval_id -> authorized_id chính là người gửi NFT
-        if approval_id.is_some() {
-            authorized_id = Some(sender_id.to_string());
+    // Approval id của operator nếu operator được phép transfer NFT thuộc collection_name của owner
+    pub(crate) fn internal_operator_approval_id(
+        &self,
+        owner_id: &AccountId,
+        operator_id: &AccountId,
+        collection_name: &CollectionName,
+    ) -> Option<u64> {
+        let operators = self.operators_per_owner.get(owner_id)?;
+        let approval = operators.get(operator_id)?;
+        match &approval.collection_name {
+            Some(name) if name != collection_name => None,
+            _ => Some(approval.approval_id),
         }
+    }
 
-        let nft_transfer_log: EventLog = EventLog {
-            standard: "nep171".to_string(),
-            version: "1.0.0".to_string(),
-            event: EventLogVariant::NftTransfer(vec![NftTransferLog {
-                authorized_id,
-                old_owner_id: token.owner_id.to_string(),
-                new_owner_id: receiver_id.to_string(),
-                token_ids: vec![token_id.to_string()],
-                memo,
-            }]),
-        };
+    // Internal mint NFTs
+    // Return the ids of the minted NFTs
+    pub(crate) fn internal_nft_mint(
+        &mut self,
+        collection_name: CollectionName,
+        schema_id: SchemaId,
+        template_id: TemplateId,
+        mint_number: Option<u32>,
+        receiver_id: AccountId,
+    ) -> Vec<TokenId> {
+        let token_ids = self.internal_nft_mint_without_log(
+            collection_name,
+            schema_id,
+            template_id,
+            mint_number,
+            receiver_id.clone(),
+        );
 
-        env::log(&nft_transfer_log.to_string().as_bytes());
-        // -------------------------------------------------------------------
+        for token_id in token_ids.iter() {
+            // -------------------------------------------------------------------
+            // NFT MINT LOG
+            let nft_mint_log: EventLog = EventLog {
+                standard: "nep171".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::NftMint(vec![NftMintLog {
+                    owner_id: receiver_id.to_string(),
+                    token_ids: vec![token_id.to_string()],
+                    memo: Some(format!("{{\"template_id\":{}}}", template_id)), // Which template the NFT was minted from
+                }]),
+            };
+            env::log(nft_mint_log.to_string().as_bytes());
+            // -------------------------------------------------------------------
+        }
 
-        // Return token cũ
-        token
+        token_ids
     }
 
-    // Internal mint NFTs
-    pub(crate) fn internal_nft_mint(
+    // Giống internal_nft_mint nhưng không emit event (caller tự emit, VD: nft_airdrop gộp event theo owner)
+    pub(crate) fn internal_nft_mint_without_log(
         &mut self,
         collection_name: CollectionName,
         schema_id: SchemaId,
         template_id: TemplateId,
         mint_number: Option<u32>,
         receiver_id: AccountId,
-    ) {
+    ) -> Vec<TokenId> {
+        // Mint đang bị pause thì không được mint
+        self.assert_not_paused(PauseFeature::Mint);
+        // Collection bị freeze thì không được mint
+        self.assert_collection_not_frozen(&collection_name);
+
+        let mut token_ids = Vec::<TokenId>::new();
         for _i in 0..mint_number.unwrap_or(1) {
             // Default: mint_number = 1
             let token_id = DEFAULT_TOKEN_ID + self.token_id_counter; // TokeId: 1000000001, ...
@@ -254,59 +365,29 @@ impl NFTContract {
             );
 
             // Add token metadata due to Template's immutable data
-            let metadata = TokenMetadata {
-                title: Some(template.immutable_data.name.clone()),
-                description: None,
-                media: template.immutable_data.img.clone(),
-                media_hash: Some(Base64VecU8::from(
-                    template
-                        .immutable_data
-                        .img
-                        .clone()
-                        .unwrap()
-                        .as_bytes()
-                        .to_vec(),
-                )),
-                copies: Some(template.max_supply as u64),
-                issued_at: Some(env::block_timestamp()),
-                expires_at: None,
-                starts_at: None,
-                updated_at: None,
-                extra: template.immutable_data.extra_immutable_data.clone(),
-                reference: None,
-                reference_hash: None,
-                nft_type: "NFT".to_string(),
-            };
+            let metadata = template_token_metadata(&template);
 
             self.token_metadata_by_id.insert(&token_id, &metadata);
 
             // Thêm token vào danh sách sở hữu bởi owner
             self.internal_add_token_to_owner(&token_id, &token.owner_id);
+            // Thêm token vào index của Template + Collection
+            self.internal_add_token_to_indexes(&token);
 
             // Update stt của NFT hiện tại trong token_by_template_id_counter
             token_by_template_id += 1;
             self.token_by_template_id_counter
                 .insert(&template_id, &token_by_template_id);
 
-            // -------------------------------------------------------------------
-            // NFT MINT LOG
-            let nft_mint_log: EventLog = EventLog {
-                standard: "nep171".to_string(),
-                version: "1.0.0".to_string(),
-                event: EventLogVariant::NftMint(vec![NftMintLog {
-                    owner_id: token.owner_id.to_string(),
-                    token_ids: vec![token_id.to_string()],
-                    memo: None,
-                }]),
-            };
-            env::log(&nft_mint_log.to_string().as_bytes());
-            // -------------------------------------------------------------------
-
             // Increase issued_supply of this template by 1
             template.issued_supply += 1;
             // Update data of template
             self.templates_by_id.insert(&template_id, &template);
+
+            token_ids.push(token_id);
         }
+
+        token_ids
     }
 
     pub(crate) fn internal_lootbox_nft_mint(
@@ -315,6 +396,9 @@ impl NFTContract {
         mint_number: Option<u32>,
         receiver_id: AccountId,
     ) {
+        // Mint đang bị pause thì không được mint
+        self.assert_not_paused(PauseFeature::Mint);
+
         for _i in 0..mint_number.unwrap_or(1) {
             // Default: mint_number = 1
 
@@ -338,6 +422,8 @@ impl NFTContract {
                 lootbox.is_lootbox, true,
                 "This function can only be called on a Lootbox"
             );
+            // Collection bị freeze thì không được mint
+            self.assert_collection_not_frozen(&lootbox.collection_name);
 
             // Tạo NFT mới
             let lootbox_nft = Token {
@@ -367,23 +453,15 @@ impl NFTContract {
                 title: Some(lootbox.immutable_data.name.clone()), // TODO: Define name in display_data
                 description: None,
                 media: lootbox.immutable_data.img.clone(),
-                media_hash: Some(Base64VecU8::from(
-                    lootbox
-                        .immutable_data
-                        .img
-                        .clone()
-                        .unwrap()
-                        .as_bytes()
-                        .to_vec(),
-                )),
+                media_hash: lootbox.immutable_data.img_hash.clone(),
                 copies: None,
-                issued_at: Some(env::block_timestamp()),
+                issued_at: Some(current_time_ms()),
                 expires_at: None,
                 starts_at: lootbox.unlock_time,
                 updated_at: None,
                 extra: Some(serde_json::to_string(&lootbox.config).unwrap()),
-                reference: None,
-                reference_hash: None,
+                reference: lootbox.immutable_data.reference.clone(),
+                reference_hash: lootbox.immutable_data.reference_hash.clone(),
                 nft_type: "Lootbox".to_string(),
             };
 
@@ -391,6 +469,8 @@ impl NFTContract {
 
             // Thêm Lootbox NFT vào danh sách sở hữu bởi owner
             self.internal_add_lootbox_nft_to_owner(&lootbox_nft_id, &receiver_id);
+            // Thêm Lootbox NFT vào index của Template + Collection
+            self.internal_add_token_to_indexes(&lootbox_nft);
 
             // Update stt của Lootbox NFT hiện tại trong lootbox_nft_by_lootbox_id_counter
             lootbox_nft_by_lootbox_id += 1;
@@ -417,4 +497,1149 @@ impl NFTContract {
             // self.templates_by_id.insert(&template_id, &template);
         }
     }
+
+    // Current unit price of a Drop Sale
+    /**
+     * - Fixed: drop.price
+     * - Linear Dutch auction: falls continuously from drop.price to floor_price
+     * - Stepped Dutch auction: falls from drop.price to floor_price once every step_duration
+     */
+    pub(crate) fn internal_drop_current_price(&self, drop: &DropSale) -> Balance {
+        // Auction times are in milliseconds
+        let now = current_time_ms();
+
+        match drop.pricing_mode {
+            DropPricingMode::Fixed => drop.price.0,
+            DropPricingMode::LinearDutchAuction {
+                floor_price,
+                start_time,
+                end_time,
+                ..
+            } => {
+                if now <= start_time {
+                    return drop.price.0;
+                }
+                if now >= end_time {
+                    return floor_price.0;
+                }
+
+                let price_range = drop.price.0 - floor_price.0;
+                let elapsed = (now - start_time) as u128;
+                let duration = (end_time - start_time) as u128;
+                drop.price.0 - mul_div(price_range, elapsed, duration)
+            }
+            DropPricingMode::SteppedDutchAuction {
+                floor_price,
+                start_time,
+                end_time,
+                step_duration,
+                ..
+            } => {
+                if now <= start_time {
+                    return drop.price.0;
+                }
+
+                // Number of price drops until the floor is reached, and the ones passed so far
+                let total_steps = std::cmp::max((end_time - start_time) / step_duration, 1);
+                let passed_steps =
+                    std::cmp::min((now - start_time) / step_duration, total_steps);
+
+                let price_range = drop.price.0 - floor_price.0;
+                drop.price.0 - mul_div(price_range, passed_steps as u128, total_steps as u128)
+            }
+        }
+    }
+
+    // Storage (bytes) of 1 NFT minted from a Template to an owner with an account id of owner_id_len bytes
+    // Token + TokenMetadata + entries of tokens_by_id, token_metadata_by_id, tokens_per_owner, tokens_per_template, tokens_per_collection
+    // Không gồm các index tạo mới bởi NFT đầu tiên của owner / Template / Collection (internal_new_*_storage)
+    pub(crate) fn internal_token_storage(&self, template: &Template, owner_id_len: u64) -> u64 {
+        let token = Token {
+            owner_id: AccountId::new(), // owner_id_len bytes được cộng vào sau
+            token_id: 0,
+            token_by_template_id: 0,
+            collection_id: template.collection_id,
+            collection_name: template.collection_name.clone(),
+            schema_id: template.schema_id,
+            schema_name: template.schema_name.clone(),
+            template_id: template.template_id,
+            approved_account_ids: HashMap::default(),
+            next_approval_id: 0,
+        };
+        let token_len = token.try_to_vec().unwrap().len() as u64 + owner_id_len;
+        let metadata_len = template_token_metadata(template).try_to_vec().unwrap().len() as u64;
+        let token_id_len = size_of::<TokenId>() as u64;
+
+        bytes_for_unordered_map_entry(
+            bytes_for_storage_key(StorageKey::TokensByIdKey),
+            token_id_len,
+            token_len,
+        ) + bytes_for_unordered_map_entry(
+            bytes_for_storage_key(StorageKey::TokenMetadataByIdKey),
+            token_id_len,
+            metadata_len,
+        ) + bytes_for_unordered_set_element(
+            bytes_for_storage_key(StorageKey::TokensPerOwnerInnerKey {
+                account_id_hash: CryptoHash::default(),
+            }),
+            token_id_len,
+        ) + bytes_for_tree_map_entry(
+            bytes_for_storage_key(StorageKey::TokensPerTemplateInnerKey {
+                template_id: template.template_id,
+            }),
+            token_id_len,
+            0,
+        ) + bytes_for_tree_map_entry(
+            bytes_for_storage_key(StorageKey::TokensPerCollectionInnerKey {
+                collection_name_hash: CryptoHash::default(),
+            }),
+            token_id_len,
+            0,
+        )
+    }
+
+    // Storage (bytes) of the indexes created by the first NFT of an owner in a Collection
+    // owner_id None -> Chưa biết receiver: tính như 1 owner mới có account id dài nhất
+    pub(crate) fn internal_new_owner_storage(
+        &self,
+        collection_name: &CollectionName,
+        owner_id: Option<&AccountId>,
+    ) -> u64 {
+        let owner_id = match owner_id {
+            Some(owner_id) => owner_id,
+            None => return bytes_for_new_owner(MAX_ACCOUNT_ID_LEN, true, true),
+        };
+
+        let is_new_owner = !self.tokens_per_owner.contains_key(owner_id);
+        let is_new_holder = self
+            .holders_per_collection
+            .get(collection_name)
+            .is_none_or(|holders| holders.get(owner_id).is_none());
+        bytes_for_new_owner(owner_id.len() as u64, is_new_owner, is_new_holder)
+    }
+
+    // Storage (bytes) of the indexes created by the first NFT of a Template
+    pub(crate) fn internal_new_template_storage(&self, template_id: TemplateId) -> u64 {
+        if self.tokens_per_template.contains_key(&template_id) {
+            return 0;
+        }
+
+        bytes_for_lookup_map_entry(
+            bytes_for_storage_key(StorageKey::TokensPerTemplateKey),
+            size_of::<TemplateId>() as u64,
+            bytes_for_tree_map(bytes_for_storage_key(StorageKey::TokensPerTemplateInnerKey {
+                template_id,
+            })),
+        )
+    }
+
+    // Storage (bytes) of the indexes created by the first NFT of a Collection (tokens_per_collection, holders_per_collection)
+    pub(crate) fn internal_new_collection_storage(&self, collection_name: &CollectionName) -> u64 {
+        let collection_name_len = bytes_for_string(collection_name.len() as u64);
+        let mut storage = 0;
+
+        if !self.tokens_per_collection.contains_key(collection_name) {
+            storage += bytes_for_lookup_map_entry(
+                bytes_for_storage_key(StorageKey::TokensPerCollectionKey),
+                collection_name_len,
+                bytes_for_tree_map(bytes_for_storage_key(
+                    StorageKey::TokensPerCollectionInnerKey {
+                        collection_name_hash: CryptoHash::default(),
+                    },
+                )),
+            );
+        }
+        if !self.holders_per_collection.contains_key(collection_name) {
+            storage += bytes_for_lookup_map_entry(
+                bytes_for_storage_key(StorageKey::HoldersPerCollectionKey),
+                collection_name_len,
+                bytes_for_tree_map(bytes_for_storage_key(
+                    StorageKey::HoldersPerCollectionInnerKey {
+                        collection_name_hash: CryptoHash::default(),
+                    },
+                )),
+            );
+        }
+
+        storage
+    }
+
+    // Mint the NFTs of `claim_amount` units of a Drop Sale to receiver_id
+    // Return which template each claimed unit became
+    pub(crate) fn internal_drop_mint(
+        &mut self,
+        drop: &DropSale,
+        receiver_id: &AccountId,
+        claim_amount: u32,
+    ) -> Vec<DropClaimedToken> {
+        let mut rng = Rng::new(&env::random_seed());
+        let mut claimed_tokens = Vec::<DropClaimedToken>::new();
+        for unit in 0..claim_amount {
+            let template_ids = match &drop.distribution_mode {
+                DropDistributionMode::AllTemplates => drop.template_ids.clone(),
+                DropDistributionMode::RandomWeighted { weights } => {
+                    // Templates that have issued all the NFTs cannot be picked anymore
+                    let unit_weights: Vec<u64> = drop
+                        .template_ids
+                        .iter()
+                        .enumerate()
+                        .map(|(i, template_id)| {
+                            let template = self.templates_by_id.get(template_id).unwrap();
+                            let remaining_supply = template.max_supply - template.issued_supply;
+                            match weights {
+                                Some(_) if remaining_supply == 0 => 0,
+                                Some(weights) => weights[i] as u64,
+                                None => remaining_supply as u64,
+                            }
+                        })
+                        .collect();
+
+                    let index = random_weighted_index(&mut rng, &unit_weights)
+                        .expect("All the templates of this Drop Sale have issued all the NFTs");
+                    vec![drop.template_ids[index]]
+                }
+            };
+
+            for template_id in template_ids {
+                let template = self.templates_by_id.get(&template_id).unwrap();
+                let token_ids = self.internal_nft_mint(
+                    drop.collection_name.clone(),
+                    template.schema_id,
+                    template.template_id,
+                    Some(1),
+                    receiver_id.clone(),
+                );
+                for token_id in token_ids {
+                    claimed_tokens.push(DropClaimedToken {
+                        unit,
+                        template_id,
+                        token_id,
+                    });
+                }
+            }
+        }
+
+        claimed_tokens
+    }
+
+    // Storage (bytes) used by claiming `claim_amount` units of a Drop Sale (without the rebate payment)
+    // NFTs + new indexes of the receiver / Templates / Collection + holding tokens used by a single use access rule
+    // receiver_id None -> Chưa biết receiver (claim codes): tính như 1 owner mới có account id dài nhất
+    // RandomWeighted -> Chưa biết Template nào: tính theo Template tốn storage nhất
+    pub(crate) fn internal_estimate_drop_claim_storage(
+        &self,
+        drop: &DropSale,
+        claim_amount: u32,
+        receiver_id: Option<&AccountId>,
+    ) -> u64 {
+        let owner_id_len = receiver_id.map_or(MAX_ACCOUNT_ID_LEN, |receiver_id| receiver_id.len() as u64);
+        let templates_storage = drop.template_ids.iter().map(|template_id| {
+            let template = self.templates_by_id.get(template_id).unwrap();
+            self.internal_token_storage(&template, owner_id_len)
+        });
+        let unit_storage: u64 = match drop.distribution_mode {
+            DropDistributionMode::AllTemplates => templates_storage.sum(),
+            DropDistributionMode::RandomWeighted { .. } => templates_storage.max().unwrap_or(0),
+        };
+
+        let new_templates_storage: u64 = drop
+            .template_ids
+            .iter()
+            .map(|template_id| self.internal_new_template_storage(*template_id))
+            .sum();
+
+        let used_tokens_storage = match &drop.access_rule {
+            Some(access_rule) if access_rule.is_single_use => {
+                let prefix_len = bytes_for_storage_key(StorageKey::UsedTokensPerDropInnerKey {
+                    drop_id: drop.drop_id,
+                });
+                let new_set_storage = if self.used_tokens_per_drop.contains_key(&drop.drop_id) {
+                    0
+                } else {
+                    bytes_for_lookup_map_entry(
+                        bytes_for_storage_key(StorageKey::UsedTokensPerDropKey),
+                        size_of::<DropId>() as u64,
+                        bytes_for_unordered_set(prefix_len),
+                    )
+                };
+                new_set_storage
+                    + (access_rule.min_amount * claim_amount) as u64
+                        * bytes_for_unordered_set_element(prefix_len, size_of::<TokenId>() as u64)
+            }
+            _ => 0,
+        };
+
+        unit_storage * claim_amount as u64
+            + new_templates_storage
+            + self.internal_new_collection_storage(&drop.collection_name)
+            + self.internal_new_owner_storage(&drop.collection_name, receiver_id)
+            + used_tokens_storage
+    }
+
+    // Check the token-gating rule of a Drop Sale
+    pub(crate) fn assert_valid_drop_access_rule(&self, access_rule: &DropAccessRule) {
+        assert!(
+            access_rule.min_amount > 0,
+            "Required amount of holding tokens must be greater than 0"
+        );
+
+        match &access_rule.requirement {
+            DropTokenRequirement::Template { template_id } => assert!(
+                self.templates_by_id.get(template_id).is_some(),
+                "Template does not exist"
+            ),
+            DropTokenRequirement::Schema { schema_id } => assert!(
+                self.schemas_by_id.get(schema_id).is_some(),
+                "Schema does not exist"
+            ),
+            DropTokenRequirement::Collection { collection_name } => assert!(
+                self.collections_by_name.get(collection_name).is_some(),
+                "Collection does not exist"
+            ),
+        }
+    }
+
+    // Tokens of account_id that match the token-gating rule of a Drop Sale
+    // Tokens already used for a single use rule are not returned
+    pub(crate) fn internal_drop_holding_tokens(
+        &self,
+        drop: &DropSale,
+        account_id: &AccountId,
+    ) -> Vec<TokenId> {
+        let access_rule = match &drop.access_rule {
+            Some(access_rule) => access_rule,
+            None => return vec![],
+        };
+        let tokens_set = match self.tokens_per_owner.get(account_id) {
+            Some(tokens_set) => tokens_set,
+            None => return vec![],
+        };
+        let used_tokens = self.used_tokens_per_drop.get(&drop.drop_id);
+
+        tokens_set
+            .iter()
+            .filter(|token_id| {
+                let token = self.tokens_by_id.get(token_id).unwrap();
+                match &access_rule.requirement {
+                    DropTokenRequirement::Template { template_id } => {
+                        token.template_id == *template_id
+                    }
+                    DropTokenRequirement::Schema { schema_id } => token.schema_id == *schema_id,
+                    DropTokenRequirement::Collection { collection_name } => {
+                        &token.collection_name == collection_name
+                    }
+                }
+            })
+            .filter(|token_id| {
+                !access_rule.is_single_use
+                    || used_tokens
+                        .as_ref()
+                        .is_none_or(|used_tokens| !used_tokens.contains(token_id))
+            })
+            .collect()
+    }
+
+    // Mark the holding tokens used by a claim of a single use token-gated Drop Sale
+    pub(crate) fn internal_use_drop_holding_tokens(
+        &mut self,
+        drop: &DropSale,
+        account_id: &AccountId,
+        claim_amount: u32,
+    ) {
+        let min_amount = match &drop.access_rule {
+            Some(access_rule) if access_rule.is_single_use => access_rule.min_amount,
+            _ => return,
+        };
+
+        let holding_tokens = self.internal_drop_holding_tokens(drop, account_id);
+
+        let mut used_tokens = self
+            .used_tokens_per_drop
+            .get(&drop.drop_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(
+                    StorageKey::UsedTokensPerDropInnerKey {
+                        drop_id: drop.drop_id,
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        for token_id in holding_tokens
+            .iter()
+            .take((min_amount * claim_amount) as usize)
+        {
+            used_tokens.insert(token_id);
+        }
+
+        self.used_tokens_per_drop
+            .insert(&drop.drop_id, &used_tokens);
+    }
+
+    // Emit drop_whitelist_remove event (drop_revoke, drop_revoke_all)
+    pub(crate) fn internal_log_drop_whitelist_remove(drop: &DropSale, account_ids: Vec<AccountId>) {
+        // -------------------------------------------------------------------
+        // DROP WHITELIST REMOVE LOG
+        let drop_whitelist_remove_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropWhitelistRemove(vec![DropWhitelistLog {
+                drop_id: drop.drop_id.to_string(),
+                owner_id: drop.owner_id.to_string(),
+                account_ids,
+                memo: None,
+            }]),
+        };
+        env::log(drop_whitelist_remove_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Emit drop_claim event (claim_drop, claim_drop_with_code)
+    pub(crate) fn internal_log_drop_claim(
+        drop_id: DropId,
+        receiver_id: &AccountId,
+        claimed_tokens: &[DropClaimedToken],
+        unit_price: Balance,
+    ) {
+        // -------------------------------------------------------------------
+        // DROP CLAIM LOG
+        let drop_claim_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::DropClaim(vec![DropClaimLog {
+                drop_id: drop_id.to_string(),
+                owner_id: receiver_id.to_string(),
+                token_ids: claimed_tokens
+                    .iter()
+                    .map(|claimed_token| claimed_token.token_id.to_string())
+                    .collect(),
+                unit_price: unit_price.to_string(),
+                memo: None,
+            }]),
+        };
+        env::log(drop_claim_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Internal burn 1 NFT
+    /**
+     * - Locked NFTs cannot be burned
+     * - Xoá token khỏi tokens_by_id, token_metadata_by_id và owner
+     * - Increase burned_supply of the token's template
+     * - Refund the storage of the token's approvals to the owner
+     * - Emit nft_burn event
+     */
+    pub(crate) fn internal_nft_burn(&mut self, token_id: &TokenId, memo: Option<String>) -> Token {
+        assert_state_migrated();
+        self.assert_token_not_locked(token_id);
+
+        let token = self
+            .tokens_by_id
+            .remove(token_id)
+            .expect("Token does not exist");
+        self.token_metadata_by_id.remove(token_id);
+        self.internal_remove_token_from_owner(token_id, &token.owner_id);
+        self.internal_remove_token_from_indexes(&token);
+
+        // Increase burned_supply of the token's template by 1
+        if let Some(mut template) = self.templates_by_id.get(&token.template_id) {
+            template.burned_supply += 1;
+            self.templates_by_id.insert(&token.template_id, &template);
+        }
+
+        if !token.approved_account_ids.is_empty() {
+            refund_approved_account_ids(token.owner_id.clone(), &token.approved_account_ids);
+        }
+
+        // -------------------------------------------------------------------
+        // NFT BURN LOG
+        let nft_burn_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftBurn(vec![NftBurnLog {
+                owner_id: token.owner_id.to_string(),
+                authorized_id: None,
+                token_ids: vec![token_id.to_string()],
+                memo,
+            }]),
+        };
+        env::log(nft_burn_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        token
+    }
+
+    // Value of an attribute in the immutable data of a Template
+    // name, img, rarity or a key of the stringified JSON extra_immutable_data
+    pub(crate) fn internal_template_attribute(
+        &self,
+        template: &Template,
+        attribute_name: &str,
+    ) -> Option<String> {
+        let immutable_data = &template.immutable_data;
+        match attribute_name {
+            "name" => Some(immutable_data.name.clone()),
+            "img" => immutable_data.img.clone(),
+            "rarity" => immutable_data.rarity.clone(),
+            _ => {
+                let extra: serde_json::Value =
+                    serde_json::from_str(immutable_data.extra_immutable_data.as_ref()?).ok()?;
+                match extra.get(attribute_name)? {
+                    serde_json::Value::String(value) => Some(value.clone()),
+                    value => Some(value.to_string()),
+                }
+            }
+        }
+    }
+
+    // Check if a token matches an ingredient of a Blend recipe
+    pub(crate) fn internal_blend_ingredient_matches(
+        &self,
+        ingredient: &BlendIngredient,
+        token: &Token,
+        template: &Template,
+    ) -> bool {
+        match ingredient {
+            BlendIngredient::Template { template_id } => token.template_id == *template_id,
+            BlendIngredient::Schema { schema_id } => token.schema_id == *schema_id,
+            BlendIngredient::Attribute {
+                schema_id,
+                attribute_name,
+                attribute_value,
+            } => {
+                token.schema_id == *schema_id
+                    && self
+                        .internal_template_attribute(template, attribute_name)
+                        .as_ref()
+                        == Some(attribute_value)
+            }
+        }
+    }
+
+    // Check that every ingredient (of a Blend recipe or an Upgrade rule) belongs to the Collection
+    pub(crate) fn internal_assert_valid_ingredients(
+        &self,
+        ingredients: &[BlendIngredient],
+        collection_name: &CollectionName,
+    ) {
+        for ingredient in ingredients.iter() {
+            let ingredient_collection_name = match ingredient {
+                BlendIngredient::Template { template_id } => {
+                    self.templates_by_id
+                        .get(template_id)
+                        .expect("Template does not exist")
+                        .collection_name
+                }
+                BlendIngredient::Schema { schema_id }
+                | BlendIngredient::Attribute { schema_id, .. } => {
+                    self.schemas_by_id
+                        .get(schema_id)
+                        .expect("Schema does not exist")
+                        .collection_name
+                }
+            };
+            assert_eq!(
+                &ingredient_collection_name, collection_name,
+                "Ingredient does not belong to this collection"
+            );
+        }
+    }
+
+    // Check the tokens that will be burned as ingredients
+    /**
+     * - token_ids[i] must match ingredients[i], no duplicate tokens
+     * - Each token must be owned by owner_id and burnable
+     */
+    pub(crate) fn internal_assert_ingredient_tokens(
+        &self,
+        ingredients: &[BlendIngredient],
+        token_ids: &[TokenId],
+        owner_id: &AccountId,
+    ) {
+        assert_eq!(
+            token_ids.len(),
+            ingredients.len(),
+            "Number of tokens must equal number of ingredients"
+        );
+        let unique_token_ids: HashSet<TokenId> = token_ids.iter().cloned().collect();
+        assert_eq!(
+            unique_token_ids.len(),
+            token_ids.len(),
+            "Duplicate ingredient tokens"
+        );
+
+        for (token_id, ingredient) in token_ids.iter().zip(ingredients.iter()) {
+            let token = self
+                .tokens_by_id
+                .get(token_id)
+                .expect("Token does not exist");
+            assert_eq!(
+                &token.owner_id, owner_id,
+                "Only owner of the ingredients can burn them"
+            );
+
+            let template = self
+                .templates_by_id
+                .get(&token.template_id)
+                .expect("Template does not exist");
+            assert!(template.burnable, "Ingredient is not burnable");
+            assert!(
+                self.internal_blend_ingredient_matches(ingredient, &token, &template),
+                "Token {} does not match the ingredient",
+                token_id
+            );
+        }
+    }
+
+    // Points per second of the Template of an NFT
+    pub(crate) fn internal_staking_rate(&self, token: &Token) -> u128 {
+        self.templates_by_id
+            .get(&token.template_id)
+            .map_or(0, |template| self.internal_template_staking_rate(&template))
+    }
+
+    // Points per second of a Template: the Template rate first, then the rarity rate
+    pub(crate) fn internal_template_staking_rate(&self, template: &Template) -> u128 {
+        if let Some(rate) = self.staking_rate_per_template.get(&template.template_id) {
+            return rate;
+        }
+        template
+            .immutable_data
+            .rarity
+            .as_ref()
+            .and_then(|rarity| {
+                self.staking_rate_per_rarity
+                    .get(&format!("{}{}{}", template.collection_name, ".", rarity))
+            })
+            .unwrap_or(0)
+    }
+
+    // Add the accrued points of a lock to the balance of its owner
+    pub(crate) fn internal_claim_staking_points(
+        &mut self,
+        token_id: &TokenId,
+        mut lock: TokenLock,
+    ) {
+        let (points, claimed_until) = accrued_staking_points(&lock, current_time_ms());
+        lock.claimed_until = claimed_until;
+        self.token_locks_by_id.insert(token_id, &lock);
+
+        if points > 0 {
+            let collection_and_account_id =
+                format!("{}{}{}", lock.collection_name, ".", lock.owner_id);
+            let balance = self
+                .staking_points_by_account
+                .get(&collection_and_account_id)
+                .unwrap_or(0);
+            self.staking_points_by_account
+                .insert(&collection_and_account_id, &(balance + points));
+        }
+    }
+
+    // Locked NFTs cannot be transferred, approved or burned
+    pub(crate) fn assert_token_not_locked(&self, token_id: &TokenId) {
+        assert!(
+            self.token_locks_by_id.get(token_id).is_none(),
+            "Token {} is locked",
+            token_id
+        );
+    }
+
+    // Only the Collection's owner can airdrop the NFTs of its Templates
+    pub(crate) fn assert_airdrop_owner(&self, template: &Template) {
+        let collection = self
+            .collections_by_name
+            .get(&template.collection_name)
+            .expect("Collection does not exist");
+        assert_eq!(
+            collection.owner_id,
+            env::predecessor_account_id(),
+            "Only this Collection's owner can airdrop"
+        );
+        assert!(!template.is_lootbox, "Cannot airdrop a Lootbox");
+    }
+
+    // Mint NFTs of 1 Template to many receivers, emit 1 nft_mint event with 1 NftMintLog per receiver
+    pub(crate) fn internal_airdrop_mint(
+        &mut self,
+        template: &Template,
+        recipients: &[(AccountId, u32)],
+    ) -> Vec<TokenId> {
+        let mut token_ids = Vec::<TokenId>::new();
+        let mut mint_logs = Vec::<NftMintLog>::new();
+
+        for (receiver_id, mint_number) in recipients.iter() {
+            assert!(*mint_number > 0, "Number of NFTs must be greater than 0");
+
+            let minted_token_ids = self.internal_nft_mint_without_log(
+                template.collection_name.clone(),
+                template.schema_id,
+                template.template_id,
+                Some(*mint_number),
+                receiver_id.clone(),
+            );
+
+            // Gộp các NFT của cùng 1 receiver vào 1 NftMintLog
+            let minted_token_ids_str = minted_token_ids.iter().map(|id| id.to_string());
+            match mint_logs
+                .iter_mut()
+                .find(|log| &log.owner_id == receiver_id)
+            {
+                Some(log) => log.token_ids.extend(minted_token_ids_str),
+                None => mint_logs.push(NftMintLog {
+                    owner_id: receiver_id.to_string(),
+                    token_ids: minted_token_ids_str.collect(),
+                    memo: Some(format!("{{\"template_id\":{}}}", template.template_id)), // Which template the NFT was minted from
+                }),
+            }
+            token_ids.extend(minted_token_ids);
+        }
+
+        if !mint_logs.is_empty() {
+            // -------------------------------------------------------------------
+            // NFT MINT LOG
+            let nft_mint_log: EventLog = EventLog {
+                standard: "nep171".to_string(),
+                version: "1.0.0".to_string(),
+                event: EventLogVariant::NftMint(mint_logs),
+            };
+            env::log(nft_mint_log.to_string().as_bytes());
+            // -------------------------------------------------------------------
+        }
+
+        token_ids
+    }
+
+    // Add recipients to an Airdrop job
+    // Return the estimated storage cost of their NFTs (prepaid into job.storage_balance)
+    pub(crate) fn internal_add_airdrop_recipients(
+        &mut self,
+        job: &mut AirdropJob,
+        recipients: Vec<(AccountId, u32)>,
+    ) -> Balance {
+        let template = self
+            .templates_by_id
+            .get(&job.template_id)
+            .expect("Template does not exist");
+
+        let mut recipients_list = self
+            .airdrop_recipients_per_job
+            .get(&job.job_id)
+            .unwrap_or_else(|| {
+                Vector::new(
+                    StorageKey::AirdropRecipientsPerJobInnerKey { job_id: job.job_id }
+                        .try_to_vec()
+                        .unwrap(),
+                )
+            });
+
+        let mut new_tokens: u32 = 0;
+        for recipient in recipients.iter() {
+            assert!(recipient.1 > 0, "Number of NFTs must be greater than 0");
+            new_tokens += recipient.1;
+            recipients_list.push(recipient);
+        }
+        self.airdrop_recipients_per_job
+            .insert(&job.job_id, &recipients_list);
+
+        job.total_recipients += recipients.len() as u32;
+        job.total_tokens += new_tokens;
+        // Check Template còn đủ NFT cho các NFT chưa được mint của job
+        assert!(
+            template.issued_supply + (job.total_tokens - job.minted_tokens) <= template.max_supply,
+            "This template does not have enough NFTs for the Airdrop"
+        );
+
+        // Mỗi recipient tính như 1 owner mới của Collection (có thể đã bán hết NFTs trước khi được mint)
+        let recipients_storage: u64 = recipients
+            .iter()
+            .map(|(receiver_id, mint_number)| {
+                let owner_id_len = receiver_id.len() as u64;
+                self.internal_token_storage(&template, owner_id_len) * *mint_number as u64
+                    + bytes_for_new_owner(owner_id_len, true, true)
+            })
+            .sum();
+        let tokens_storage = recipients_storage
+            + self.internal_new_template_storage(template.template_id)
+            + self.internal_new_collection_storage(&template.collection_name);
+        let tokens_storage_cost = Balance::from(tokens_storage) * env::storage_byte_cost();
+        job.storage_balance = U128(u128::from(job.storage_balance) + tokens_storage_cost);
+
+        tokens_storage_cost
+    }
+
+    // Chỉ owner của Contract
+    pub(crate) fn assert_contract_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner of this contract can call this function"
+        );
+    }
+
+    // Owner của Contract hoặc account có 1 trong các roles
+    pub(crate) fn assert_has_role(&self, roles: &[Role]) {
+        let account_id = env::predecessor_account_id();
+        if account_id == self.owner_id {
+            return;
+        }
+        let account_roles = self.roles_by_account.get(&account_id).unwrap_or_default();
+        assert!(
+            roles.iter().any(|role| account_roles.contains(role)),
+            "Account does not have the required role"
+        );
+    }
+
+    // Thêm Schema vào index schemas_per_collection
+    pub(crate) fn internal_add_schema_to_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        schema_id: SchemaId,
+    ) {
+        insert_into_collection_index(
+            &mut self.schemas_per_collection,
+            collection_name,
+            schema_id,
+            |collection_name_hash| StorageKey::SchemasPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+    }
+
+    // Thêm Template vào index templates_per_collection (+ lootboxes_per_collection, lootbox_ids nếu là Lootbox)
+    pub(crate) fn internal_add_template_to_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        template_id: TemplateId,
+        is_lootbox: bool,
+    ) {
+        insert_into_collection_index(
+            &mut self.templates_per_collection,
+            collection_name,
+            template_id,
+            |collection_name_hash| StorageKey::TemplatesPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+
+        if is_lootbox {
+            insert_into_collection_index(
+                &mut self.lootboxes_per_collection,
+                collection_name,
+                template_id,
+                |collection_name_hash| StorageKey::LootboxesPerCollectionInnerKey {
+                    collection_name_hash,
+                },
+            );
+            self.lootbox_ids.insert(&template_id, &());
+        }
+    }
+
+    // Thêm Drop Sale vào index drops_per_collection
+    pub(crate) fn internal_add_drop_to_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        drop_id: DropId,
+    ) {
+        insert_into_collection_index(
+            &mut self.drops_per_collection,
+            collection_name,
+            drop_id,
+            |collection_name_hash| StorageKey::DropsPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+    }
+
+    // Xoá Drop Sale khỏi index drops_per_collection
+    pub(crate) fn internal_remove_drop_from_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        drop_id: DropId,
+    ) {
+        remove_from_collection_index(&mut self.drops_per_collection, collection_name, drop_id);
+    }
+
+    // Thêm Blend recipe vào index blends_per_collection
+    pub(crate) fn internal_add_blend_to_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        blend_id: BlendId,
+    ) {
+        insert_into_collection_index(
+            &mut self.blends_per_collection,
+            collection_name,
+            blend_id,
+            |collection_name_hash| StorageKey::BlendsPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+    }
+
+    // Thêm Upgrade rule vào index upgrade_rules_per_collection
+    pub(crate) fn internal_add_upgrade_rule_to_collection(
+        &mut self,
+        collection_name: &CollectionName,
+        rule_id: UpgradeRuleId,
+    ) {
+        insert_into_collection_index(
+            &mut self.upgrade_rules_per_collection,
+            collection_name,
+            rule_id,
+            |collection_name_hash| StorageKey::UpgradeRulesPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+    }
+
+    // Thêm NFT vào index tokens_per_template
+    pub(crate) fn internal_add_token_to_template(
+        &mut self,
+        template_id: TemplateId,
+        token_id: TokenId,
+    ) {
+        let mut tokens_set = self
+            .tokens_per_template
+            .get(&template_id)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::TokensPerTemplateInnerKey { template_id }
+                        .try_to_vec()
+                        .unwrap(),
+                )
+            });
+        tokens_set.insert(&token_id, &());
+        self.tokens_per_template.insert(&template_id, &tokens_set);
+    }
+
+    // Xoá NFT khỏi index tokens_per_template
+    pub(crate) fn internal_remove_token_from_template(
+        &mut self,
+        template_id: TemplateId,
+        token_id: TokenId,
+    ) {
+        if let Some(mut tokens_set) = self.tokens_per_template.get(&template_id) {
+            tokens_set.remove(&token_id);
+            if tokens_set.len() == 0 {
+                self.tokens_per_template.remove(&template_id);
+            } else {
+                self.tokens_per_template.insert(&template_id, &tokens_set);
+            }
+        }
+    }
+
+    // Thêm NFT vào các index tokens_per_template, tokens_per_collection, holders_per_collection
+    // NFT đã có trong index thì không đếm lại cho holder
+    pub(crate) fn internal_add_token_to_indexes(&mut self, token: &Token) {
+        self.internal_add_token_to_template(token.template_id, token.token_id);
+
+        let is_new = insert_into_collection_index(
+            &mut self.tokens_per_collection,
+            &token.collection_name,
+            token.token_id,
+            |collection_name_hash| StorageKey::TokensPerCollectionInnerKey {
+                collection_name_hash,
+            },
+        );
+        if is_new {
+            self.internal_add_collection_holder(&token.collection_name, &token.owner_id);
+        }
+    }
+
+    // Xoá NFT khỏi các index tokens_per_template, tokens_per_collection, holders_per_collection
+    pub(crate) fn internal_remove_token_from_indexes(&mut self, token: &Token) {
+        self.internal_remove_token_from_template(token.template_id, token.token_id);
+
+        let is_removed = remove_from_collection_index(
+            &mut self.tokens_per_collection,
+            &token.collection_name,
+            token.token_id,
+        );
+        if is_removed {
+            self.internal_remove_collection_holder(&token.collection_name, &token.owner_id);
+        }
+    }
+
+    // NFT được transfer -> Chuyển số NFTs của Collection từ owner cũ sang owner mới
+    pub(crate) fn internal_move_collection_holder(
+        &mut self,
+        token: &Token,
+        old_owner_id: &AccountId,
+        new_owner_id: &AccountId,
+    ) {
+        // NFT chưa có trong index -> Không có holder nào để chuyển
+        let is_indexed = self
+            .tokens_per_collection
+            .get(&token.collection_name)
+            .is_some_and(|tokens_set| tokens_set.contains_key(&token.token_id));
+        if is_indexed {
+            self.internal_remove_collection_holder(&token.collection_name, old_owner_id);
+            self.internal_add_collection_holder(&token.collection_name, new_owner_id);
+        }
+    }
+
+    // Tăng số NFTs của holder trong Collection
+    pub(crate) fn internal_add_collection_holder(
+        &mut self,
+        collection_name: &CollectionName,
+        account_id: &AccountId,
+    ) {
+        let mut holders = self
+            .holders_per_collection
+            .get(collection_name)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::HoldersPerCollectionInnerKey {
+                        collection_name_hash: hash_account_id(collection_name),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+        let token_count = holders.get(account_id).unwrap_or(0);
+        holders.insert(account_id, &(token_count + 1));
+        self.holders_per_collection
+            .insert(collection_name, &holders);
+    }
+
+    // Giảm số NFTs của holder trong Collection (hết NFTs -> Xoá holder)
+    pub(crate) fn internal_remove_collection_holder(
+        &mut self,
+        collection_name: &CollectionName,
+        account_id: &AccountId,
+    ) {
+        if let Some(mut holders) = self.holders_per_collection.get(collection_name) {
+            match holders.get(account_id) {
+                Some(token_count) if token_count > 1 => {
+                    holders.insert(account_id, &(token_count - 1));
+                }
+                _ => {
+                    holders.remove(account_id);
+                }
+            }
+            self.holders_per_collection
+                .insert(collection_name, &holders);
+        }
+    }
+
+    // Collection bị freeze bởi moderator -> panic
+    pub(crate) fn assert_collection_not_frozen(&self, collection_name: &CollectionName) {
+        let collection = self
+            .collections_by_name
+            .get(collection_name)
+            .expect("Collection does not exist");
+        assert!(
+            !collection.moderation.frozen,
+            "Collection {} is frozen",
+            collection_name
+        );
+    }
+
+    // Check link + hash của ảnh và reference trong immutable data của Template / Lootbox
+    /**
+     * - Hash phải là sha256 (32 bytes) và chỉ có khi có link tương ứng
+     * - reference bắt buộc phải có reference_hash (NEP-177)
+     * - Link tương đối (VD: IPFS CID) cần Contract có base_uri
+     */
+    pub(crate) fn assert_valid_immutable_data(&self, immutable_data: &ImmutableData) {
+        self.assert_valid_link_and_hash(
+            "img",
+            immutable_data.img.as_ref(),
+            immutable_data.img_hash.as_ref(),
+        );
+        self.assert_valid_link_and_hash(
+            "reference",
+            immutable_data.reference.as_ref(),
+            immutable_data.reference_hash.as_ref(),
+        );
+        assert!(
+            immutable_data.reference.is_none() || immutable_data.reference_hash.is_some(),
+            "reference_hash is required when reference is set"
+        );
+    }
+
+    fn assert_valid_link_and_hash(
+        &self,
+        field: &str,
+        link: Option<&String>,
+        hash: Option<&Base64VecU8>,
+    ) {
+        if let Some(hash) = hash {
+            assert!(link.is_some(), "{}_hash is set without {}", field, field);
+            assert_eq!(
+                hash.0.len(),
+                SHA256_HASH_LEN,
+                "{}_hash must be a base64-encoded sha256 hash ({} bytes)",
+                field,
+                SHA256_HASH_LEN
+            );
+        }
+
+        if let Some(link) = link {
+            assert!(
+                !link.is_empty() && !link.contains(char::is_whitespace),
+                "Invalid {}",
+                field
+            );
+            if !is_absolute_link(link) {
+                assert!(
+                    self.metadata.get().unwrap().base_uri.is_some(),
+                    "Relative {} requires the contract base_uri",
+                    field
+                );
+            }
+        }
+    }
+
+    // Lưu Collection sau khi moderate vào collections_by_name + collections_by_id -> Emit event
+    pub(crate) fn internal_save_moderated_collection(
+        &mut self,
+        collection: &Collection,
+        action: &str,
+        reason: Option<String>,
+    ) {
+        self.collections_by_name
+            .insert(&collection.collection_name, collection);
+        self.collections_by_id
+            .insert(&collection.collection_id, collection);
+
+        // -------------------------------------------------------------------
+        // COLLECTION MODERATE LOG
+        let collection_moderate_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::CollectionModerate(vec![CollectionModerateLog {
+                moderator_id: env::predecessor_account_id(),
+                collection_name: collection.collection_name.clone(),
+                action: action.to_string(),
+                reason,
+            }]),
+        };
+        env::log(collection_moderate_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+
+    // Feature đang bị pause -> panic
+    pub(crate) fn assert_not_paused(&self, feature: PauseFeature) {
+        assert_state_migrated();
+        assert!(
+            !self.paused_features.contains(&feature),
+            "The {} feature is paused",
+            feature.as_str()
+        );
+    }
+
+    // Dạng JsonToken theo chuẩn NEP-171 của 1 token
+    pub(crate) fn internal_json_token(&self, token_id: TokenId) -> Option<JsonToken> {
+        let token = self.tokens_by_id.get(&token_id)?;
+        let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+
+        Some(JsonToken {
+            token_id: token_id.to_string(),
+            owner_id: token.owner_id,
+            metadata,
+            approved_account_ids: token.approved_account_ids,
+        })
+    }
 }
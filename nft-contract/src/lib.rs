@@ -1,17 +1,25 @@
 use near_rng::Rng;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::collections::{
+    LazyOption, LookupMap, LookupSet, TreeMap, UnorderedMap, UnorderedSet, Vector,
+};
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     env, ext_contract, log, near_bindgen, serde_json, AccountId, Balance, CryptoHash, Gas,
     PanicOnDefault, Promise, PromiseOrValue, PromiseResult
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 // use std::time::{SystemTime, UNIX_EPOCH};
 use near_sdk::Timestamp;
 
 const DEFAULT_TOKEN_ID: u32 = 1000000000; // MAX u32 = 4294967295
+const DEFAULT_PAGE_LIMIT: u64 = 10; // Limit mặc định của 1 trang trong các list views
+const MAX_PAGE_LIMIT: u64 = 100; // Limit tối đa của 1 trang trong các list views
+const MAX_PAGE_SCAN: usize = 500; // Số keys tối đa được đọc trong 1 trang (kể cả keys bị bỏ qua) -> Giới hạn gas của 1 trang
+const SHA256_HASH_LEN: usize = 32; // Độ dài (bytes) của media_hash, reference_hash
+const STORAGE_BYTES_PER_RECORD: u64 = 40; // Storage tính thêm cho mỗi record key-value (NEAR runtime)
+const MAX_ACCOUNT_ID_LEN: u64 = 64; // Độ dài tối đa của 1 AccountId -> Dùng để ước tính storage khi chưa biết receiver
 
 pub type CollectionId = u32;
 pub type CollectionName = String;
@@ -19,11 +27,27 @@ pub type SchemaId = u32;
 pub type SchemaName = String;
 pub type TemplateId = u32;
 pub type TokenId = u32;
+// Token id dạng string trong JSON của các method theo chuẩn NEP-171/178/181
+pub type JsonTokenId = String;
 pub type LootboxId = u32;
 pub type LootboxNftId = u32;
 pub type DropId = u32;
+pub type BlendId = u32;
+pub type UpgradeRuleId = u32;
+pub type AirdropJobId = u32;
+// Key of the data of 1 account inside 1 Drop Sale: <drop_id>.<account_id>
+pub type DropAndAccountId = String;
+// Key of a Mint Voucher nonce inside 1 Collection: <collection_name>.<nonce>
+pub type CollectionAndNonce = String;
+// Key of the staking rate of 1 rarity inside 1 Collection: <collection_name>.<rarity>
+pub type CollectionAndRarity = String;
+// Key of the data of 1 account inside 1 Collection: <collection_name>.<account_id>
+pub type CollectionAndAccountId = String;
 
+pub use crate::admin::*;
+pub use crate::airdrop::*;
 pub use crate::approval::*;
+pub use crate::blend::*;
 pub use crate::collections::*;
 pub use crate::custom_struct::*;
 pub use crate::drop::*;
@@ -31,13 +55,21 @@ pub use crate::event::*;
 pub use crate::internal::*;
 pub use crate::lootbox::*;
 pub use crate::metadata::*;
+pub use crate::migrate::*;
 pub use crate::nft::*;
 pub use crate::nft_core::*;
 pub use crate::schemas::*;
+pub use crate::search::*;
+pub use crate::staking::*;
 pub use crate::templates::*;
+pub use crate::upgrade::*;
 use crate::utils::*;
+pub use crate::voucher::*;
 
+mod admin;
+mod airdrop;
 mod approval;
+mod blend;
 mod collections;
 mod custom_struct;
 mod drop;
@@ -45,32 +77,72 @@ mod event;
 mod internal;
 mod lootbox;
 mod metadata;
+mod migrate;
+mod moderation;
 mod nft;
 mod nft_core;
 mod schemas;
+mod search;
+mod staking;
 mod templates;
+mod upgrade;
 mod utils;
+mod voucher;
 
+// Các index được phân trang là TreeMap (keys có thứ tự) -> 1 trang chỉ đọc các keys sau cursor
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct NFTContract {
     pub owner_id: AccountId, // Chủ sở hữu của Contract
+    pub roles_by_account: TreeMap<AccountId, Vec<Role>>, // Roles được owner cấp cho từng account
+    pub paused_features: Vec<PauseFeature>,                    // Các features đang bị pause
     pub collections_per_owner: LookupMap<AccountId, UnorderedSet<CollectionName>>, // Lưu danh sách NFT Collections mà user sở hữu
     pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>, // Lưu danh sách NFT mà user sở hữu
     pub collections_by_name: UnorderedMap<CollectionName, Collection>, // Danh sách tất cả Collections của Contract
     pub collections_by_id: UnorderedMap<CollectionId, Collection>, // Danh sách tất cả Collections của Contract
     pub schemas_by_id: UnorderedMap<SchemaId, Schema>, // Danh sách tất cả Schemas của Contract
+    pub schemas_per_collection: LookupMap<CollectionName, TreeMap<SchemaId, ()>>, // Index: Schemas của từng Collection
     pub templates_by_id: UnorderedMap<TemplateId, Template>, // Danh sách tất cả Templates của Contract
+    pub templates_per_collection: LookupMap<CollectionName, TreeMap<TemplateId, ()>>, // Index: Templates (gồm cả Lootboxes) của từng Collection
+    pub lootboxes_per_collection: LookupMap<CollectionName, TreeMap<LootboxId, ()>>, // Index: Lootboxes của từng Collection
+    pub lootbox_ids: TreeMap<LootboxId, ()>, // Index: tất cả Lootboxes của Contract
     pub token_id_counter: u32,                               // Auto increment Token id
     pub token_by_template_id_counter: UnorderedMap<TemplateId, TokenId>, // Đếm stt hiện tại của từng NFT trong Template
     pub tokens_by_id: UnorderedMap<TokenId, Token>, // Danh sách tất cả NFT Tokens của Contract
+    pub tokens_per_template: LookupMap<TemplateId, TreeMap<TokenId, ()>>, // Index: NFTs của từng Template (gồm cả Lootboxes)
+    pub tokens_per_collection: LookupMap<CollectionName, TreeMap<TokenId, ()>>, // Index: NFTs của từng Collection
+    pub holders_per_collection: LookupMap<CollectionName, TreeMap<AccountId, u32>>, // Index: số NFTs của từng holder trong từng Collection
     // pub lootboxes_by_id: UnorderedMap<LootboxId, Lootbox>, // Danh sách tất cả Lootboxs của Contract
     pub lootbox_nft_by_lootbox_id_counter: UnorderedMap<LootboxId, LootboxNftId>, // Đếm stt hiện tại của từng Lootbox NFT trong Lootbox
+    pub drop_id_counter: u32,                        // Auto increment Drop id
     pub drops_by_id: UnorderedMap<DropId, DropSale>, // Danh sách tất cả Lootboxs của Contract
+    pub drops_per_collection: LookupMap<CollectionName, TreeMap<DropId, ()>>, // Index: Drop Sales của từng Collection
+    pub drop_payment_by_account: LookupMap<DropAndAccountId, DropPayment>, // Amount paid by each account in Dutch auction Drop Sales (for rebates)
+    pub claim_codes_per_drop: LookupMap<DropId, UnorderedMap<CryptoHash, bool>>, // Claim code hashes of each claim-code Drop Sale (true if the code has been used)
+    pub used_tokens_per_drop: LookupMap<DropId, UnorderedSet<TokenId>>, // Holding tokens already used to claim each token-gated Drop Sale
+    pub blend_id_counter: u32,                         // Auto increment Blend id
+    pub blends_by_id: UnorderedMap<BlendId, BlendRecipe>, // Danh sách tất cả Blend recipes của Contract
+    pub blends_per_collection: LookupMap<CollectionName, TreeMap<BlendId, ()>>, // Index: Blend recipes của từng Collection
+    pub upgrade_rule_id_counter: u32,                  // Auto increment Upgrade rule id
+    pub upgrade_rules_by_id: UnorderedMap<UpgradeRuleId, UpgradeRule>, // Danh sách tất cả Upgrade rules của Contract
+    pub upgrade_rules_per_collection: LookupMap<CollectionName, TreeMap<UpgradeRuleId, ()>>, // Index: Upgrade rules của từng Collection
+    pub token_locks_by_id: LookupMap<TokenId, TokenLock>, // Locked (staked) NFTs
+    pub staking_rate_per_template: LookupMap<TemplateId, u128>, // Points per second of the locked NFTs of each Template
+    pub staking_rate_per_rarity: LookupMap<CollectionAndRarity, u128>, // Points per second of the locked NFTs of each rarity
+    pub staking_points_by_account: LookupMap<CollectionAndAccountId, u128>, // Staking points of each account in each Collection
+    pub staking_points_consumers: LookupSet<CollectionAndAccountId>, // Accounts allowed to consume the staking points of each Collection
+    pub airdrop_job_id_counter: u32,                   // Auto increment Airdrop job id
+    pub airdrop_jobs_by_id: LookupMap<AirdropJobId, AirdropJob>, // Danh sách tất cả Airdrop jobs của Contract
+    pub airdrop_recipients_per_job: LookupMap<AirdropJobId, Vector<(AccountId, u32)>>, // Recipients of each unfinished Airdrop job
+    pub operator_approval_id_counter: u64,             // Auto increment operator approval id
+    pub operators_per_owner: LookupMap<AccountId, HashMap<AccountId, OperatorApproval>>, // Operators allowed to transfer all the NFTs of each owner
     pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>, // Mapping token id với token metadata
+    pub used_voucher_nonces: LookupSet<CollectionAndNonce>, // Nonces of the Mint Vouchers that have been redeemed
+    pub search_index: LookupMap<String, TreeMap<SearchEntry, ()>>, // Index: prefix của các từ trong tên -> Collections, Schemas, Templates
     pub metadata: LazyOption<NFTContractMetadata>,
 }
 
+// Storage prefix = Borsh index of the variant -> Only add new keys at the end (the deployed state uses the old indexes)
 #[derive(BorshDeserialize, BorshSerialize)]
 pub enum StorageKey {
     CollectionsPerOwnerKey,
@@ -95,14 +167,84 @@ pub enum StorageKey {
     DropsByIdKey,
     TokenMetadataByIdKey,
     ContractMetadataKey,
+    DropPaymentByAccountKey,
+    UsedVoucherNoncesKey,
+    ClaimCodesPerDropKey,
+    ClaimCodesPerDropInnerKey {
+        drop_id: DropId,
+    },
+    UsedTokensPerDropKey,
+    UsedTokensPerDropInnerKey {
+        drop_id: DropId,
+    },
+    BlendsByIdKey,
+    UpgradeRulesByIdKey,
+    TokenLocksByIdKey,
+    StakingRatePerTemplateKey,
+    StakingRatePerRarityKey,
+    StakingPointsByAccountKey,
+    StakingPointsConsumersKey,
+    AirdropJobsByIdKey,
+    AirdropRecipientsPerJobKey,
+    AirdropRecipientsPerJobInnerKey {
+        job_id: AirdropJobId,
+    },
+    OperatorsPerOwnerKey,
+    RolesByAccountKey,
+    SchemasPerCollectionKey,
+    SchemasPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    TemplatesPerCollectionKey,
+    TemplatesPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    LootboxesPerCollectionKey,
+    LootboxesPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    LootboxIdsKey,
+    DropsPerCollectionKey,
+    DropsPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    TokensPerTemplateKey,
+    TokensPerTemplateInnerKey {
+        template_id: TemplateId,
+    },
+    TokensPerCollectionKey,
+    TokensPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    HoldersPerCollectionKey,
+    HoldersPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    SearchIndexKey,
+    SearchIndexInnerKey {
+        prefix_hash: CryptoHash,
+    },
+    BlendsPerCollectionKey,
+    BlendsPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
+    UpgradeRulesPerCollectionKey,
+    UpgradeRulesPerCollectionInnerKey {
+        collection_name_hash: CryptoHash,
+    },
 }
 
 #[near_bindgen]
 impl NFTContract {
     #[init]
     pub fn new(owner_id: AccountId, token_metadata: NFTContractMetadata) -> Self {
+        // Contract mới luôn dùng layout hiện tại
+        write_state_version();
+
         Self {
             owner_id,
+            roles_by_account: TreeMap::new(StorageKey::RolesByAccountKey.try_to_vec().unwrap()),
+            paused_features: Vec::new(),
             collections_per_owner: LookupMap::new(
                 StorageKey::CollectionsPerOwnerKey.try_to_vec().unwrap(),
             ),
@@ -114,22 +256,88 @@ impl NFTContract {
                 StorageKey::CollectionsByNameKey.try_to_vec().unwrap(),
             ),
             schemas_by_id: UnorderedMap::new(StorageKey::SchemasByIdKey.try_to_vec().unwrap()),
+            schemas_per_collection: LookupMap::new(
+                StorageKey::SchemasPerCollectionKey.try_to_vec().unwrap(),
+            ),
             templates_by_id: UnorderedMap::new(StorageKey::TemplatesByIdKey.try_to_vec().unwrap()),
+            templates_per_collection: LookupMap::new(
+                StorageKey::TemplatesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            lootboxes_per_collection: LookupMap::new(
+                StorageKey::LootboxesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            lootbox_ids: TreeMap::new(StorageKey::LootboxIdsKey.try_to_vec().unwrap()),
             token_id_counter: 0,
             token_by_template_id_counter: UnorderedMap::new(
                 StorageKey::TokenByTemplateIdCounter.try_to_vec().unwrap(),
             ),
             tokens_by_id: UnorderedMap::new(StorageKey::TokensByIdKey.try_to_vec().unwrap()),
+            tokens_per_template: LookupMap::new(StorageKey::TokensPerTemplateKey.try_to_vec().unwrap()),
+            tokens_per_collection: LookupMap::new(
+                StorageKey::TokensPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            holders_per_collection: LookupMap::new(
+                StorageKey::HoldersPerCollectionKey.try_to_vec().unwrap(),
+            ),
             // lootboxes_by_id: UnorderedMap::new(StorageKey::LootboxesByIdKey.try_to_vec().unwrap()),
             lootbox_nft_by_lootbox_id_counter: UnorderedMap::new(
                 StorageKey::LootboxNftByLootboxIdCounter
                     .try_to_vec()
                     .unwrap(),
             ),
+            drop_id_counter: 0,
             drops_by_id: UnorderedMap::new(StorageKey::DropsByIdKey.try_to_vec().unwrap()),
+            drops_per_collection: LookupMap::new(
+                StorageKey::DropsPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            drop_payment_by_account: LookupMap::new(
+                StorageKey::DropPaymentByAccountKey.try_to_vec().unwrap(),
+            ),
+            claim_codes_per_drop: LookupMap::new(
+                StorageKey::ClaimCodesPerDropKey.try_to_vec().unwrap(),
+            ),
+            used_tokens_per_drop: LookupMap::new(
+                StorageKey::UsedTokensPerDropKey.try_to_vec().unwrap(),
+            ),
+            blend_id_counter: 0,
+            blends_by_id: UnorderedMap::new(StorageKey::BlendsByIdKey.try_to_vec().unwrap()),
+            blends_per_collection: LookupMap::new(
+                StorageKey::BlendsPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            upgrade_rule_id_counter: 0,
+            upgrade_rules_by_id: UnorderedMap::new(
+                StorageKey::UpgradeRulesByIdKey.try_to_vec().unwrap(),
+            ),
+            upgrade_rules_per_collection: LookupMap::new(
+                StorageKey::UpgradeRulesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            token_locks_by_id: LookupMap::new(StorageKey::TokenLocksByIdKey.try_to_vec().unwrap()),
+            staking_rate_per_template: LookupMap::new(
+                StorageKey::StakingRatePerTemplateKey.try_to_vec().unwrap(),
+            ),
+            staking_rate_per_rarity: LookupMap::new(
+                StorageKey::StakingRatePerRarityKey.try_to_vec().unwrap(),
+            ),
+            staking_points_by_account: LookupMap::new(
+                StorageKey::StakingPointsByAccountKey.try_to_vec().unwrap(),
+            ),
+            staking_points_consumers: LookupSet::new(
+                StorageKey::StakingPointsConsumersKey.try_to_vec().unwrap(),
+            ),
+            airdrop_job_id_counter: 0,
+            airdrop_jobs_by_id: LookupMap::new(StorageKey::AirdropJobsByIdKey.try_to_vec().unwrap()),
+            airdrop_recipients_per_job: LookupMap::new(
+                StorageKey::AirdropRecipientsPerJobKey.try_to_vec().unwrap(),
+            ),
+            operator_approval_id_counter: 0,
+            operators_per_owner: LookupMap::new(StorageKey::OperatorsPerOwnerKey.try_to_vec().unwrap()),
             token_metadata_by_id: UnorderedMap::new(
                 StorageKey::TokenMetadataByIdKey.try_to_vec().unwrap(),
             ),
+            used_voucher_nonces: LookupSet::new(
+                StorageKey::UsedVoucherNoncesKey.try_to_vec().unwrap(),
+            ),
+            search_index: LookupMap::new(StorageKey::SearchIndexKey.try_to_vec().unwrap()),
             metadata: LazyOption::new(
                 StorageKey::ContractMetadataKey.try_to_vec().unwrap(),
                 Some(&token_metadata),
@@ -142,7 +350,7 @@ impl NFTContract {
         Self::new(
             owner_id,
             NFTContractMetadata {
-                spec: "nearhub-nft-1.0.0".to_string(),
+                spec: "nft-1.0.0".to_string(),
                 name: "Nearhub NFT".to_string(),
                 symbol: "NHT".to_string(),
                 icon: None,
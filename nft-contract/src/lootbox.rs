@@ -19,6 +19,7 @@ impl NFTContract {
         // display_data: Option<String>,
         config: Option<LootboxConfig>,
     ) -> Template {
+        self.assert_not_paused(PauseFeature::Lootbox);
         let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
 
         let lootbox_id = self.templates_by_id.len() as u32;
@@ -33,6 +34,8 @@ impl NFTContract {
             .get(&collection_name)
             .expect("Collection not exists");
         let collection_of_lootbox_id = collection_of_lootbox.collection_id;
+        // Collection bị freeze thì không được tạo Lootbox
+        self.assert_collection_not_frozen(&collection_name);
 
         // Check signer id is Collection's owner or not
         assert_eq!(
@@ -58,6 +61,9 @@ impl NFTContract {
             .get(&schema_id)
             .expect("Schema does not exist");
 
+        // Check link + hash của ảnh và reference
+        self.assert_valid_immutable_data(&immutable_data);
+
         // Tạo Lootbox mới
         let new_lootbox = Template {
             template_id: lootbox_id,
@@ -69,7 +75,9 @@ impl NFTContract {
             burnable,
             max_supply,
             issued_supply: 0,
+            burned_supply: 0,
             immutable_data,
+            validity_duration: None,
             is_lootbox: true,
             unlock_time,
             config,
@@ -77,6 +85,32 @@ impl NFTContract {
 
         // Insert lootbox mới vào templates_by_id
         self.templates_by_id.insert(&lootbox_id, &new_lootbox);
+        // Insert lootbox mới vào index của Collection + index của Lootboxes
+        self.internal_add_template_to_collection(&new_lootbox.collection_name, lootbox_id, true);
+        // Index tên của Lootbox để search
+        self.internal_update_search_index(
+            SearchKind::Template,
+            lootbox_id,
+            &[],
+            &[&new_lootbox.immutable_data.name],
+        );
+
+        // -------------------------------------------------------------------
+        // LOOTBOX CREATE LOG
+        let lootbox_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::LootboxCreate(vec![TemplateCreateLog {
+                owner_id: collection_of_lootbox.owner_id.to_string(),
+                collection_name: new_lootbox.collection_name.clone(),
+                schema_id: schema_id.to_string(),
+                template_id: lootbox_id.to_string(),
+                max_supply,
+                memo: None,
+            }]),
+        };
+        env::log(lootbox_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
 
         // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
         let after_storage_usage = env::storage_usage();
@@ -93,6 +127,7 @@ impl NFTContract {
         mint_number: Option<u32>,
         receiver_id: AccountId,
     ) {
+        self.assert_not_paused(PauseFeature::Lootbox);
         let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
 
         // Check if the person who call this function is the Collection's owner or not
@@ -136,6 +171,7 @@ impl NFTContract {
         lootbox_nft_id: TokenId,
         // metadata: TokenMetadata,
     ) {
+        self.assert_not_paused(PauseFeature::Lootbox);
         let mut rng = Rng::new(&env::random_seed());
         let receiver_id = env::predecessor_account_id();
 
@@ -166,23 +202,22 @@ impl NFTContract {
             lootbox_nft.owner_id,
             "Only owner of this lootbox nft can unbox it!"
         );
+        // Unbox burns the Lootbox NFT -> Locked Lootbox cannot be unboxed
+        self.assert_token_not_locked(&lootbox_nft_id);
 
         // Check current time is after lootbox.unlock_time or not
-        let unbox_time = env::block_timestamp(); // Claim drop timestamp in nanoseconds
-
-        let base: u64 = 10;
+        let unbox_time = current_time_ms(); // Unbox timestamp in milliseconds
 
         log!("Current time: {}", unbox_time);
         if lootbox_nft_metadata.starts_at.is_some() {
             // If lootbox.unclock_time == 0 -> Can unbox at any time
-            let starts_at_nano = lootbox_nft_metadata.starts_at.unwrap() * base.pow(6); // Claim drop timestamp in milliseconds
+            let starts_at = lootbox_nft_metadata.starts_at.unwrap(); // Unlock timestamp in milliseconds
             assert!(
-                unbox_time >= starts_at_nano,
+                unbox_time >= starts_at,
                 "Cannot unbox this Lootbox during this time"
             );
         }
 
-        let mut _result = 0; // result: template_id random ra được
         let mut result_arr = Vec::<u32>::new(); // result_arr: Mảng chứa kết quả các template_id phần thưởng trong Lootbox
 
         let config: LootboxConfig =
@@ -192,20 +227,13 @@ impl NFTContract {
         // Duyệt mảng config của lootbox
         for slot in config.iter() {
             // Trả về kết quả random -> quyết định mint ra NFT thuộc template_id nào
-            // Random từ 0 -> total_odds
-            let value = rng.rand_range_u32(0, slot.total_odds);
-
-            let mut result = 0;
-            // Lấy random value
-            for i in 0..slot.outcomes.len() - 1 {
-                // If 0 <= value < slot.outcomes[0]
-                if value < slot.outcomes[0].odds {
-                    result = slot.outcomes[0].template_id;
-                } else if value >= slot.outcomes[i].odds && value < slot.outcomes[i + 1].odds {
-                    result = slot.outcomes[i + 1].template_id;
+            // Random từ 0 -> total_odds, phần sau outcome cuối cùng -> slot này không ra NFT nào
+            let weights = lootbox_slot_weights(slot);
+            if let Some(index) = random_weighted_index(&mut rng, &weights) {
+                if let Some(outcome) = slot.outcomes.get(index) {
+                    result_arr.push(outcome.template_id);
                 }
             }
-            result_arr.push(result);
         }
 
         for template_id in result_arr.iter() {
@@ -229,6 +257,7 @@ impl NFTContract {
 
         // Delete Lootbox NFT
         self.tokens_by_id.remove(&lootbox_nft_id);
+        self.internal_remove_token_from_indexes(&lootbox_nft);
 
         // Delete Lootbox NFT Metadata
         self.token_metadata_by_id.remove(&lootbox_nft_id);
@@ -248,18 +277,7 @@ impl NFTContract {
     //  -------------------------------------- ENUMERATION --------------------------------------
     // Lấy tổng số Lootboxes đang có trong contract
     pub fn lootbox_total_supply(&self) -> U128 {
-        // Đếm tổng số lượng id đang có trong token_metadata_by_id
-
-        let template_set: Vec<Template> = self.templates_by_id.values().collect();
-        let mut lootbox_set = Vec::<Template>::new();
-
-        for template in template_set {
-            if template.is_lootbox == true {
-                lootbox_set.push(template);
-            }
-        }
-
-        U128(lootbox_set.len() as u128)
+        U128(self.lootbox_ids.len() as u128)
     }
 
     // Lấy tổng số Lootboxes đang có của Collection nào đó
@@ -270,20 +288,51 @@ impl NFTContract {
             "Collection does not exist"
         );
 
-        let mut count = 0;
+        U128(collection_index_len(&self.lootboxes_per_collection, &collection_name) as u128)
+    }
 
-        let templates_set_by_collection: Vec<Template> = self
-            .templates_by_id
-            .keys()
-            .map(|template_id| self.templates_by_id.get(&template_id).unwrap())
-            .collect();
+    // Lấy danh sách tất cả Lootboxes trong Contract (cursor: lootbox id)
+    pub fn get_all_lootboxes(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Template> {
+        paginate_tree_map(&self.lootbox_ids, cursor, limit, |lootbox_id| {
+            self.templates_by_id.get(lootbox_id)
+        })
+    }
 
-        for template in templates_set_by_collection {
-            if template.collection_name == collection_name && template.is_lootbox == true {
-                count += 1;
-            }
-        }
+    // Lấy danh sách Lootboxes của Collection nào đó (cursor: lootbox id)
+    pub fn get_all_lootboxes_by_collection(
+        &self,
+        collection_name: CollectionName,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<Template> {
+        // Check collection id có tồn tại không
+        assert!(
+            self.collections_by_name.get(&collection_name).is_some(),
+            "Collection does not exist"
+        );
+
+        // Duyệt các lootbox ids trong index của Collection -> Trả về Lootbox
+        paginate_collection_index(
+            &self.lootboxes_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |lootbox_id| self.templates_by_id.get(lootbox_id),
+        )
+    }
+}
 
-        U128(count)
+// Weight of each outcome of a Lootbox slot
+// outcome.odds là cận trên (cộng dồn) của khoảng random -> weight = odds - odds của outcome trước
+// Weight cuối cùng: phần còn lại tới total_odds (không ra NFT nào)
+fn lootbox_slot_weights(slot: &Slot) -> Vec<u64> {
+    let mut weights = Vec::<u64>::new();
+    let mut lower_bound = 0;
+    for outcome in slot.outcomes.iter() {
+        let upper_bound = std::cmp::min(std::cmp::max(outcome.odds, lower_bound), slot.total_odds);
+        weights.push((upper_bound - lower_bound) as u64);
+        lower_bound = upper_bound;
     }
+    weights.push((slot.total_odds - lower_bound) as u64);
+    weights
 }
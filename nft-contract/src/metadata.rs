@@ -17,10 +17,21 @@ pub struct Token {
     pub next_approval_id: u64,                         // Id của approve tiếp theo
 }
 
-// Dạng Json của NFT để trả về giá trị cần thiết
+// Dạng Json của NFT theo chuẩn NEP-171 (token_id là string)
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct JsonToken {
+    pub token_id: JsonTokenId,   // Id của NFT
+    pub owner_id: AccountId,     // Chủ sở hữu của NFT
+    pub metadata: TokenMetadata, // Metadata của NFT (NEP-177)
+
+    pub approved_account_ids: HashMap<AccountId, u64>, // Danh sách các accounts được approved để transfer Token này (NEP-178)
+}
+
+// Dạng Json của NFT kèm các thông tin riêng của NearHub (Collection, Schema, Template)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTokenDetail {
     pub owner_id: AccountId,           // Chủ sở hữu của NFT
     pub token_id: TokenId,             // Id của NFT
     pub token_by_template_id: TokenId, // Stt của NFT trong template nó thuộc vào
@@ -77,3 +88,26 @@ impl NonFungibleTokenMetadata for NFTContract {
         self.metadata.get().unwrap()
     }
 }
+
+#[near_bindgen]
+impl NFTContract {
+    // Owner cập nhật base_uri: gateway để resolve các link tương đối (VD: IPFS CID) trong media / reference
+    #[payable]
+    pub fn set_base_uri(&mut self, base_uri: Option<String>) {
+        assert_at_least_one_yocto();
+        self.assert_contract_owner();
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        if let Some(base_uri) = base_uri.as_ref() {
+            assert!(is_absolute_link(base_uri), "base_uri must be an absolute URL");
+        }
+
+        let mut metadata = self.metadata.get().unwrap();
+        metadata.base_uri = base_uri;
+        self.metadata.set(&metadata);
+
+        // Storage tăng -> Trừ vào deposit, storage giảm -> Chỉ refund deposit
+        let after_storage_usage = env::storage_usage();
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+}
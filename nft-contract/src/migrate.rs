@@ -0,0 +1,592 @@
+// Upgrade the code of the Contract and migrate its state to the new layout
+use crate::*;
+
+const GAS_FOR_UPGRADE: Gas = 20_000_000_000_000;
+const NO_DEPOSIT: Balance = 0;
+
+// Storage key of the state version (the deployed baseline doesn't have this key -> version 1)
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+pub const CURRENT_STATE_VERSION: u32 = 2;
+// Storage key of the V1 -> V2 migration progress (only exists while migrate_state is running)
+const MIGRATION_PROGRESS_KEY: &[u8] = b"MIGRATION_PROGRESS";
+const DEFAULT_MIGRATION_LIMIT: u64 = 100;
+
+// ----------------------------------- Versioned State -----------------------------------
+// Every layout of the Contract state that has been deployed
+/**
+ * - V1: baseline layout (no STATE_VERSION key)
+ * - V2: current layout
+ * - Khi deploy 1 layout mới: thêm 1 variant mới + routine migrate từ các layout cũ
+ */
+pub enum VersionedNFTContract {
+    V1(Box<NFTContractV1>),
+    V2(Box<NFTContract>),
+}
+
+impl VersionedNFTContract {
+    // Đọc state hiện tại theo version đã lưu
+    pub fn read() -> Self {
+        match read_state_version() {
+            1 => Self::V1(Box::new(
+                env::state_read().expect("Contract is not initialized"),
+            )),
+            CURRENT_STATE_VERSION => Self::V2(Box::new(
+                env::state_read().expect("Contract is not initialized"),
+            )),
+            version => env::panic(format!("Unknown state version {}", version).as_bytes()),
+        }
+    }
+
+    pub fn into_current(self) -> NFTContract {
+        match self {
+            Self::V1(contract) => contract.migrate(),
+            Self::V2(contract) => *contract,
+        }
+    }
+}
+
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| u32::try_from_slice(&version).expect("Invalid state version"))
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(
+        STATE_VERSION_KEY,
+        &CURRENT_STATE_VERSION.try_to_vec().unwrap(),
+    );
+}
+
+// ----------------------------------- Migration Progress -----------------------------------
+// Các bước của migrate_state, theo thứ tự
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationStep {
+    Collections, // Ghi lại Collections + search_index
+    Schemas,     // schemas_per_collection + search_index
+    Templates,   // Ghi lại Templates + templates_per_collection, lootboxes + search_index
+    Drops,       // Ghi lại Drop Sales + drops_per_collection
+    Tokens,      // Timestamps, media_hash của NFTs + các index theo Template, Collection
+    Done,
+}
+
+// Tiến độ migrate state V1, lưu ở key riêng tới khi migrate_state xong
+/**
+ * - collections, schemas, templates, drops, tokens: số entries của state V1
+ * - Entries được thêm sau khi upgrade đã theo layout hiện tại -> Không migrate lại
+ */
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MigrationProgress {
+    pub step: MigrationStep,
+    pub next_index: u64, // Index của entry tiếp theo trong step hiện tại
+    pub collections: u64,
+    pub schemas: u64,
+    pub templates: u64,
+    pub drops: u64,
+    pub tokens: u64,
+}
+
+impl MigrationProgress {
+    // Số entries V1 của step hiện tại
+    fn step_len(&self) -> u64 {
+        match self.step {
+            MigrationStep::Collections => self.collections,
+            MigrationStep::Schemas => self.schemas,
+            MigrationStep::Templates => self.templates,
+            MigrationStep::Drops => self.drops,
+            MigrationStep::Tokens => self.tokens,
+            MigrationStep::Done => 0,
+        }
+    }
+
+    fn next_step(&mut self) {
+        self.step = match self.step {
+            MigrationStep::Collections => MigrationStep::Schemas,
+            MigrationStep::Schemas => MigrationStep::Templates,
+            MigrationStep::Templates => MigrationStep::Drops,
+            MigrationStep::Drops => MigrationStep::Tokens,
+            MigrationStep::Tokens | MigrationStep::Done => MigrationStep::Done,
+        };
+        self.next_index = 0;
+    }
+}
+
+pub(crate) fn read_migration_progress() -> Option<MigrationProgress> {
+    env::storage_read(MIGRATION_PROGRESS_KEY).map(|progress| {
+        MigrationProgress::try_from_slice(&progress).expect("Invalid migration progress")
+    })
+}
+
+fn write_migration_progress(progress: &MigrationProgress) {
+    env::storage_write(MIGRATION_PROGRESS_KEY, &progress.try_to_vec().unwrap());
+}
+
+// State V1 chưa migrate xong -> panic (các values cũ chưa đọc được theo layout hiện tại)
+pub(crate) fn assert_state_migrated() {
+    assert!(
+        !env::storage_has_key(MIGRATION_PROGRESS_KEY),
+        "The contract state is being migrated"
+    );
+}
+
+// ----------------------------------- V1 Layout -----------------------------------
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct NFTContractV1 {
+    pub owner_id: AccountId,
+    pub collections_per_owner: LookupMap<AccountId, UnorderedSet<CollectionName>>,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub collections_by_name: UnorderedMap<CollectionName, CollectionV1>,
+    pub collections_by_id: UnorderedMap<CollectionId, CollectionV1>,
+    pub schemas_by_id: UnorderedMap<SchemaId, Schema>,
+    pub templates_by_id: UnorderedMap<TemplateId, TemplateV1>,
+    pub token_id_counter: u32,
+    pub token_by_template_id_counter: UnorderedMap<TemplateId, TokenId>,
+    pub tokens_by_id: UnorderedMap<TokenId, Token>,
+    pub lootbox_nft_by_lootbox_id_counter: UnorderedMap<LootboxId, LootboxNftId>,
+    pub drops_by_id: UnorderedMap<DropId, DropSaleV1>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CollectionV1 {
+    pub collection_id: CollectionId,
+    pub owner_id: AccountId,
+    pub collection_name: String,
+    pub market_fee: f32,
+    pub data: CollectionExtraData,
+    pub approved_account_ids: HashMap<AccountId, u64>,
+    pub next_approval_id: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TemplateV1 {
+    pub template_id: TemplateId,
+    pub collection_id: CollectionId,
+    pub collection_name: String,
+    pub schema_id: SchemaId,
+    pub schema_name: String,
+    pub transferable: bool,
+    pub burnable: bool,
+    pub max_supply: u32,
+    pub issued_supply: u32,
+    pub immutable_data: ImmutableDataV1,
+    pub is_lootbox: bool,
+    pub unlock_time: Option<Timestamp>,
+    pub config: Option<LootboxConfig>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ImmutableDataV1 {
+    pub name: String,
+    pub img: Option<String>,
+    pub rarity: Option<String>,
+    pub extra_immutable_data: Option<String>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct DropSaleV1 {
+    pub drop_id: DropId,
+    pub owner_id: AccountId,
+    pub collection_name: CollectionName,
+    pub template_ids: Vec<TemplateId>,
+    pub price: U128,
+    pub price_type: String,
+    pub is_public: bool,
+    pub max_supply: u32,
+    pub issued_supply: u32,
+    pub account_limit: u32,
+    pub account_limit_cooldown: Timestamp,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub display_data: Option<String>,
+    pub approved_account_ids: HashMap<AccountId, u64>,
+    pub next_approval_id: u64,
+}
+
+// ----------------------------------- V1 -> V2 -----------------------------------
+impl From<CollectionV1> for Collection {
+    fn from(collection: CollectionV1) -> Self {
+        Self {
+            collection_id: collection.collection_id,
+            owner_id: collection.owner_id,
+            collection_name: collection.collection_name,
+            market_fee: collection.market_fee,
+            data: collection.data,
+            approved_account_ids: collection.approved_account_ids,
+            next_approval_id: collection.next_approval_id,
+            voucher_public_key: None,
+            allow_expired_transfer: true,
+            moderation: CollectionModeration::default(),
+        }
+    }
+}
+
+// V1 media_hash was not a real hash -> Templates cũ không có img_hash
+impl From<ImmutableDataV1> for ImmutableData {
+    fn from(immutable_data: ImmutableDataV1) -> Self {
+        Self {
+            name: immutable_data.name,
+            img: immutable_data.img,
+            rarity: immutable_data.rarity,
+            extra_immutable_data: immutable_data.extra_immutable_data,
+            img_hash: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+}
+
+impl From<TemplateV1> for Template {
+    fn from(template: TemplateV1) -> Self {
+        Self {
+            template_id: template.template_id,
+            collection_id: template.collection_id,
+            collection_name: template.collection_name,
+            schema_id: template.schema_id,
+            schema_name: template.schema_name,
+            transferable: template.transferable,
+            burnable: template.burnable,
+            max_supply: template.max_supply,
+            issued_supply: template.issued_supply,
+            burned_supply: 0,
+            immutable_data: template.immutable_data.into(),
+            validity_duration: None,
+            is_lootbox: template.is_lootbox,
+            unlock_time: template.unlock_time, // V1 đã dùng milliseconds
+            config: template.config,
+        }
+    }
+}
+
+impl From<DropSaleV1> for DropSale {
+    fn from(drop: DropSaleV1) -> Self {
+        Self {
+            drop_id: drop.drop_id,
+            owner_id: drop.owner_id,
+            collection_name: drop.collection_name,
+            template_ids: drop.template_ids,
+            price: drop.price,
+            price_type: drop.price_type,
+            is_public: drop.is_public,
+            max_supply: drop.max_supply,
+            issued_supply: drop.issued_supply,
+            account_limit: drop.account_limit,
+            account_limit_cooldown: drop.account_limit_cooldown,
+            start_time: drop.start_time,
+            end_time: drop.end_time,
+            display_data: drop.display_data,
+            approved_account_ids: drop.approved_account_ids,
+            next_approval_id: drop.next_approval_id,
+            pricing_mode: DropPricingMode::Fixed,
+            last_claim_price: drop.price,
+            distribution_mode: DropDistributionMode::AllTemplates,
+            is_paused: false,
+            claim_mode: DropClaimMode::Purchase,
+            storage_balance: U128(0),
+            access_rule: None,
+        }
+    }
+}
+
+// Đổi kiểu value của 1 UnorderedMap, không ghi lại các values (cùng storage prefix)
+fn cast_map<K, V1, V2>(map: UnorderedMap<K, V1>) -> UnorderedMap<K, V2>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V1: BorshSerialize + BorshDeserialize,
+    V2: BorshSerialize + BorshDeserialize,
+{
+    UnorderedMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap()
+}
+
+// Ghi lại value thứ `index` của 1 UnorderedMap (đang lưu theo layout V1) theo layout hiện tại
+fn migrate_map_value<K, V1, V2>(map: &UnorderedMap<K, V2>, index: u64) -> V2
+where
+    K: BorshSerialize + BorshDeserialize,
+    V1: BorshSerialize + BorshDeserialize,
+    V2: BorshSerialize + BorshDeserialize + From<V1>,
+{
+    let raw_value = map
+        .values_as_vector()
+        .get_raw(index)
+        .expect("Index out of bounds");
+    let value = V2::from(V1::try_from_slice(&raw_value).expect("Invalid V1 value"));
+
+    // Vector cùng prefix + len với values của map -> replace trực tiếp trên storage
+    let mut values: Vector<V2> =
+        Vector::try_from_slice(&map.values_as_vector().try_to_vec().unwrap()).unwrap();
+    values.replace_raw(index, &value.try_to_vec().unwrap());
+    value
+}
+
+// V1 issued_at is in nanoseconds, the current layout uses milliseconds
+fn nanos_to_millis(timestamp: Option<u64>) -> Option<u64> {
+    timestamp.map(|timestamp| timestamp / 1_000_000)
+}
+
+impl NFTContractV1 {
+    // Các field cũ giữ nguyên storage prefix, các field mới dùng các StorageKey mới
+    /**
+     * - Chỉ đổi layout của Contract struct, values của Collections, Templates, Drop Sales, NFTs
+     *   được ghi lại theo từng batch bằng migrate_state (V1 có thể có rất nhiều entries)
+     * - Mint, transfer, burn, Drop Sales, Lootboxes bị chặn tới khi migrate_state xong
+     */
+    pub fn migrate(self) -> NFTContract {
+        write_migration_progress(&MigrationProgress {
+            step: MigrationStep::Collections,
+            next_index: 0,
+            collections: self.collections_by_id.len(),
+            schemas: self.schemas_by_id.len(),
+            templates: self.templates_by_id.len(),
+            drops: self.drops_by_id.len(),
+            tokens: self.tokens_by_id.len(),
+        });
+
+        NFTContract {
+            owner_id: self.owner_id,
+            roles_by_account: TreeMap::new(
+                StorageKey::RolesByAccountKey.try_to_vec().unwrap(),
+            ),
+            paused_features: Vec::new(),
+            collections_per_owner: self.collections_per_owner,
+            tokens_per_owner: self.tokens_per_owner,
+            collections_by_name: cast_map(self.collections_by_name),
+            collections_by_id: cast_map(self.collections_by_id),
+            schemas_by_id: self.schemas_by_id,
+            schemas_per_collection: LookupMap::new(
+                StorageKey::SchemasPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            templates_by_id: cast_map(self.templates_by_id),
+            templates_per_collection: LookupMap::new(
+                StorageKey::TemplatesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            lootboxes_per_collection: LookupMap::new(
+                StorageKey::LootboxesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            lootbox_ids: TreeMap::new(StorageKey::LootboxIdsKey.try_to_vec().unwrap()),
+            token_id_counter: self.token_id_counter,
+            token_by_template_id_counter: self.token_by_template_id_counter,
+            tokens_by_id: self.tokens_by_id,
+            tokens_per_template: LookupMap::new(
+                StorageKey::TokensPerTemplateKey.try_to_vec().unwrap(),
+            ),
+            tokens_per_collection: LookupMap::new(
+                StorageKey::TokensPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            holders_per_collection: LookupMap::new(
+                StorageKey::HoldersPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            lootbox_nft_by_lootbox_id_counter: self.lootbox_nft_by_lootbox_id_counter,
+            drop_id_counter: self.drops_by_id.len() as u32, // V1: drop_id = drops_by_id.len()
+            drops_by_id: cast_map(self.drops_by_id),
+            drops_per_collection: LookupMap::new(
+                StorageKey::DropsPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            drop_payment_by_account: LookupMap::new(
+                StorageKey::DropPaymentByAccountKey.try_to_vec().unwrap(),
+            ),
+            claim_codes_per_drop: LookupMap::new(
+                StorageKey::ClaimCodesPerDropKey.try_to_vec().unwrap(),
+            ),
+            used_tokens_per_drop: LookupMap::new(
+                StorageKey::UsedTokensPerDropKey.try_to_vec().unwrap(),
+            ),
+            blend_id_counter: 0,
+            blends_by_id: UnorderedMap::new(StorageKey::BlendsByIdKey.try_to_vec().unwrap()),
+            blends_per_collection: LookupMap::new(
+                StorageKey::BlendsPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            upgrade_rule_id_counter: 0,
+            upgrade_rules_by_id: UnorderedMap::new(
+                StorageKey::UpgradeRulesByIdKey.try_to_vec().unwrap(),
+            ),
+            upgrade_rules_per_collection: LookupMap::new(
+                StorageKey::UpgradeRulesPerCollectionKey.try_to_vec().unwrap(),
+            ),
+            token_locks_by_id: LookupMap::new(StorageKey::TokenLocksByIdKey.try_to_vec().unwrap()),
+            staking_rate_per_template: LookupMap::new(
+                StorageKey::StakingRatePerTemplateKey.try_to_vec().unwrap(),
+            ),
+            staking_rate_per_rarity: LookupMap::new(
+                StorageKey::StakingRatePerRarityKey.try_to_vec().unwrap(),
+            ),
+            staking_points_by_account: LookupMap::new(
+                StorageKey::StakingPointsByAccountKey.try_to_vec().unwrap(),
+            ),
+            staking_points_consumers: LookupSet::new(
+                StorageKey::StakingPointsConsumersKey.try_to_vec().unwrap(),
+            ),
+            airdrop_job_id_counter: 0,
+            airdrop_jobs_by_id: LookupMap::new(
+                StorageKey::AirdropJobsByIdKey.try_to_vec().unwrap(),
+            ),
+            airdrop_recipients_per_job: LookupMap::new(
+                StorageKey::AirdropRecipientsPerJobKey.try_to_vec().unwrap(),
+            ),
+            operator_approval_id_counter: 0,
+            operators_per_owner: LookupMap::new(
+                StorageKey::OperatorsPerOwnerKey.try_to_vec().unwrap(),
+            ),
+            token_metadata_by_id: self.token_metadata_by_id,
+            used_voucher_nonces: LookupSet::new(
+                StorageKey::UsedVoucherNoncesKey.try_to_vec().unwrap(),
+            ),
+            search_index: LookupMap::new(StorageKey::SearchIndexKey.try_to_vec().unwrap()),
+            metadata: self.metadata,
+        }
+    }
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Deploy code mới của Contract rồi gọi migrate trên code mới
+    /**
+     * - Only the Contract's owner can upgrade
+     * - Input của hàm là wasm code (raw bytes, không phải JSON)
+     */
+    pub fn upgrade(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner of this contract can upgrade it"
+        );
+        let code = env::input().expect("Missing contract code");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                b"{}".to_vec(),
+                NO_DEPOSIT,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE,
+            );
+    }
+
+    // Đọc state theo layout cũ, chuyển sang layout hiện tại
+    // Chỉ Contract tự gọi được (qua upgrade), gọi lại khi state đã là layout hiện tại thì không thay đổi gì
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate() -> Self {
+        let contract = VersionedNFTContract::read().into_current();
+        write_state_version();
+        contract
+    }
+
+    // Migrate state V1 theo từng batch sau khi upgrade
+    /**
+     * - Only the Contract's owner can call
+     * - Mỗi lần gọi xử lý tối đa `limit` entries (default 100), theo thứ tự các MigrationStep
+     * - Gọi lại cho tới khi return step = done
+     * - Mint, transfer, burn, Drop Sales, Lootboxes bị chặn tới khi xong
+     */
+    pub fn migrate_state(&mut self, limit: Option<u64>) -> MigrationProgress {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner of this contract can migrate the state"
+        );
+        let mut progress = read_migration_progress().expect("The state has already been migrated");
+
+        let mut remaining = limit.unwrap_or(DEFAULT_MIGRATION_LIMIT);
+        assert!(remaining > 0, "Limit must be greater than 0");
+        while remaining > 0 && progress.step != MigrationStep::Done {
+            let end = progress.step_len().min(progress.next_index + remaining);
+            for index in progress.next_index..end {
+                self.internal_migrate_entry(progress.step, index);
+            }
+            remaining -= end - progress.next_index;
+            progress.next_index = end;
+
+            if progress.next_index == progress.step_len() {
+                progress.next_step();
+            }
+        }
+
+        if progress.step == MigrationStep::Done {
+            env::storage_remove(MIGRATION_PROGRESS_KEY);
+        } else {
+            write_migration_progress(&progress);
+        }
+        progress
+    }
+
+    // Tiến độ migrate state V1 (None: không có state nào đang được migrate)
+    pub fn get_migration_progress(&self) -> Option<MigrationProgress> {
+        read_migration_progress()
+    }
+
+    // Version của layout state hiện tại
+    pub fn get_state_version(&self) -> u32 {
+        read_state_version()
+    }
+}
+
+impl NFTContract {
+    // Migrate entry thứ `index` (trong state V1) của 1 step
+    fn internal_migrate_entry(&mut self, step: MigrationStep, index: u64) {
+        match step {
+            MigrationStep::Collections => {
+                migrate_map_value::<_, CollectionV1, Collection>(&self.collections_by_name, index);
+                let collection = migrate_map_value::<_, CollectionV1, Collection>(
+                    &self.collections_by_id,
+                    index,
+                );
+                self.internal_update_search_index(
+                    SearchKind::Collection,
+                    collection.collection_id,
+                    &[],
+                    &[
+                        collection.collection_name.as_str(),
+                        collection.data.name.as_str(),
+                    ],
+                );
+            }
+            MigrationStep::Schemas => {
+                let schema = self.schemas_by_id.values_as_vector().get(index).unwrap();
+                self.internal_add_schema_to_collection(&schema.collection_name, schema.schema_id);
+                self.internal_update_search_index(
+                    SearchKind::Schema,
+                    schema.schema_id,
+                    &[],
+                    &[schema.schema_name.as_str()],
+                );
+            }
+            MigrationStep::Templates => {
+                let template =
+                    migrate_map_value::<_, TemplateV1, Template>(&self.templates_by_id, index);
+                self.internal_add_template_to_collection(
+                    &template.collection_name,
+                    template.template_id,
+                    template.is_lootbox,
+                );
+                self.internal_update_search_index(
+                    SearchKind::Template,
+                    template.template_id,
+                    &[],
+                    &[template.immutable_data.name.as_str()],
+                );
+            }
+            MigrationStep::Drops => {
+                let drop = migrate_map_value::<_, DropSaleV1, DropSale>(&self.drops_by_id, index);
+                self.internal_add_drop_to_collection(&drop.collection_name, drop.drop_id);
+            }
+            MigrationStep::Tokens => {
+                let token = self.tokens_by_id.values_as_vector().get(index).unwrap();
+                self.internal_add_token_to_indexes(&token);
+
+                // V1 media_hash was the bytes of the media URL, not a hash
+                // Chỉ issued_at của V1 là nanoseconds, starts_at (unlock_time của Lootbox) đã là milliseconds
+                let mut metadata = self.token_metadata_by_id.get(&token.token_id).unwrap();
+                metadata.media_hash = None;
+                metadata.issued_at = nanos_to_millis(metadata.issued_at);
+                self.token_metadata_by_id.insert(&token.token_id, &metadata);
+            }
+            MigrationStep::Done => {}
+        }
+    }
+}
@@ -0,0 +1,77 @@
+// Moderation của platform: freeze, hide và verify Collections
+use crate::*;
+
+#[near_bindgen]
+impl NFTContract {
+    // Freeze / unfreeze 1 Collection (owner, admin hoặc moderator)
+    /**
+     * - Frozen Collection: không được mint, tạo / claim Drop Sales, tạo / mint / open Lootboxes
+     * - NFTs đã mint vẫn được transfer bình thường
+     */
+    #[payable]
+    pub fn set_collection_frozen(
+        &mut self,
+        collection_name: CollectionName,
+        frozen: bool,
+        reason: Option<String>,
+    ) {
+        assert_one_yocto();
+        self.assert_has_role(&[Role::Admin, Role::Moderator]);
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        collection.moderation.frozen = frozen;
+        collection.moderation.frozen_reason = if frozen { reason.clone() } else { None };
+
+        let action = if frozen { "freeze" } else { "unfreeze" };
+        self.internal_save_moderated_collection(&collection, action, reason);
+    }
+
+    // Hide / unhide 1 Collection khỏi các listing views (owner, admin hoặc moderator)
+    #[payable]
+    pub fn set_collection_hidden(
+        &mut self,
+        collection_name: CollectionName,
+        hidden: bool,
+        reason: Option<String>,
+    ) {
+        assert_one_yocto();
+        self.assert_has_role(&[Role::Admin, Role::Moderator]);
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        collection.moderation.hidden = hidden;
+        collection.moderation.hidden_reason = if hidden { reason.clone() } else { None };
+
+        let action = if hidden { "hide" } else { "unhide" };
+        self.internal_save_moderated_collection(&collection, action, reason);
+    }
+
+    // Gắn / gỡ badge verified của 1 Collection (owner, admin hoặc moderator)
+    #[payable]
+    pub fn set_collection_verified(
+        &mut self,
+        collection_name: CollectionName,
+        verified: bool,
+        reason: Option<String>,
+    ) {
+        assert_one_yocto();
+        self.assert_has_role(&[Role::Admin, Role::Moderator]);
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        collection.moderation.verified = verified;
+
+        let action = if verified { "verify" } else { "unverify" };
+        self.internal_save_moderated_collection(&collection, action, reason);
+    }
+}
@@ -45,14 +45,19 @@ impl NFTContract {
         refund_deposit(after_storage_usage - before_storage_usage);
     }
 
-    // Lấy thông tin 1 token dưới dạng JsonToken
-    pub fn nft_token(&self, token_id: TokenId) -> Option<JsonToken> {
+    // Lấy thông tin 1 token theo chuẩn NEP-171
+    pub fn nft_token(&self, token_id: JsonTokenId) -> Option<JsonToken> {
+        self.internal_json_token(parse_token_id(&token_id))
+    }
+
+    // Lấy thông tin 1 token kèm các thông tin của NearHub (Collection, Schema, Template)
+    pub fn nft_token_detail(&self, token_id: TokenId) -> Option<JsonTokenDetail> {
         let token = self.tokens_by_id.get(&token_id);
 
         if let Some(token) = token {
             let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
 
-            Some(JsonToken {
+            Some(JsonTokenDetail {
                 owner_id: token.owner_id,
                 token_id,
                 token_by_template_id: token.token_by_template_id,
@@ -69,12 +74,22 @@ impl NFTContract {
         }
     }
 
+    // Check if a token is inside its validity period (starts_at <= now < expires_at)
+    pub fn nft_is_valid(&self, token_id: TokenId) -> bool {
+        let metadata = self
+            .token_metadata_by_id
+            .get(&token_id)
+            .expect("Token does not exist");
+
+        is_token_valid(&metadata, current_time_ms())
+    }
+
     // -------------------------------- Enumerations --------------------------------
 
     // Lấy tổng số token đang có trong contract
     pub fn nft_total_supply(&self) -> U128 {
-        // Đếm tổng số lượng id đang có trong token_metadata_by_id
-        U128(self.token_metadata_by_id.len() as u128)
+        // Đếm tổng số lượng id đang có trong tokens_by_id
+        U128(self.tokens_by_id.len() as u128)
     }
 
     // Lấy tổng số token đang có của account nào đó
@@ -88,22 +103,36 @@ impl NFTContract {
         }
     }
 
-    // Lấy danh sách token (có pagination)
+    // Lấy danh sách token theo chuẩn NEP-181 (có pagination)
     pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
-        let collection_keys = self.token_metadata_by_id.keys_as_vector();
-
         let start = u128::from(from_index.unwrap_or(U128(0)));
 
         // Duyệt tất cả các keys -> Trả về JsonToken
-        collection_keys
-            .iter()
+        self.tokens_by_id
+            .keys()
             .skip(start as usize) // Pagination
-            .take(limit.unwrap_or(10) as usize) // Pagination
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+            .take(page_limit(limit)) // Pagination
+            .map(|token_id| self.internal_json_token(token_id).unwrap())
             .collect()
     }
 
-    // Lấy danh sách token của account nào đó (có pagination)
+    // Lấy danh sách token kèm thông tin của NearHub (cursor: token id)
+    pub fn nft_tokens_detail(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<JsonTokenDetail> {
+        // Token id tăng dần từ DEFAULT_TOKEN_ID, bỏ qua các tokens đã bị burn
+        paginate_ids(
+            DEFAULT_TOKEN_ID,
+            DEFAULT_TOKEN_ID + self.token_id_counter,
+            cursor,
+            limit,
+            |token_id| self.nft_token_detail(*token_id),
+        )
+    }
+
+    // Lấy danh sách token của account nào đó theo chuẩn NEP-181 (có pagination)
     pub fn nft_tokens_for_owner(
         &self,
         account_id: AccountId,
@@ -124,8 +153,79 @@ impl NFTContract {
         keys.as_vector()
             .iter()
             .skip(start as usize) // Pagination
-            .take(limit.unwrap_or(10) as usize) // Pagination
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+            .take(page_limit(limit)) // Pagination
+            .map(|token_id| self.internal_json_token(token_id).unwrap())
             .collect()
     }
+
+    // Lấy danh sách token của account nào đó kèm thông tin của NearHub (cursor: token id)
+    pub fn nft_tokens_detail_for_owner(
+        &self,
+        account_id: AccountId,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<JsonTokenDetail> {
+        let keys = if let Some(token_keys) = self.tokens_per_owner.get(&account_id) {
+            token_keys
+        } else {
+            return Page::empty();
+        };
+
+        // Duyệt các keys sau cursor -> Trả về JsonTokenDetail
+        paginate_keys(keys.iter(), cursor, limit, |token_id| {
+            self.nft_token_detail(*token_id)
+        })
+    }
+
+    // Lấy tổng số NFTs đang có của Template nào đó
+    pub fn nft_supply_for_template(&self, template_id: TemplateId) -> U128 {
+        let tokens_set = self.tokens_per_template.get(&template_id);
+
+        if let Some(tokens_set) = tokens_set {
+            U128(tokens_set.len() as u128)
+        } else {
+            U128(0)
+        }
+    }
+
+    // Lấy danh sách token của Template nào đó (cursor: token id)
+    pub fn nft_tokens_for_template(
+        &self,
+        template_id: TemplateId,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken> {
+        let keys = if let Some(token_keys) = self.tokens_per_template.get(&template_id) {
+            token_keys
+        } else {
+            return Page::empty();
+        };
+
+        // Duyệt các keys sau cursor -> Trả về JsonToken
+        paginate_tree_map(&keys, cursor, limit, |token_id| {
+            self.internal_json_token(*token_id)
+        })
+    }
+
+    // Lấy tổng số NFTs đang có của Collection nào đó
+    pub fn nft_supply_for_collection(&self, collection_name: CollectionName) -> U128 {
+        U128(collection_index_len(&self.tokens_per_collection, &collection_name) as u128)
+    }
+
+    // Lấy danh sách token của Collection nào đó (cursor: token id)
+    pub fn nft_tokens_for_collection(
+        &self,
+        collection_name: CollectionName,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken> {
+        // Duyệt các token ids trong index của Collection -> Trả về JsonToken
+        paginate_collection_index(
+            &self.tokens_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |token_id| self.internal_json_token(*token_id),
+        )
+    }
 }
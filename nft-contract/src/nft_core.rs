@@ -10,8 +10,8 @@ pub trait NonFungibleTokenCore {
     fn nft_transfer(
         &mut self,
         receiver_id: AccountId,
-        token_id: TokenId,
-        approval_id: u64,
+        token_id: JsonTokenId,
+        approval_id: Option<u64>,
         memo: Option<String>,
     );
 
@@ -19,9 +19,9 @@ pub trait NonFungibleTokenCore {
     fn nft_transfer_call(
         &mut self,
         receiver_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
+        approval_id: Option<u64>,
         memo: Option<String>,
-        approval_id: u64,
         msg: String,
     ) -> PromiseOrValue<bool>;
 }
@@ -35,7 +35,7 @@ trait NonFungibleTokenReceiver {
         &mut self,
         sender_id: AccountId,
         previous_owner_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         msg: String,
     ) -> Promise;
 }
@@ -49,19 +49,20 @@ trait NonFungibleTokenResolver {
         authorized_id: Option<AccountId>,
         owner_id: AccountId,
         receiver_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         approved_account_ids: HashMap<AccountId, u64>,
         memo: Option<String>,
     ) -> bool;
 }
 
-trait NonFungibleTokenResolver {
+// Callback của nft_transfer_call (NEP-171), chỉ Contract này được gọi
+pub trait NonFungibleTokenResolver {
     fn nft_resolve_transfer(
         &mut self,
         authorized_id: Option<AccountId>,
         owner_id: AccountId,
         receiver_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         approved_account_ids: HashMap<AccountId, u64>,
         memo: Option<String>,
     ) -> bool;
@@ -75,15 +76,20 @@ impl NonFungibleTokenCore for NFTContract {
     fn nft_transfer(
         &mut self,
         receiver_id: AccountId,
-        token_id: TokenId,
-        approval_id: u64,
+        token_id: JsonTokenId,
+        approval_id: Option<u64>,
         memo: Option<String>,
     ) {
         assert_one_yocto();
         let sender_id = env::predecessor_account_id();
 
-        let previous_token =
-            self.internal_transfer(&sender_id, &receiver_id, &token_id, Some(approval_id), memo);
+        let previous_token = self.internal_transfer(
+            &sender_id,
+            &receiver_id,
+            &parse_token_id(&token_id),
+            approval_id,
+            memo,
+        );
 
         // Refund nếu deposit thừa
         refund_approved_account_ids(sender_id, &previous_token.approved_account_ids);
@@ -93,9 +99,9 @@ impl NonFungibleTokenCore for NFTContract {
     fn nft_transfer_call(
         &mut self,
         receiver_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
+        approval_id: Option<u64>,
         memo: Option<String>,
-        approval_id: u64,
         msg: String,
     ) -> PromiseOrValue<bool> {
         assert_one_yocto();
@@ -104,8 +110,8 @@ impl NonFungibleTokenCore for NFTContract {
         let previous_token = self.internal_transfer(
             &sender_id,
             &receiver_id,
-            &token_id,
-            Some(approval_id),
+            &parse_token_id(&token_id),
+            approval_id,
             memo.clone(),
         );
 
@@ -145,22 +151,32 @@ impl NonFungibleTokenCore for NFTContract {
 #[near_bindgen]
 impl NonFungibleTokenResolver for NFTContract {
     // Xử lý call back của nft_on_transfer khi contract nhận gọi lại
+    // Return true nếu receiver giữ NFT, false nếu NFT được trả lại cho owner cũ (NEP-171)
+    #[private]
     fn nft_resolve_transfer(
         &mut self,
         authorized_id: Option<AccountId>,
         owner_id: AccountId,
         receiver_id: AccountId,
-        token_id: TokenId,
+        token_id: JsonTokenId,
         approved_account_ids: HashMap<AccountId, u64>,
         memo: Option<String>,
     ) -> bool {
-        if let PromiseResult::Successful(value) = env::promise_result(0) {
-            // Thành công, chỉ có 1 promise
-            if let Ok(is_rollback_token) = near_sdk::serde_json::from_slice::<bool>(&value) {
-                return is_rollback_token;
+        // nft_on_transfer return true hoặc bị lỗi (không return bool) -> Phải trả NFT lại cho owner cũ
+        let must_revert = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(true)
             }
+            _ => true,
+        };
+        if !must_revert {
+            // Receiver giữ NFT -> Refund storage của approvals cũ cho owner cũ
+            refund_approved_account_ids(owner_id, &approved_account_ids);
+            return true;
         }
 
+        let token_id = parse_token_id(&token_id);
+
         // Xử lý các case không thể rollback lại được
         let mut token = if let Some(token) = self.tokens_by_id.get(&token_id) {
             // Nếu người nhận ko phải là owner -> Không thực hiện được -> rollback
@@ -185,6 +201,7 @@ impl NonFungibleTokenResolver for NFTContract {
 
         self.internal_remove_token_from_owner(&token_id, &receiver_id); // Xoá token của người vừa nhận
         self.internal_add_token_to_owner(&token_id, &owner_id); // Trả lại token cho owner cũ
+        self.internal_move_collection_holder(&token, &receiver_id, &owner_id);
 
         // Lấy lại các giá trị của token
         token.owner_id = owner_id.clone();
@@ -215,3 +232,85 @@ impl NonFungibleTokenResolver for NFTContract {
         false // Cho front-end biết là giao dịch thất bại -> Rollback toàn bộ data
     }
 }
+
+#[near_bindgen]
+impl NFTContract {
+    // Transfer nhiều token trong 1 lần gọi, emit 1 nft_transfer event gộp theo (old_owner_id, new_owner_id)
+    /**
+     * - Mỗi token được check owner và approval giống nft_transfer
+     * - All-or-nothing: 1 token không hợp lệ -> panic -> không token nào được transfer
+     * - Refund storage của approvals của từng token cho owner của token
+     */
+    #[payable]
+    pub fn nft_batch_transfer(
+        &mut self,
+        transfers: Vec<(JsonTokenId, AccountId)>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+
+        assert!(
+            !transfers.is_empty(),
+            "Batch transfer must have at least 1 token"
+        );
+        let transfers: Vec<(TokenId, AccountId)> = transfers
+            .into_iter()
+            .map(|(token_id, receiver_id)| (parse_token_id(&token_id), receiver_id))
+            .collect();
+        let unique_token_ids: HashSet<TokenId> =
+            transfers.iter().map(|(token_id, _)| *token_id).collect();
+        assert_eq!(
+            unique_token_ids.len(),
+            transfers.len(),
+            "Duplicate tokens in batch transfer"
+        );
+
+        let mut transfer_logs = Vec::<NftTransferLog>::new();
+        for (token_id, receiver_id) in transfers.iter() {
+            let previous_token =
+                self.internal_transfer_without_log(&sender_id, receiver_id, token_id, None);
+
+            // Refund storage của approvals của token cho owner cũ (người đã trả storage khi approve)
+            if !previous_token.approved_account_ids.is_empty() {
+                refund_approved_account_ids(
+                    previous_token.owner_id.clone(),
+                    &previous_token.approved_account_ids,
+                );
+            }
+
+            // Gộp các token có cùng old_owner_id và new_owner_id vào 1 NftTransferLog
+            let existing_log = transfer_logs.iter_mut().find(|log| {
+                log.old_owner_id == previous_token.owner_id && &log.new_owner_id == receiver_id
+            });
+            match existing_log {
+                Some(log) => log.token_ids.push(token_id.to_string()),
+                None => {
+                    // Sender không phải owner -> Sender là approved account
+                    let authorized_id = if sender_id != previous_token.owner_id {
+                        Some(sender_id.to_string())
+                    } else {
+                        None
+                    };
+                    transfer_logs.push(NftTransferLog {
+                        authorized_id,
+                        old_owner_id: previous_token.owner_id.to_string(),
+                        new_owner_id: receiver_id.to_string(),
+                        token_ids: vec![token_id.to_string()],
+                        memo: memo.clone(),
+                    });
+                }
+            }
+        }
+
+        // -------------------------------------------------------------------
+        // NFT TRANSFER LOG
+        let nft_transfer_log: EventLog = EventLog {
+            standard: "nep171".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftTransfer(transfer_logs),
+        };
+        env::log(nft_transfer_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+    }
+}
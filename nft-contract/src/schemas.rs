@@ -6,7 +6,8 @@ impl NFTContract {
     // Tạo 1 Schema mới thuộc 1 Collection nào đó
     /**
      * - Yêu cầu user nạp tiền để cover phí lưu trữ
-     * - Thêm Schema vào schemas_by_id
+     * - Thêm Schema vào schemas_by_id + schemas_per_collection
+     * - Thêm tên của Schema vào search_index
      * - Refund lại NEAR user deposit thừa
      */
     #[payable]
@@ -46,6 +47,31 @@ impl NFTContract {
 
         // Insert schema mới vào schemas_by_id
         self.schemas_by_id.insert(&schema_id, &new_schema);
+        // Insert schema mới vào index của Collection
+        self.internal_add_schema_to_collection(&new_schema.collection_name, schema_id);
+        // Index tên của Schema để search
+        self.internal_update_search_index(
+            SearchKind::Schema,
+            schema_id,
+            &[],
+            &[&new_schema.schema_name],
+        );
+
+        // -------------------------------------------------------------------
+        // SCHEMA CREATE LOG
+        let schema_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::SchemaCreate(vec![SchemaCreateLog {
+                owner_id: collection_of_schema.owner_id.to_string(),
+                collection_name: new_schema.collection_name.clone(),
+                schema_id: schema_id.to_string(),
+                schema_name: new_schema.schema_name.clone(),
+                memo: None,
+            }]),
+        };
+        env::log(schema_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
 
         // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
         let after_storage_usage = env::storage_usage();
@@ -69,97 +95,53 @@ impl NFTContract {
             "Collection does not exist"
         );
 
-        let mut count = 0;
-
-        let schemas_set_by_collection: Vec<Schema> = self
-            .schemas_by_id
-            .keys()
-            .map(|schema_id| self.schemas_by_id.get(&schema_id).unwrap())
-            .collect();
-
-        for schema in schemas_set_by_collection {
-            if schema.collection_name == collection_name {
-                count += 1;
-            }
-        }
-
-        U128(count)
+        U128(collection_index_len(&self.schemas_per_collection, &collection_name) as u128)
     }
 
-    // Lấy danh sách tất cả Schemas trong Contract
-    pub fn get_all_schemas(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Schema> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        // Duyệt tất cả các keys -> Trả về Collection
-        // self.collections_by_id.values_as_vector().to_vec()
-        self.schemas_by_id
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(10) as usize)
-            .map(|(schema_id, _schema)| self.schemas_by_id.get(&schema_id).unwrap())
-            .collect()
+    // Lấy danh sách tất cả Schemas trong Contract (cursor: schema id)
+    pub fn get_all_schemas(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Schema> {
+        // Schema id = thứ tự tạo
+        let schema_count = self.schemas_by_id.len() as u32;
+        paginate_ids(0, schema_count, cursor, limit, |schema_id| {
+            self.schemas_by_id.get(schema_id)
+        })
     }
 
-    // Lấy danh sách Schema của Collection nào đó (có pagination)
+    // Lấy danh sách Schema của Collection nào đó (cursor: schema id)
     pub fn get_all_schemas_by_collection(
         &self,
         collection_name: CollectionName,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Schema> {
-        let mut count = 0;
-
+    ) -> Page<Schema> {
         // Check collection id có tồn tại không
         assert!(
             self.collections_by_name.get(&collection_name).is_some(),
             "Collection does not exist"
         );
 
-        let mut result = Vec::<Schema>::new();
-
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        // Duyệt tất cả các keys -> Trả về Collection
-        let schemas_set_for_owner: Vec<Schema> = self
-            .schemas_by_id
-            .keys()
-            .skip(start as usize) // Pagination
-            // .take(limit.unwrap_or(10) as usize) // Pagination
-            .map(|schema_id| self.schemas_by_id.get(&schema_id).unwrap())
-            .collect();
-
-        // If limit = 0 -> Return empty Array
-        if limit.is_some() && limit.unwrap() == 0 {
-            return result;
-        }
-
-        for schema in schemas_set_for_owner {
-            if schema.collection_name == collection_name {
-                result.push(schema);
-                count += 1;
-            }
-            if count == limit.unwrap_or(10) {
-                break;
-            }
-        }
-        result
+        // Duyệt các schema ids trong index của Collection -> Trả về Schema
+        paginate_collection_index(
+            &self.schemas_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |schema_id| self.schemas_by_id.get(schema_id),
+        )
     }
 
-    // Search Schema theo name
-    // Lấy về tất cả Schema mà tên có chứa ký tự của `search_string`
-    pub fn get_schemas_by_name(&self, search_string: String) -> Vec<Schema> {
-        let schemas_set: Vec<Schema> = self.schemas_by_id.values().collect();
-
-        let mut result = Vec::<Schema>::new();
-
-        for schema in schemas_set {
-            if schema
-                .schema_name
-                .to_lowercase()
-                .contains(&search_string.to_lowercase())
-            {
-                result.push(schema);
-            }
-        }
-        result
+    // Search Schema theo name (cursor pagination như `search`)
+    // Lấy về các Schema mà mỗi từ của `search_string` là prefix của 1 từ trong tên
+    pub fn get_schemas_by_name(
+        &self,
+        search_string: String,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<Schema> {
+        self.search(search_string, Some(vec![SearchKind::Schema]), cursor, limit)
+            .filter_map(|result| match result {
+                SearchResult::Schema(schema) => Some(schema),
+                _ => None,
+            })
     }
 }
@@ -0,0 +1,257 @@
+// Search Collections, Schemas và Templates theo tên (index theo prefix của từng từ trong tên)
+use crate::*;
+use std::fmt;
+use std::ops::Bound;
+use std::str::FromStr;
+
+// Chỉ index prefix tối đa MAX_SEARCH_PREFIX_LEN ký tự, từ dài hơn được so khớp lại khi search
+const MAX_SEARCH_PREFIX_LEN: usize = 10;
+
+// ----------------------------------- Search Structs -----------------------------------
+#[derive(
+    Serialize,
+    Deserialize,
+    BorshDeserialize,
+    BorshSerialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Collection,
+    Schema,
+    Template, // Gồm cả Lootboxes
+}
+
+// 1 phần tử của search_index
+// Cursor của `search` là entry cuối cùng của trang, dạng "<kind>:<id>" (vd: "template:3")
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SearchEntry {
+    pub kind: SearchKind,
+    pub id: u32, // collection_id, schema_id hoặc template_id
+}
+
+impl fmt::Display for SearchEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            SearchKind::Collection => "collection",
+            SearchKind::Schema => "schema",
+            SearchKind::Template => "template",
+        };
+        write!(f, "{}:{}", kind, self.id)
+    }
+}
+
+impl FromStr for SearchEntry {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s.split_once(':').ok_or(())?;
+        let kind = match kind {
+            "collection" => SearchKind::Collection,
+            "schema" => SearchKind::Schema,
+            "template" => SearchKind::Template,
+            _ => return Err(()),
+        };
+        let id = id.parse().map_err(|_| ())?;
+        Ok(SearchEntry { kind, id })
+    }
+}
+
+// Kết quả search, có kiểu theo từng loại
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum SearchResult {
+    Collection(Collection),
+    Schema(Schema),
+    Template(Template),
+}
+
+// Chuẩn hoá tên: lowercase, tách thành các từ theo ký tự không phải chữ / số
+pub(crate) fn normalize_search_words(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect()
+}
+
+// Tất cả prefixes (tối đa MAX_SEARCH_PREFIX_LEN ký tự) của các từ trong các tên
+pub(crate) fn search_prefixes(names: &[&str]) -> HashSet<String> {
+    let mut prefixes = HashSet::new();
+    for name in names.iter() {
+        for word in normalize_search_words(name) {
+            let chars: Vec<char> = word.chars().take(MAX_SEARCH_PREFIX_LEN).collect();
+            for len in 1..=chars.len() {
+                prefixes.insert(chars[..len].iter().collect());
+            }
+        }
+    }
+    prefixes
+}
+
+// Mỗi từ của query phải là prefix của 1 từ trong các tên
+fn is_search_match(query_words: &[String], names: &[&str]) -> bool {
+    let words: Vec<String> = names
+        .iter()
+        .flat_map(|name| normalize_search_words(name))
+        .collect();
+    query_words.iter().all(|query_word| {
+        words
+            .iter()
+            .any(|word| word.starts_with(query_word.as_str()))
+    })
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Search Collections, Schemas và Templates mà mỗi từ của `query` là prefix của 1 từ trong tên
+    /**
+     * - kinds: chỉ search các loại này (None -> tất cả)
+     * - Không trả về Collections bị hidden, và Schemas / Templates của chúng
+     * - Cursor pagination trên các kết quả đã lọc, sort theo (kind, id)
+     */
+    pub fn search(
+        &self,
+        query: String,
+        kinds: Option<Vec<SearchKind>>,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<SearchResult> {
+        let query_words = normalize_search_words(&query);
+        let first_prefix: String = if let Some(first_word) = query_words.first() {
+            first_word.chars().take(MAX_SEARCH_PREFIX_LEN).collect()
+        } else {
+            return Page::empty();
+        };
+
+        let entries = if let Some(entries) = self.search_index.get(&first_prefix) {
+            entries
+        } else {
+            return Page::empty();
+        };
+
+        // Entries được sort theo (kind, id) -> Chỉ đọc các entries của từng kind, sau cursor
+        let mut kinds = kinds.unwrap_or_else(|| {
+            vec![
+                SearchKind::Collection,
+                SearchKind::Schema,
+                SearchKind::Template,
+            ]
+        });
+        kinds.sort();
+        kinds.dedup();
+        let cursor: Option<SearchEntry> = cursor.map(|cursor| parse_cursor(&cursor));
+        let sorted_entries = kinds.into_iter().flat_map(|kind| {
+            let first = SearchEntry { kind, id: 0 };
+            let last = SearchEntry { kind, id: u32::MAX };
+            let start = match cursor {
+                Some(cursor) if cursor >= last => return None,
+                Some(cursor) if cursor >= first => Bound::Excluded(cursor),
+                _ => Bound::Included(first),
+            };
+            Some(entries.range((start, Bound::Included(last))))
+        });
+        page_from_sorted_keys(
+            sorted_entries.flatten().map(|(entry, _)| entry),
+            limit,
+            |entry| self.internal_search_result(entry, &query_words),
+        )
+    }
+}
+
+impl NFTContract {
+    // Tên được index của 1 entry
+    fn internal_search_names(result: &SearchResult) -> Vec<&str> {
+        match result {
+            SearchResult::Collection(collection) => {
+                vec![&collection.collection_name, &collection.data.name]
+            }
+            SearchResult::Schema(schema) => vec![&schema.schema_name],
+            SearchResult::Template(template) => vec![&template.immutable_data.name],
+        }
+    }
+
+    // Lấy entity của entry -> None nếu không khớp query hoặc Collection bị hidden
+    fn internal_search_result(
+        &self,
+        entry: &SearchEntry,
+        query_words: &[String],
+    ) -> Option<SearchResult> {
+        let (result, collection_name) = match entry.kind {
+            SearchKind::Collection => {
+                let collection = self.collections_by_id.get(&entry.id)?;
+                let collection_name = collection.collection_name.clone();
+                (SearchResult::Collection(collection), collection_name)
+            }
+            SearchKind::Schema => {
+                let schema = self.schemas_by_id.get(&entry.id)?;
+                let collection_name = schema.collection_name.clone();
+                (SearchResult::Schema(schema), collection_name)
+            }
+            SearchKind::Template => {
+                let template = self.templates_by_id.get(&entry.id)?;
+                let collection_name = template.collection_name.clone();
+                (SearchResult::Template(template), collection_name)
+            }
+        };
+
+        if !is_search_match(query_words, &Self::internal_search_names(&result)) {
+            return None;
+        }
+        let collection = self.collections_by_name.get(&collection_name)?;
+        if collection.moderation.hidden {
+            return None;
+        }
+        Some(result)
+    }
+
+    // Cập nhật search_index khi tên của 1 entity thay đổi (tạo mới: old_names rỗng)
+    /**
+     * - Xoá entry khỏi các prefixes chỉ có trong tên cũ
+     * - Thêm entry vào các prefixes chỉ có trong tên mới
+     */
+    pub(crate) fn internal_update_search_index(
+        &mut self,
+        kind: SearchKind,
+        id: u32,
+        old_names: &[&str],
+        new_names: &[&str],
+    ) {
+        let old_prefixes = search_prefixes(old_names);
+        let new_prefixes = search_prefixes(new_names);
+        let entry = SearchEntry { kind, id };
+
+        for prefix in old_prefixes.difference(&new_prefixes) {
+            if let Some(mut entries) = self.search_index.get(prefix) {
+                entries.remove(&entry);
+                if entries.len() == 0 {
+                    self.search_index.remove(prefix);
+                } else {
+                    self.search_index.insert(prefix, &entries);
+                }
+            }
+        }
+
+        for prefix in new_prefixes.difference(&old_prefixes) {
+            let mut entries = self.search_index.get(prefix).unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::SearchIndexInnerKey {
+                        prefix_hash: hash_account_id(prefix),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+            entries.insert(&entry, &());
+            self.search_index.insert(prefix, &entries);
+        }
+    }
+}
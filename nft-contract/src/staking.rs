@@ -0,0 +1,320 @@
+use crate::*;
+
+// ----------------------------------- Staking Struct -----------------------------------
+// 1 NFT đang bị lock (stake): không thể transfer, approve hay burn cho tới khi được unlock
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenLock {
+    pub owner_id: AccountId,             // Owner của NFT lúc lock
+    pub collection_name: CollectionName, // Collection mà NFT thuộc vào (points được tính trong Collection này)
+    pub locked_at: Timestamp,            // Thời điểm lock NFT (milliseconds)
+    pub unlock_at: Timestamp, // Thời điểm được unlock NFT, sau thời điểm này không cộng thêm points (milliseconds)
+    pub points_per_second: U128, // Rate của Template của NFT lúc lock
+    pub claimed_until: Timestamp, // Points trước thời điểm này đã được cộng vào số dư của owner (milliseconds)
+}
+
+// Dạng Json của TokenLock kèm số points chưa được claim
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTokenLock {
+    pub token_id: TokenId,
+    pub lock: TokenLock,
+    pub accrued_points: U128, // Số points tích luỹ từ claimed_until
+}
+
+// Đối tượng của 1 staking rate: 1 Template hoặc tất cả Templates có cùng 1 rarity
+// Ưu tiên rate của Template, nếu không có thì dùng rate của rarity
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StakingRateTarget {
+    Template { template_id: TemplateId },
+    Rarity { rarity: String },
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Set số points mỗi giây mà các NFT đang lock của 1 Template hoặc 1 rarity nhận được
+    // points_per_second = 0 -> Xoá rate
+    #[payable]
+    pub fn set_staking_rate(
+        &mut self,
+        collection_name: CollectionName,
+        target: StakingRateTarget,
+        points_per_second: U128,
+    ) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+        assert_eq!(
+            collection.owner_id,
+            env::predecessor_account_id(),
+            "Only owner of this collection can set staking rates"
+        );
+
+        let points_per_second = u128::from(points_per_second);
+        match target {
+            StakingRateTarget::Template { template_id } => {
+                let template = self
+                    .templates_by_id
+                    .get(&template_id)
+                    .expect("Template does not exist");
+                assert_eq!(
+                    template.collection_name, collection_name,
+                    "Template does not belong to this collection"
+                );
+                if points_per_second == 0 {
+                    self.staking_rate_per_template.remove(&template_id);
+                } else {
+                    self.staking_rate_per_template
+                        .insert(&template_id, &points_per_second);
+                }
+            }
+            StakingRateTarget::Rarity { rarity } => {
+                let collection_and_rarity = format!("{}{}{}", collection_name, ".", rarity);
+                if points_per_second == 0 {
+                    self.staking_rate_per_rarity.remove(&collection_and_rarity);
+                } else {
+                    self.staking_rate_per_rarity
+                        .insert(&collection_and_rarity, &points_per_second);
+                }
+            }
+        }
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+
+    // Lock các NFT của caller trong `duration` milliseconds
+    /**
+     * - Chỉ owner mới được lock NFT, NFT chưa bị lock
+     * - Rate của từng NFT được chốt tại thời điểm lock
+     * - Caller trả phí lưu trữ của các lock
+     */
+    #[payable]
+    pub fn nft_lock(&mut self, token_ids: Vec<TokenId>, duration: u64) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        assert!(duration > 0, "Lock duration must be greater than 0");
+
+        let owner_id = env::predecessor_account_id();
+        let now = current_time_ms();
+
+        for token_id in token_ids.iter() {
+            let token = self
+                .tokens_by_id
+                .get(token_id)
+                .expect("Token does not exist");
+            assert_eq!(
+                token.owner_id, owner_id,
+                "Only owner of this token can lock it"
+            );
+            assert!(
+                self.token_locks_by_id.get(token_id).is_none(),
+                "Token {} is already locked",
+                token_id
+            );
+
+            let lock = TokenLock {
+                owner_id: owner_id.clone(),
+                collection_name: token.collection_name.clone(),
+                locked_at: now,
+                unlock_at: now + duration,
+                points_per_second: U128(self.internal_staking_rate(&token)),
+                claimed_until: now,
+            };
+            self.token_locks_by_id.insert(token_id, &lock);
+        }
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage - before_storage_usage);
+    }
+
+    // Unlock các NFT của caller sau khi hết thời gian lock
+    // Points tích luỹ được cộng vào số dư của caller, refund phí lưu trữ của các lock
+    #[payable]
+    pub fn nft_unlock(&mut self, token_ids: Vec<TokenId>) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let owner_id = env::predecessor_account_id();
+        let now = current_time_ms();
+
+        for token_id in token_ids.iter() {
+            let lock = self
+                .token_locks_by_id
+                .get(token_id)
+                .expect("Token is not locked");
+            assert_eq!(
+                lock.owner_id, owner_id,
+                "Only owner of this token can unlock it"
+            );
+            assert!(now >= lock.unlock_at, "Token {} is still locked", token_id);
+
+            self.internal_claim_staking_points(token_id, lock);
+            self.token_locks_by_id.remove(token_id);
+        }
+
+        let after_storage_usage = env::storage_usage();
+        if after_storage_usage >= before_storage_usage {
+            refund_deposit(after_storage_usage - before_storage_usage);
+        } else {
+            // Refund storage được giải phóng bởi các lock + deposit đã gửi kèm
+            let storage_released = before_storage_usage - after_storage_usage;
+            Promise::new(owner_id).transfer(
+                Balance::from(storage_released) * env::storage_byte_cost()
+                    + env::attached_deposit(),
+            );
+        }
+    }
+
+    // Cộng points tích luỹ của các NFT đang lock vào số dư của caller, không unlock NFT
+    #[payable]
+    pub fn claim_staking_points(&mut self, token_ids: Vec<TokenId>) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let owner_id = env::predecessor_account_id();
+        for token_id in token_ids.iter() {
+            let lock = self
+                .token_locks_by_id
+                .get(token_id)
+                .expect("Token is not locked");
+            assert_eq!(
+                lock.owner_id, owner_id,
+                "Only owner of this token can claim its points"
+            );
+            self.internal_claim_staking_points(token_id, lock);
+        }
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+
+    // Cho phép (hoặc bỏ quyền) 1 account dùng points của 1 Collection (VD: game server)
+    #[payable]
+    pub fn set_staking_points_consumer(
+        &mut self,
+        collection_name: CollectionName,
+        account_id: AccountId,
+        is_authorized: bool,
+    ) {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+        assert_eq!(
+            collection.owner_id,
+            env::predecessor_account_id(),
+            "Only owner of this collection can set points consumers"
+        );
+
+        let collection_and_account_id = format!("{}{}{}", collection_name, ".", account_id);
+        if is_authorized {
+            self.staking_points_consumers
+                .insert(&collection_and_account_id);
+        } else {
+            self.staking_points_consumers
+                .remove(&collection_and_account_id);
+        }
+
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+
+    // Trừ points của 1 account (gọi bởi owner của Collection hoặc account được cho phép)
+    // Return số points còn lại của account
+    pub fn consume_staking_points(
+        &mut self,
+        collection_name: CollectionName,
+        account_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+        let consumer_id = env::predecessor_account_id();
+        assert!(
+            consumer_id == collection.owner_id
+                || self
+                    .staking_points_consumers
+                    .contains(&format!("{}{}{}", collection_name, ".", consumer_id)),
+            "Only owner of this collection or an authorized account can consume points"
+        );
+
+        let collection_and_account_id = format!("{}{}{}", collection_name, ".", account_id);
+        let points = self
+            .staking_points_by_account
+            .get(&collection_and_account_id)
+            .unwrap_or(0);
+        let amount = u128::from(amount);
+        assert!(points >= amount, "Not enough points");
+
+        self.staking_points_by_account
+            .insert(&collection_and_account_id, &(points - amount));
+
+        U128(points - amount)
+    }
+
+    //  -------------------------------------- ENUMERATION --------------------------------------
+    // Trạng thái lock của 1 NFT kèm số points tích luỹ (None nếu NFT không bị lock)
+    pub fn nft_lock_info(&self, token_id: TokenId) -> Option<JsonTokenLock> {
+        self.token_locks_by_id
+            .get(&token_id)
+            .map(|lock| JsonTokenLock {
+                token_id,
+                accrued_points: U128(accrued_staking_points(&lock, current_time_ms()).0),
+                lock,
+            })
+    }
+
+    // Check NFT có đang bị lock không
+    pub fn nft_is_locked(&self, token_id: TokenId) -> bool {
+        self.token_locks_by_id.get(&token_id).is_some()
+    }
+
+    // Số dư points của 1 account trong 1 Collection (chưa tính points tích luỹ của các NFT đang lock)
+    pub fn staking_points_of(
+        &self,
+        collection_name: CollectionName,
+        account_id: AccountId,
+    ) -> U128 {
+        U128(
+            self.staking_points_by_account
+                .get(&format!("{}{}{}", collection_name, ".", account_id))
+                .unwrap_or(0),
+        )
+    }
+
+    // Số points mỗi giây của 1 Template (dùng rate của rarity nếu Template không có rate)
+    pub fn staking_rate_of(&self, template_id: TemplateId) -> U128 {
+        let template = self
+            .templates_by_id
+            .get(&template_id)
+            .expect("Template does not exist");
+        U128(self.internal_template_staking_rate(&template))
+    }
+}
+
+// Số points tích luỹ của 1 lock tới thời điểm `now` (không cộng thêm sau unlock_at)
+// Return (points, claimed_until mới), chỉ tính các giây tròn
+pub(crate) fn accrued_staking_points(lock: &TokenLock, now: Timestamp) -> (u128, Timestamp) {
+    let until = std::cmp::min(now, lock.unlock_at);
+    if until <= lock.claimed_until {
+        return (0, lock.claimed_until);
+    }
+
+    let seconds = (until - lock.claimed_until) / 1000;
+    (
+        u128::from(seconds) * u128::from(lock.points_per_second),
+        lock.claimed_until + seconds * 1000,
+    )
+}
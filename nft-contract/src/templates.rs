@@ -6,21 +6,23 @@ impl NFTContract {
     // Tạo 1 Template mới
     /**
      * - Yêu cầu user nạp tiền để cover phí lưu trữ
+     * - validity_duration: NFT hết hạn sau validity_duration milliseconds kể từ lúc mint
      * - Thêm Template vào templates_by_id
      * - Refund lại NEAR user deposit thừa
      */
     #[payable]
-    pub fn create_template(
-        &mut self,
-        collection_name: CollectionName,
-        schema_id: SchemaId,
-        transferable: bool,
-        burnable: bool,
-        max_supply: u32,
-        immutable_data: ImmutableData,
-    ) -> Template {
+    pub fn create_template(&mut self, collection_name: CollectionName, args: TemplateArgs) -> Template {
         let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
 
+        let TemplateArgs {
+            schema_id,
+            transferable,
+            burnable,
+            max_supply,
+            immutable_data,
+            validity_duration,
+        } = args;
+
         let template_id = self.templates_by_id.len() as u32;
 
         self.token_by_template_id_counter.insert(&template_id, &0); // Khi tạo Template -> Cho stt counter = 0
@@ -45,6 +47,12 @@ impl NFTContract {
         // Check xem schema_id đó có thuộc collection_id đó không
         assert_eq!(schema_of_template.collection_name, collection_name, "Schema does not belongs to this collection");
 
+        // Check thời hạn sử dụng của NFT (milliseconds)
+        assert!(validity_duration != Some(0), "Validity duration must be greater than 0");
+
+        // Check link + hash của ảnh và reference
+        self.assert_valid_immutable_data(&immutable_data);
+
         // Tạo Template mới
         let new_template = Template {
             template_id,
@@ -56,7 +64,9 @@ impl NFTContract {
             burnable,
             max_supply,
             issued_supply: 0,
+            burned_supply: 0,
             immutable_data,
+            validity_duration,
             is_lootbox: false,
             unlock_time: None,
             config: None,
@@ -64,6 +74,27 @@ impl NFTContract {
 
         // Insert template mới vào templates_by_id
         self.templates_by_id.insert(&template_id, &new_template);
+        // Insert template mới vào index của Collection
+        self.internal_add_template_to_collection(&new_template.collection_name, template_id, false);
+        // Index tên của Template để search
+        self.internal_update_search_index(SearchKind::Template, template_id, &[], &[&new_template.immutable_data.name]);
+
+        // -------------------------------------------------------------------
+        // TEMPLATE CREATE LOG
+        let template_create_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::TemplateCreate(vec![TemplateCreateLog {
+                owner_id: collection_of_template.owner_id.to_string(),
+                collection_name: new_template.collection_name.clone(),
+                schema_id: schema_id.to_string(),
+                template_id: template_id.to_string(),
+                max_supply,
+                memo: None,
+            }]),
+        };
+        env::log(template_create_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
 
         // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
         let after_storage_usage = env::storage_usage();
@@ -84,74 +115,30 @@ impl NFTContract {
         // Check collection id có tồn tại không
         assert!(self.collections_by_name.get(&collection_name).is_some(), "Collection does not exist");
 
-        let mut count = 0;
-
-        let templates_set_by_collection: Vec<Template> = self
-            .templates_by_id
-            .keys()
-            .map(|template_id| self.templates_by_id.get(&template_id).unwrap())
-            .collect();
-
-        for template in templates_set_by_collection {
-            if template.collection_name == collection_name {
-                count += 1;
-            }
-        }
-
-        U128(count)
+        U128(collection_index_len(&self.templates_per_collection, &collection_name) as u128)
     }
 
-    // Lấy danh sách tất cả Templates trong Contract
-    pub fn get_all_templates(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Template> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        // Duyệt tất cả các keys -> Trả về Template
-        self.templates_by_id
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(10) as usize)
-            .map(|(template_id, _template)| self.templates_by_id.get(&template_id).unwrap())
-            .collect()
+    // Lấy danh sách tất cả Templates trong Contract (cursor: template id)
+    pub fn get_all_templates(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Template> {
+        // Template id = thứ tự tạo
+        let template_count = self.templates_by_id.len() as u32;
+        paginate_ids(0, template_count, cursor, limit, |template_id| self.templates_by_id.get(template_id))
     }
 
-    // Lấy danh sách Template của Collection nào đó (có pagination)
+    // Lấy danh sách Template của Collection nào đó (cursor: template id)
     pub fn get_all_templates_by_collection(
         &self,
         collection_name: CollectionName,
-        from_index: Option<U128>,
+        cursor: Option<String>,
         limit: Option<u64>,
-    ) -> Vec<Template> {
-        let mut count = 0;
-
+    ) -> Page<Template> {
         // Check collection id có tồn tại không
         assert!(self.collections_by_name.get(&collection_name).is_some(), "Collection does not exist");
 
-        let mut result = Vec::<Template>::new();
-
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        // Duyệt tất cả các keys -> Trả về Collection
-        let templates_set_for_owner: Vec<Template> = self
-            .templates_by_id
-            .keys()
-            .skip(start as usize) // Pagination
-            .map(|template_id| self.templates_by_id.get(&template_id).unwrap())
-            .collect();
-
-        // If limit = 0 -> Return empty Array
-        if limit.is_some() && limit.unwrap() == 0 {
-            return result;
-        }
-
-        for template in templates_set_for_owner {
-            if template.collection_name == collection_name {
-                result.push(template);
-                count += 1;
-            }
-            if count == limit.unwrap_or(10) {
-                break;
-            }
-        }
-        result
+        // Duyệt các template ids trong index của Collection -> Trả về Template
+        paginate_collection_index(&self.templates_per_collection, &collection_name, cursor, limit, |template_id| {
+            self.templates_by_id.get(template_id)
+        })
     }
 
     // Lấy Template theo id
@@ -159,18 +146,17 @@ impl NFTContract {
         self.templates_by_id.get(&template_id).expect("Template does not exist")
     }
 
-    // Search Template theo name
-    // Lấy về tất cả Template mà tên có chứa ký tự của `search_string`
-    pub fn get_templates_by_name(&self, search_string: String) -> Vec<Template> {
-        let templates_set: Vec<Template> = self.templates_by_id.values().collect();
-
-        let mut result = Vec::<Template>::new();
-
-        for template in templates_set {
-            if template.immutable_data.name.to_lowercase().contains(&search_string.to_lowercase()) {
-                result.push(template);
-            }
-        }
-        result
+    // Search Template theo name (cursor pagination như `search`)
+    // Lấy về các Template mà mỗi từ của `search_string` là prefix của 1 từ trong tên
+    pub fn get_templates_by_name(
+        &self,
+        search_string: String,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<Template> {
+        self.search(search_string, Some(vec![SearchKind::Template]), cursor, limit).filter_map(|result| match result {
+            SearchResult::Template(template) => Some(template),
+            _ => None,
+        })
     }
 }
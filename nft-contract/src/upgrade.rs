@@ -0,0 +1,272 @@
+use crate::*;
+
+// ----------------------------------- Upgrade Rule Struct -----------------------------------
+// Rule: 1 NFT of from_template + cost -> the same NFT becomes an NFT of to_template
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UpgradeRule {
+    pub rule_id: UpgradeRuleId,                 // Id of the Upgrade rule
+    pub owner_id: AccountId,                    // Owner of the Collection that created the rule
+    pub collection_name: CollectionName,        // Collection that the rule belongs to
+    pub from_template_id: TemplateId,           // Template of the NFT before the upgrade
+    pub to_template_id: TemplateId,             // Template of the NFT after the upgrade
+    pub price: U128,                            // NEAR paid to the Collection's owner (yoctoNEAR)
+    pub burn_ingredients: Vec<BlendIngredient>, // NFTs burned by the upgrade (the n-th token must match the n-th ingredient)
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Tạo 1 Upgrade rule mới thuộc 1 Collection nào đó
+    /**
+     * - Only the Collection's owner can create an Upgrade rule
+     * - from_template and to_template must be 2 different normal Templates of the Collection
+     * - The cost can be NEAR, burned NFTs or both, but cannot be empty
+     */
+    #[payable]
+    pub fn create_upgrade_rule(
+        &mut self,
+        collection_name: CollectionName,
+        from_template_id: TemplateId,
+        to_template_id: TemplateId,
+        price: Option<U128>,
+        burn_ingredients: Option<Vec<BlendIngredient>>,
+    ) -> UpgradeRule {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        // Check signer id is Collection's owner or not
+        assert_eq!(
+            collection.owner_id,
+            env::predecessor_account_id(),
+            "Only owner of this collection can create Upgrade rule"
+        );
+
+        assert_ne!(
+            from_template_id, to_template_id,
+            "Cannot upgrade a Template to itself"
+        );
+        for template_id in [from_template_id, to_template_id].iter() {
+            let template = self
+                .templates_by_id
+                .get(template_id)
+                .expect("Template does not exist");
+            assert_eq!(
+                template.collection_name, collection_name,
+                "Template does not belong to this collection"
+            );
+            assert!(!template.is_lootbox, "Cannot upgrade a Lootbox");
+        }
+
+        let price = price.unwrap_or(U128(0));
+        let burn_ingredients = burn_ingredients.unwrap_or_default();
+        assert!(
+            price.0 > 0 || !burn_ingredients.is_empty(),
+            "Upgrade rule must have a cost"
+        );
+        self.internal_assert_valid_ingredients(&burn_ingredients, &collection_name);
+
+        let rule_id = self.upgrade_rule_id_counter;
+        self.upgrade_rule_id_counter += 1;
+
+        let rule = UpgradeRule {
+            rule_id,
+            owner_id: collection.owner_id,
+            collection_name,
+            from_template_id,
+            to_template_id,
+            price,
+            burn_ingredients,
+        };
+
+        self.upgrade_rules_by_id.insert(&rule_id, &rule);
+        // Insert Upgrade rule vào index của Collection
+        self.internal_add_upgrade_rule_to_collection(&rule.collection_name, rule_id);
+
+        // Luợng data storage sử dụng = after_storage_usage - before_storage_usage
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit(after_storage_usage - before_storage_usage);
+
+        rule
+    }
+
+    // Upgrade 1 NFT: the NFT keeps its token_id but becomes an NFT of rule.to_template_id
+    /**
+     * - Only the NFT's owner can upgrade it, the NFT must not be locked
+     * - Mint must not be paused and the Collection must not be frozen
+     * - Burn the ingredient NFTs, pay rule.price to the Collection's owner
+     * - Switch template_id, schema_id of the NFT and regenerate its metadata from the new Template
+     * - Keep the validity window (starts_at, expires_at) of the NFT if it has one
+     * - from_template: burned_supply += 1, to_template: issued_supply += 1
+     */
+    #[payable]
+    pub fn upgrade_token(
+        &mut self,
+        token_id: TokenId,
+        rule_id: UpgradeRuleId,
+        burn_token_ids: Option<Vec<TokenId>>,
+    ) -> JsonTokenDetail {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let owner_id = env::predecessor_account_id();
+        let rule = self
+            .upgrade_rules_by_id
+            .get(&rule_id)
+            .expect("Upgrade rule does not exist");
+        let mut token = self
+            .tokens_by_id
+            .get(&token_id)
+            .expect("Token does not exist");
+
+        assert_eq!(
+            token.owner_id, owner_id,
+            "Only owner of this token can upgrade it"
+        );
+        assert_eq!(
+            token.template_id, rule.from_template_id,
+            "Token does not belong to the Template of this rule"
+        );
+        // The NFT becomes a new NFT of to_template -> Same checks as minting
+        self.assert_not_paused(PauseFeature::Mint);
+        self.assert_collection_not_frozen(&rule.collection_name);
+        // Locked (staked) NFT cannot change while it accrues points of its Template
+        self.assert_token_not_locked(&token_id);
+
+        // --- Check and burn the ingredients ---
+        let burn_token_ids = burn_token_ids.unwrap_or_default();
+        assert!(
+            !burn_token_ids.contains(&token_id),
+            "Cannot burn the upgraded token"
+        );
+        self.internal_assert_ingredient_tokens(&rule.burn_ingredients, &burn_token_ids, &owner_id);
+        for burn_token_id in burn_token_ids.iter() {
+            self.internal_nft_burn(
+                burn_token_id,
+                Some(format!("{{\"upgrade_rule_id\":{}}}", rule_id)),
+            );
+        }
+
+        // --- Update the supply of both templates ---
+        let mut from_template = self
+            .templates_by_id
+            .get(&rule.from_template_id)
+            .expect("Template does not exist");
+        let mut to_template = self
+            .templates_by_id
+            .get(&rule.to_template_id)
+            .expect("Template does not exist");
+        assert!(
+            to_template.issued_supply < to_template.max_supply,
+            "This template has issued all the NFTs"
+        );
+
+        from_template.burned_supply += 1;
+        self.templates_by_id
+            .insert(&rule.from_template_id, &from_template);
+        to_template.issued_supply += 1;
+        self.templates_by_id
+            .insert(&rule.to_template_id, &to_template);
+
+        // Stt của NFT trong Template mới
+        let token_by_template_id = self
+            .token_by_template_id_counter
+            .get(&rule.to_template_id)
+            .expect("Not found Template");
+        self.token_by_template_id_counter
+            .insert(&rule.to_template_id, &(token_by_template_id + 1));
+
+        // --- Switch the NFT to the new template ---
+        self.internal_remove_token_from_template(token.template_id, token_id);
+        self.internal_add_token_to_template(to_template.template_id, token_id);
+        token.template_id = to_template.template_id;
+        token.schema_id = to_template.schema_id;
+        token.schema_name = to_template.schema_name.clone();
+        token.token_by_template_id = token_by_template_id;
+        self.tokens_by_id.insert(&token_id, &token);
+
+        // Regenerate metadata from the new Template's immutable data (keep issued_at)
+        // NFT đã có thời hạn -> Giữ nguyên starts_at, expires_at (upgrade không gia hạn NFT)
+        // NFT chưa có thời hạn -> Thời hạn của Template mới tính từ lúc upgrade
+        let old_metadata = self
+            .token_metadata_by_id
+            .get(&token_id)
+            .expect("Token metadata does not exist");
+        let mut metadata = template_token_metadata(&to_template);
+        metadata.issued_at = old_metadata.issued_at;
+        if old_metadata.starts_at.is_some() || old_metadata.expires_at.is_some() {
+            metadata.starts_at = old_metadata.starts_at;
+            metadata.expires_at = old_metadata.expires_at;
+        }
+        metadata.updated_at = Some(current_time_ms());
+        self.token_metadata_by_id.insert(&token_id, &metadata);
+
+        // --- Pay the Collection's owner ---
+        let price = u128::from(rule.price);
+        if price > 0 {
+            Promise::new(rule.owner_id.clone()).transfer(price);
+        }
+
+        // -------------------------------------------------------------------
+        // NFT UPGRADE LOG
+        let nft_upgrade_log: EventLog = EventLog {
+            standard: "nearhub".to_string(),
+            version: "1.0.0".to_string(),
+            event: EventLogVariant::NftUpgrade(vec![NftUpgradeLog {
+                owner_id: owner_id.to_string(),
+                token_id: token_id.to_string(),
+                rule_id: rule_id.to_string(),
+                from_template_id: rule.from_template_id.to_string(),
+                to_template_id: rule.to_template_id.to_string(),
+                burned_token_ids: burn_token_ids.iter().map(|id| id.to_string()).collect(),
+                memo: None,
+            }]),
+        };
+        env::log(nft_upgrade_log.to_string().as_bytes());
+        // -------------------------------------------------------------------
+
+        // Burned NFTs release storage -> Only charge the extra storage
+        let after_storage_usage = env::storage_usage();
+        // Refund NEAR
+        refund_deposit_with_price(
+            after_storage_usage.saturating_sub(before_storage_usage),
+            price,
+        );
+
+        self.nft_token_detail(token_id).unwrap()
+    }
+
+    //  -------------------------------------- ENUMERATION --------------------------------------
+    // Lấy Upgrade rule theo id
+    pub fn get_upgrade_rule_by_id(&self, rule_id: UpgradeRuleId) -> UpgradeRule {
+        self.upgrade_rules_by_id
+            .get(&rule_id)
+            .expect("Upgrade rule does not exist")
+    }
+
+    // Lấy danh sách Upgrade rules của 1 Collection (cursor: rule id)
+    pub fn get_all_upgrade_rules_by_collection(
+        &self,
+        collection_name: CollectionName,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<UpgradeRule> {
+        // Check collection id có tồn tại không
+        assert!(
+            self.collections_by_name.get(&collection_name).is_some(),
+            "Collection does not exist"
+        );
+
+        // Duyệt các rule ids trong index của Collection -> Trả về Upgrade rule
+        paginate_collection_index(
+            &self.upgrade_rules_per_collection,
+            &collection_name,
+            cursor,
+            limit,
+            |rule_id| self.upgrade_rules_by_id.get(rule_id),
+        )
+    }
+}
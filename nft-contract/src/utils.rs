@@ -1,4 +1,5 @@
 use std::mem::size_of;
+use std::str::FromStr;
 
 use crate::*;
 
@@ -10,9 +11,21 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     hash
 }
 
+// Chuyển token id dạng string (NEP-171) sang TokenId
+pub(crate) fn parse_token_id(token_id: &JsonTokenId) -> TokenId {
+    token_id
+        .parse::<TokenId>()
+        .unwrap_or_else(|_| env::panic(b"Invalid token id"))
+}
+
 pub(crate) fn refund_deposit(storage_used: u64) {
+    refund_deposit_with_price(storage_used, 0);
+}
+
+// Giống refund_deposit, nhưng user phải trả thêm `price` ngoài phí lưu trữ
+pub(crate) fn refund_deposit_with_price(storage_used: u64, price: Balance) {
     // Tính lượng tiền cần nạp để cover storage
-    let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
+    let required_cost = env::storage_byte_cost() * Balance::from(storage_used) + price;
     let attached_deposit = env::attached_deposit();
 
     // Nếu người dùng deposit lượng tiền ít hơn lượng cần thiết để lưu data -> Báo lỗi
@@ -50,6 +63,94 @@ pub(crate) fn bytes_for_approved_account_id(account_id: &AccountId) -> u64 {
     account_id.as_str().len() as u64 + 4 * size_of::<u64>() as u64
 }
 
+// Return số bytes data đã sử dụng của 1 operator (account + collection scope)
+pub(crate) fn bytes_for_operator_approval(operator_id: &AccountId, approval: &OperatorApproval) -> u64 {
+    bytes_for_approved_account_id(operator_id)
+        + approval.collection_name.as_ref().map_or(0, |name| name.len() as u64)
+}
+
+// ----------------------------------- Storage của near-sdk collections -----------------------------------
+// Số bytes của 1 record key-value trong storage: key + value + STORAGE_BYTES_PER_RECORD
+pub(crate) fn bytes_for_record(key_len: u64, value_len: u64) -> u64 {
+    key_len + value_len + STORAGE_BYTES_PER_RECORD
+}
+
+// Số bytes của prefix tạo từ 1 StorageKey
+pub(crate) fn bytes_for_storage_key(storage_key: StorageKey) -> u64 {
+    storage_key.try_to_vec().unwrap().len() as u64
+}
+
+// 1 entry mới của LookupMap (hoặc LookupSet với value_len = 0): prefix + key -> value
+pub(crate) fn bytes_for_lookup_map_entry(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    bytes_for_record(prefix_len + key_len, value_len)
+}
+
+// 1 phần tử mới của UnorderedSet: prefix + 'i' + element -> index, prefix + 'e' + index -> element
+pub(crate) fn bytes_for_unordered_set_element(prefix_len: u64, element_len: u64) -> u64 {
+    let index_len = size_of::<u64>() as u64;
+    bytes_for_record(prefix_len + 1 + element_len, index_len)
+        + bytes_for_record(prefix_len + 1 + index_len, element_len)
+}
+
+// 1 entry mới của UnorderedMap: prefix + 'i' + key -> index, prefix + 'k' + index -> key, prefix + 'v' + index -> value
+pub(crate) fn bytes_for_unordered_map_entry(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    let index_len = size_of::<u64>() as u64;
+    bytes_for_record(prefix_len + 1 + key_len, index_len)
+        + bytes_for_record(prefix_len + 1 + index_len, key_len)
+        + bytes_for_record(prefix_len + 1 + index_len, value_len)
+}
+
+// 1 entry mới của TreeMap: prefix + 'v' + key -> value, prefix + 'n' + index -> node (id, key, lft, rgt, ht)
+// Mỗi node trừ root có 1 link (u64) từ node cha -> Tính link này vào entry, bytes_for_tree_map trừ link của root
+pub(crate) fn bytes_for_tree_map_entry(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    let index_len = size_of::<u64>() as u64;
+    // id + key + tag Option của lft, rgt + ht + 1 link
+    let node_len = index_len + key_len + 2 + index_len + index_len;
+    bytes_for_record(prefix_len + 1 + key_len, value_len)
+        + bytes_for_record(prefix_len + 1 + index_len, node_len)
+}
+
+// Borsh của 1 UnorderedSet lưu trong value của 1 map (index map prefix + elements Vector)
+pub(crate) fn bytes_for_unordered_set(prefix_len: u64) -> u64 {
+    2 * (size_of::<u32>() as u64 + prefix_len + 1) + size_of::<u64>() as u64
+}
+
+// Borsh của 1 TreeMap lưu trong value của 1 map (root + values LookupMap prefix + nodes Vector), trừ link của root node
+pub(crate) fn bytes_for_tree_map(prefix_len: u64) -> u64 {
+    // root + len của nodes Vector - link của root node = 1 u64
+    2 * (size_of::<u32>() as u64 + prefix_len + 1) + size_of::<u64>() as u64
+}
+
+// Borsh của 1 String (AccountId, CollectionName, ...)
+pub(crate) fn bytes_for_string(len: u64) -> u64 {
+    size_of::<u32>() as u64 + len
+}
+
+// Storage của các index tạo bởi NFT đầu tiên của 1 owner: entry trong tokens_per_owner, holder trong holders_per_collection
+pub(crate) fn bytes_for_new_owner(owner_id_len: u64, is_new_owner: bool, is_new_holder: bool) -> u64 {
+    let owner_id_len = bytes_for_string(owner_id_len);
+    let mut storage = 0;
+    if is_new_owner {
+        storage += bytes_for_lookup_map_entry(
+            bytes_for_storage_key(StorageKey::TokensPerOwnerKey),
+            owner_id_len,
+            bytes_for_unordered_set(bytes_for_storage_key(StorageKey::TokensPerOwnerInnerKey {
+                account_id_hash: CryptoHash::default(),
+            })),
+        );
+    }
+    if is_new_holder {
+        storage += bytes_for_tree_map_entry(
+            bytes_for_storage_key(StorageKey::HoldersPerCollectionInnerKey {
+                collection_name_hash: CryptoHash::default(),
+            }),
+            owner_id_len,
+            size_of::<u32>() as u64,
+        );
+    }
+    storage
+}
+
 // Refund lại số tiền đã deposit để lưu trữ data của user
 pub(crate) fn refund_approved_account_ids_iter<'a, I>(sender_id: AccountId, approved_account_ids: I)
 where
@@ -66,4 +167,240 @@ where
 // Hàm refund lại số tiền mọi người đã deposit khi gọi hàm revoke_all()
 pub(crate) fn refund_approved_account_ids(sender_id: AccountId, approved_account_ids: &HashMap<AccountId, u64>) {
     refund_approved_account_ids_iter(sender_id, approved_account_ids.keys());
-}
\ No newline at end of file
+}
+
+// value * numerator / denominator (làm tròn xuống), với numerator <= denominator <= u64::MAX
+// Tách value = q * denominator + r để không bị overflow u128 khi value * numerator quá lớn
+pub(crate) fn mul_div(value: u128, numerator: u128, denominator: u128) -> u128 {
+    let quotient = value / denominator;
+    let remainder = value % denominator;
+    quotient * numerator + remainder * numerator / denominator
+}
+
+// Deserialize a field that can be missing (None), null (Some(None)) or a value (Some(Some(value)))
+// Dùng với #[serde(default)] cho các field có thể bị xoá khi update
+pub(crate) fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: near_sdk::serde::Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer).map(Some)
+}
+
+// Pick a random index of `weights`, each index has a chance proportional to its weight
+// Return None if all the weights are 0
+pub(crate) fn random_weighted_index(rng: &mut Rng, weights: &[u64]) -> Option<usize> {
+    let total_weight: u64 = weights.iter().sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    // Random from 0 -> total_weight, then find the range that the value falls into
+    let mut value = rng.rand_range_u64(0, total_weight);
+    for (index, weight) in weights.iter().enumerate() {
+        if value < *weight {
+            return Some(index);
+        }
+        value -= weight;
+    }
+
+    None
+}
+
+// Token metadata generated from the immutable data of a Template
+// Template có validity_duration -> NFT valid từ lúc mint tới lúc mint + validity_duration
+pub(crate) fn template_token_metadata(template: &Template) -> TokenMetadata {
+    let now = current_time_ms();
+    TokenMetadata {
+        title: Some(template.immutable_data.name.clone()),
+        description: None,
+        media: template.immutable_data.img.clone(),
+        media_hash: template.immutable_data.img_hash.clone(),
+        copies: Some(template.max_supply as u64),
+        issued_at: Some(now),
+        expires_at: template.validity_duration.map(|duration| now + duration),
+        starts_at: template.validity_duration.map(|_| now),
+        updated_at: None,
+        extra: template.immutable_data.extra_immutable_data.clone(),
+        reference: template.immutable_data.reference.clone(),
+        reference_hash: template.immutable_data.reference_hash.clone(),
+        nft_type: "NFT".to_string(),
+    }
+}
+
+// Link tuyệt đối (URL, data URL) hay tương đối với base_uri của Contract (VD: IPFS CID)
+pub(crate) fn is_absolute_link(link: &str) -> bool {
+    link.contains("://") || link.starts_with("data:")
+}
+
+// Current block time in milliseconds (the unit of all the timestamps of this Contract)
+pub(crate) fn current_time_ms() -> Timestamp {
+    env::block_timestamp() / 10u64.pow(6)
+}
+
+// Token đã hết hạn chưa (now >= expires_at), tất cả timestamp tính bằng milliseconds
+pub(crate) fn is_token_expired(metadata: &TokenMetadata, now: Timestamp) -> bool {
+    metadata.expires_at.is_some_and(|expires_at| now >= expires_at)
+}
+
+// Token có đang trong thời hạn sử dụng không (starts_at <= now < expires_at)
+pub(crate) fn is_token_valid(metadata: &TokenMetadata, now: Timestamp) -> bool {
+    metadata.starts_at.is_none_or(|starts_at| now >= starts_at) && !is_token_expired(metadata, now)
+}
+
+// Thêm id vào 1 index theo Collection (schemas, templates, lootboxes, drops, tokens per collection)
+// Return false nếu id đã có trong index
+pub(crate) fn insert_into_collection_index(
+    index: &mut LookupMap<CollectionName, TreeMap<u32, ()>>,
+    collection_name: &CollectionName,
+    id: u32,
+    inner_key: impl FnOnce(CryptoHash) -> StorageKey,
+) -> bool {
+    let mut id_set = index
+        .get(collection_name)
+        .unwrap_or_else(|| TreeMap::new(inner_key(hash_account_id(collection_name)).try_to_vec().unwrap()));
+    let is_new = id_set.insert(&id, &()).is_none();
+    index.insert(collection_name, &id_set);
+    is_new
+}
+
+// Xoá id khỏi 1 index theo Collection
+// Return false nếu id không có trong index
+pub(crate) fn remove_from_collection_index(
+    index: &mut LookupMap<CollectionName, TreeMap<u32, ()>>,
+    collection_name: &CollectionName,
+    id: u32,
+) -> bool {
+    let mut id_set = if let Some(id_set) = index.get(collection_name) {
+        id_set
+    } else {
+        return false;
+    };
+
+    let is_removed = id_set.remove(&id).is_some();
+    if id_set.len() == 0 {
+        index.remove(collection_name);
+    } else {
+        index.insert(collection_name, &id_set);
+    }
+    is_removed
+}
+
+// Số lượng id của 1 Collection trong index
+pub(crate) fn collection_index_len(
+    index: &LookupMap<CollectionName, TreeMap<u32, ()>>,
+    collection_name: &CollectionName,
+) -> u64 {
+    index.get(collection_name).map(|id_set| id_set.len()).unwrap_or(0)
+}
+
+// Lấy 1 trang các ids của 1 Collection trong index (cursor pagination)
+pub(crate) fn paginate_collection_index<T>(
+    index: &LookupMap<CollectionName, TreeMap<u32, ()>>,
+    collection_name: &CollectionName,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&u32) -> Option<T>,
+) -> Page<T> {
+    match index.get(collection_name) {
+        Some(id_set) => paginate_tree_map(&id_set, cursor, limit, get),
+        None => Page::empty(),
+    }
+}
+
+// ----------------------------------- Cursor pagination -----------------------------------
+// Limit của 1 trang: mặc định DEFAULT_PAGE_LIMIT, trong khoảng [1, MAX_PAGE_LIMIT]
+pub(crate) fn page_limit(limit: Option<u64>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT) as usize
+}
+
+// Parse cursor (id của item cuối cùng của trang trước)
+pub(crate) fn parse_cursor<K: FromStr>(cursor: &str) -> K {
+    cursor.parse().unwrap_or_else(|_| env::panic(b"Invalid cursor"))
+}
+
+// Lấy 1 trang từ các keys đã sort tăng dần, bỏ qua các keys mà get -> None (đã bị xoá hoặc bị lọc)
+// Đọc tối đa MAX_PAGE_SCAN keys: quá số này thì trả về trang chưa đủ limit (có thể rỗng) kèm cursor để đọc tiếp
+pub(crate) fn page_from_sorted_keys<K: ToString, T>(
+    sorted_keys: impl Iterator<Item = K>,
+    limit: Option<u64>,
+    mut get: impl FnMut(&K) -> Option<T>,
+) -> Page<T> {
+    let limit = page_limit(limit);
+    let mut items = Vec::new();
+    let mut last_key: Option<K> = None; // Key cuối cùng đã đọc
+
+    for (scanned, key) in sorted_keys.enumerate() {
+        // Đã đọc đủ MAX_PAGE_SCAN keys và vẫn còn key -> Trang tiếp theo đọc tiếp sau key cuối cùng
+        if scanned == MAX_PAGE_SCAN {
+            return Page { items, next_cursor: last_key.map(|key| key.to_string()) };
+        }
+        if let Some(item) = get(&key) {
+            // Trang đã đủ và vẫn còn item -> Trả về cursor để lấy trang tiếp theo
+            if items.len() == limit {
+                return Page { items, next_cursor: last_key.map(|key| key.to_string()) };
+            }
+            items.push(item);
+        }
+        last_key = Some(key);
+    }
+
+    Page { items, next_cursor: None }
+}
+
+// Cursor pagination trên 1 tập keys không có thứ tự (UnorderedSet, keys của UnorderedMap)
+// Sort các keys -> Lấy các keys sau cursor, nên thứ tự không đổi khi có phần tử bị xoá (swap-remove)
+// Đọc toàn bộ set ở mỗi trang -> Chỉ dùng cho các set theo owner của layout V1 (tokens_per_owner, collections_per_owner)
+pub(crate) fn paginate_keys<K: Ord + ToString + FromStr, T>(
+    keys: impl Iterator<Item = K>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&K) -> Option<T>,
+) -> Page<T> {
+    let cursor: Option<K> = cursor.map(|cursor| parse_cursor(&cursor));
+    let mut sorted_keys: Vec<K> = keys.filter(|key| cursor.as_ref().is_none_or(|cursor| key > cursor)).collect();
+    sorted_keys.sort();
+
+    page_from_sorted_keys(sorted_keys.into_iter(), limit, get)
+}
+
+// Cursor pagination trên các keys của 1 TreeMap (đã có thứ tự)
+// Chỉ đọc các keys sau cursor -> 1 trang tốn O(limit) lần tìm key tiếp theo
+pub(crate) fn paginate_tree_map<K, V, T>(
+    map: &TreeMap<K, V>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&K) -> Option<T>,
+) -> Page<T>
+where
+    K: Ord + Clone + ToString + FromStr + BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    let entries: Box<dyn Iterator<Item = (K, V)>> = match cursor {
+        Some(cursor) => Box::new(map.iter_from(parse_cursor(&cursor))),
+        None => Box::new(map.iter()),
+    };
+
+    page_from_sorted_keys(entries.map(|(key, _)| key), limit, get)
+}
+
+// Cursor pagination trên các ids tăng dần trong [first_id, end_id) (auto increment ids)
+// Chỉ đọc các ids sau cursor, bỏ qua các ids đã bị xoá (tối đa MAX_PAGE_SCAN ids mỗi trang)
+pub(crate) fn paginate_ids<T>(
+    first_id: u32,
+    end_id: u32,
+    cursor: Option<String>,
+    limit: Option<u64>,
+    get: impl FnMut(&u32) -> Option<T>,
+) -> Page<T> {
+    let start = match cursor {
+        // Cursor là id lớn nhất (u32::MAX) -> Không còn id nào sau cursor
+        Some(cursor) => match parse_cursor::<u32>(&cursor).checked_add(1) {
+            Some(start) => start,
+            None => return Page::empty(),
+        },
+        None => first_id,
+    };
+
+    page_from_sorted_keys(start.max(first_id)..end_id, limit, get)
+}
@@ -0,0 +1,170 @@
+// Các hàm cho Mint Vouchers (lazy minting)
+use crate::*;
+
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use std::convert::TryFrom;
+
+// Voucher signed off-chain by the Collection's voucher key
+// The signed message is the Borsh serialization of MintVoucherPayload
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintVoucher {
+    pub template_id: TemplateId, // Template of the NFT to mint
+    pub receiver_id: AccountId,  // Account that receives the NFT
+    pub price: U128,             // Price that the redeemer has to pay (to the Collection's owner)
+    pub expires_at: Timestamp,   // The voucher cannot be redeemed after this time (milliseconds)
+    pub nonce: u64,              // Unique number of the voucher inside its Collection
+}
+
+// Signed message of a voucher
+// Binds the voucher to this Contract and to its Collection -> It cannot be replayed on another deployment or Collection
+#[derive(BorshSerialize)]
+pub struct MintVoucherPayload {
+    pub contract_id: AccountId,          // Account of this NFT Contract
+    pub collection_name: CollectionName, // Collection of voucher.template_id
+    pub voucher: MintVoucher,
+}
+
+#[near_bindgen]
+impl NFTContract {
+    // Set the public key that signs the Mint Vouchers of a Collection
+    // Only the owner of the Collection can set it (None -> disable vouchers)
+    #[payable]
+    pub fn set_voucher_public_key(
+        &mut self,
+        collection_name: CollectionName,
+        public_key: Option<Base58PublicKey>,
+    ) {
+        assert_at_least_one_yocto();
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let mut collection = self
+            .collections_by_name
+            .get(&collection_name)
+            .expect("Collection does not exist");
+
+        assert_eq!(
+            env::predecessor_account_id(),
+            collection.owner_id,
+            "Only owner of this collection can set the voucher public key"
+        );
+
+        if let Some(public_key) = &public_key {
+            // Only ed25519 keys: 1 byte for the curve type + 32 bytes of key
+            assert!(
+                public_key.0.len() == 33 && public_key.0[0] == 0,
+                "Voucher public key must be an ed25519 key"
+            );
+        }
+
+        collection.voucher_public_key = public_key;
+        self.collections_by_name
+            .insert(&collection_name, &collection);
+        self.collections_by_id
+            .insert(&collection.collection_id, &collection);
+
+        // Refund NEAR (the key may use less storage than before)
+        let after_storage_usage = env::storage_usage();
+        refund_deposit(after_storage_usage.saturating_sub(before_storage_usage));
+    }
+
+    // Mint the NFT of a voucher signed by the Collection's voucher key
+    /**
+     * - Check the voucher is not expired and its nonce has not been used
+     * - Check the signature of MintVoucherPayload (this Contract + Collection + voucher) with the Collection's voucher public key
+     * - Mark the nonce as used -> The voucher cannot be redeemed again
+     * - Mint the NFT to voucher.receiver_id
+     * - Yêu cầu user nạp tiền để cover price + phí lưu trữ, refund lại NEAR deposit thừa
+     */
+    #[payable]
+    pub fn redeem_voucher(&mut self, voucher: MintVoucher, signature: Base64VecU8) -> TokenId {
+        let before_storage_usage = env::storage_usage(); // Dùng để tính toán lượng near thừa khi deposit
+
+        let template = self
+            .templates_by_id
+            .get(&voucher.template_id)
+            .expect("Template does not exist");
+        assert!(
+            !template.is_lootbox,
+            "Cannot redeem a voucher for a Lootbox"
+        );
+
+        let collection = self
+            .collections_by_name
+            .get(&template.collection_name)
+            .expect("Collection does not exist");
+        let public_key = collection
+            .voucher_public_key
+            .expect("This Collection does not accept vouchers");
+
+        // Check the voucher is not expired
+        let base: u64 = 10;
+        assert!(
+            env::block_timestamp() < voucher.expires_at * base.pow(6),
+            "Voucher has expired"
+        );
+
+        // Check the signature of the voucher
+        let message = MintVoucherPayload {
+            contract_id: env::current_account_id(),
+            collection_name: collection.collection_name.clone(),
+            voucher: voucher.clone(),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert!(
+            verify_ed25519_signature(&public_key.0[1..], &message, &signature.0),
+            "Invalid voucher signature"
+        );
+
+        // Check the voucher has not been redeemed yet -> Mark it as redeemed
+        let collection_and_nonce =
+            format!("{}{}{}", collection.collection_name, ".", voucher.nonce);
+        assert!(
+            self.used_voucher_nonces.insert(&collection_and_nonce),
+            "Voucher has already been redeemed"
+        );
+
+        let token_ids = self.internal_nft_mint(
+            template.collection_name,
+            template.schema_id,
+            template.template_id,
+            Some(1),
+            voucher.receiver_id,
+        );
+
+        // Redeemer pays the voucher price + the storage of the NFT
+        let after_storage_usage = env::storage_usage();
+        refund_deposit_with_price(after_storage_usage - before_storage_usage, voucher.price.0);
+
+        if voucher.price.0 > 0 {
+            Promise::new(collection.owner_id).transfer(voucher.price.0);
+        }
+
+        token_ids[0]
+    }
+
+    // Check if the voucher of a Collection has been redeemed or not
+    pub fn is_voucher_redeemed(&self, collection_name: CollectionName, nonce: u64) -> bool {
+        self.used_voucher_nonces
+            .contains(&format!("{}{}{}", collection_name, ".", nonce))
+    }
+}
+
+// Check an ed25519 signature of `message`
+pub(crate) fn verify_ed25519_signature(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let public_key = match PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::try_from(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    public_key.verify(message, &signature).is_ok()
+}
@@ -0,0 +1,92 @@
+// Airdrop jobs: the prepaid storage must cover every recipient, even new owners with long account ids
+mod common;
+
+use common::*;
+use near_sdk::{env, AccountId};
+use nft_contract::*;
+
+// `count` recipients that don't own any NFT yet, 1 NFT each
+fn fresh_recipients(count: usize, suffix: &str) -> Vec<(AccountId, u32)> {
+    (0..count)
+        .map(|index| (format!("recipient-{}{}", index, suffix), 1))
+        .collect()
+}
+
+#[test]
+fn prepaid_storage_matches_storage_of_airdrop() {
+    let mut contract = setup_contract();
+    let first_template_id = create_template(&mut contract, "Pass A", 10);
+    let second_template_id = create_template(&mut contract, "Pass B", 10);
+    // Index của Collection đã được tạo bởi NFT đầu tiên
+    mint(&mut contract, alice(), 1);
+
+    // Airdrop trực tiếp 1 Template mới cho 5 owners mới
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let before_storage_usage = env::storage_usage();
+    contract.nft_airdrop(first_template_id, fresh_recipients(5, ".testnet"));
+    let storage_used = env::storage_usage() - before_storage_usage;
+
+    // Cùng số NFTs, owners mới có account id cùng độ dài -> Prepaid bằng đúng storage đã dùng
+    let job = contract.create_airdrop_job(second_template_id, fresh_recipients(5, ".mainnet"));
+    assert_eq!(
+        job.storage_balance.0,
+        u128::from(storage_used) * env::storage_byte_cost()
+    );
+}
+
+#[test]
+fn job_with_many_fresh_recipients_finishes_without_extra_deposit() {
+    let mut contract = setup_contract();
+
+    // Account id dài nhất (64 ký tự) -> trường hợp tốn storage nhất
+    let long_suffix = format!(".{}", "a".repeat(50));
+    let recipients = fresh_recipients(60, &long_suffix);
+    assert!(recipients.iter().all(|(account_id, _)| account_id.len() <= 64));
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let job = contract.create_airdrop_job(0, recipients.clone());
+    assert_eq!(job.total_recipients, 60);
+
+    // Gọi airdrop_continue tới khi xong, không nạp thêm NEAR
+    let mut calls = 0;
+    let mut job = job;
+    while !job.is_finished {
+        set_caller(bob(), 0);
+        job = contract.airdrop_continue(job.job_id);
+        calls += 1;
+    }
+
+    assert_eq!(calls, 6);
+    assert_eq!(job.minted_tokens, 60);
+    assert_eq!(job.storage_balance.0, 0);
+    assert!(recipients
+        .iter()
+        .all(|(account_id, _)| contract.nft_supply_for_owner(account_id.clone()).0 == 1));
+}
+
+#[test]
+fn recipients_added_later_are_prepaid_too() {
+    let mut contract = setup_contract();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let job = contract.create_airdrop_job(0, fresh_recipients(30, ".testnet"));
+    let job = contract.airdrop_job_add_recipients(job.job_id, fresh_recipients(30, ".near"));
+    assert_eq!(job.total_tokens, 60);
+
+    let mut job = job;
+    while !job.is_finished {
+        set_caller(bob(), 0);
+        job = contract.airdrop_continue(job.job_id);
+    }
+    assert_eq!(job.minted_tokens, 60);
+    assert_eq!(contract.nft_supply_for_template(0).0, 60);
+}
+
+#[test]
+#[should_panic(expected = "This template does not have enough NFTs for the Airdrop")]
+fn job_cannot_exceed_template_max_supply() {
+    let mut contract = setup_contract();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_airdrop_job(0, fresh_recipients(101, ".testnet"));
+}
@@ -0,0 +1,198 @@
+// nft_batch_transfer: same checks as nft_transfer, 1 nft_transfer event grouped by (old owner, new owner)
+mod common;
+
+use common::*;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::{get_created_receipts, get_logs};
+use nft_contract::*;
+
+// `nep171` events logged by the last call
+fn nep171_events() -> Vec<Value> {
+    get_logs()
+        .iter()
+        .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+        .map(|json| serde_json::from_str::<Value>(json).unwrap())
+        .filter(|event| event["standard"] == "nep171")
+        .collect()
+}
+
+// Receivers of the refunds (Transfer receipts) created by the last call
+fn refund_receivers() -> Vec<String> {
+    get_created_receipts()
+        .iter()
+        .map(|receipt| {
+            serde_json::from_str::<Value>(&serde_json::to_string(receipt).unwrap()).unwrap()
+        })
+        .filter(|receipt| receipt["actions"][0].get("Transfer").is_some())
+        .map(|receipt| receipt["receiver_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn owner_of(contract: &NFTContract, token_id: &str) -> String {
+    contract.nft_token(token_id.to_string()).unwrap().owner_id
+}
+
+#[test]
+fn batch_transfer_emits_one_grouped_event() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 3);
+
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(
+        vec![
+            (tokens[0].clone(), bob()),
+            (tokens[1].clone(), market()),
+            (tokens[2].clone(), bob()),
+        ],
+        Some("inventory".to_string()),
+    );
+
+    assert_eq!(owner_of(&contract, &tokens[0]), bob());
+    assert_eq!(owner_of(&contract, &tokens[1]), market());
+    assert_eq!(owner_of(&contract, &tokens[2]), bob());
+    assert_eq!(contract.nft_supply_for_owner(alice()).0, 0);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 2);
+
+    let events = nep171_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event"], "nft_transfer");
+    let data = events[0]["data"].as_array().unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0]["old_owner_id"], alice());
+    assert_eq!(data[0]["new_owner_id"], bob());
+    assert_eq!(
+        data[0]["token_ids"],
+        serde_json::json!([tokens[0].clone(), tokens[2].clone()])
+    );
+    assert_eq!(data[0]["memo"], "inventory");
+    assert!(data[0].get("authorized_id").is_none());
+    assert_eq!(data[1]["new_owner_id"], market());
+    assert_eq!(data[1]["token_ids"], serde_json::json!([tokens[1].clone()]));
+}
+
+#[test]
+fn approved_account_can_batch_transfer() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 2);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(tokens[0].clone(), market(), None);
+    contract.nft_approve(tokens[1].clone(), market(), None);
+
+    set_caller(market(), 1);
+    contract.nft_batch_transfer(
+        vec![(tokens[0].clone(), bob()), (tokens[1].clone(), bob())],
+        None,
+    );
+
+    assert_eq!(owner_of(&contract, &tokens[0]), bob());
+    assert_eq!(owner_of(&contract, &tokens[1]), bob());
+    // Approvals bị xoá khi token đổi owner
+    assert!(contract
+        .nft_token(tokens[0].clone())
+        .unwrap()
+        .approved_account_ids
+        .is_empty());
+
+    let events = nep171_events();
+    assert_eq!(events.len(), 1);
+    let data = events[0]["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["authorized_id"], market());
+    assert_eq!(data[0]["old_owner_id"], alice());
+}
+
+#[test]
+fn approved_batch_transfer_refunds_the_token_owner() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 2);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(tokens[0].clone(), market(), None);
+    contract.nft_approve(tokens[1].clone(), bob(), None);
+
+    // market chỉ được approve token 0, alice đã trả storage của cả 2 approvals
+    set_caller(market(), 1);
+    contract.nft_batch_transfer(vec![(tokens[0].clone(), bob())], None);
+    assert_eq!(refund_receivers(), vec![alice()]);
+
+    set_caller(bob(), 1);
+    contract.nft_batch_transfer(vec![(tokens[1].clone(), market())], None);
+    assert_eq!(refund_receivers(), vec![alice()]);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn batch_transfer_fails_if_any_token_is_not_owned() {
+    let mut contract = setup_contract();
+    let alice_tokens = mint(&mut contract, alice(), 1);
+    let bob_tokens = mint(&mut contract, bob(), 1);
+
+    // Token thứ 2 không thuộc alice -> Cả batch bị revert
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(
+        vec![
+            (alice_tokens[0].clone(), market()),
+            (bob_tokens[0].clone(), market()),
+        ],
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Duplicate tokens in batch transfer")]
+fn batch_transfer_rejects_duplicate_tokens() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(
+        vec![(tokens[0].clone(), bob()), (tokens[0].clone(), market())],
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Batch transfer must have at least 1 token")]
+fn batch_transfer_rejects_empty_batch() {
+    let mut contract = setup_contract();
+
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(vec![], None);
+}
+
+#[test]
+#[should_panic(expected = "Required attached deposit of exact 1 yoctoNear")]
+fn batch_transfer_requires_one_yocto() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 0);
+    contract.nft_batch_transfer(vec![(tokens[0].clone(), bob())], None);
+}
+
+#[test]
+#[should_panic(expected = "The transfer feature is paused")]
+fn batch_transfer_is_paused_with_transfers() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 1);
+
+    set_caller(owner(), 1);
+    contract.pause(vec![PauseFeature::Transfer], None);
+
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(vec![(tokens[0].clone(), bob())], None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid token id")]
+fn batch_transfer_rejects_non_numeric_token_id() {
+    let mut contract = setup_contract();
+    let tokens = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 1);
+    contract.nft_batch_transfer(
+        vec![(tokens[0].clone(), bob()), ("abc".to_string(), bob())],
+        None,
+    );
+}
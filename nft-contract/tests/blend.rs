@@ -0,0 +1,123 @@
+// Blend recipes: burn the ingredient NFTs -> mint the result NFTs
+mod common;
+
+use common::*;
+use nft_contract::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// Blend recipe: 2 NFTs of Template 0 -> 1 NFT of result_template_id
+fn create_blend(contract: &mut NFTContract, result_template_id: TemplateId, max_uses: Option<u32>) -> BlendId {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract
+        .create_blend(
+            "Game".to_string(),
+            vec![
+                BlendIngredient::Template { template_id: 0 },
+                BlendIngredient::Template { template_id: 0 },
+            ],
+            vec![result_template_id],
+            max_uses,
+            None,
+            None,
+        )
+        .blend_id
+}
+
+fn token_ids(ids: &[String]) -> Vec<TokenId> {
+    ids.iter().map(|token_id| token_id.parse().unwrap()).collect()
+}
+
+#[test]
+fn blend_burns_the_ingredients_and_mints_the_results() {
+    let mut contract = setup_contract();
+    let ingredients = token_ids(&mint(&mut contract, alice(), 2));
+    let sword = create_template(&mut contract, "Sword", 10);
+    let blend_id = create_blend(&mut contract, sword, None);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let minted = contract.blend(blend_id, ingredients.clone());
+
+    assert_eq!(minted.len(), 1);
+    assert_eq!(contract.nft_token_detail(minted[0]).unwrap().template_id, sword);
+    assert!(ingredients
+        .iter()
+        .all(|token_id| contract.nft_token_detail(*token_id).is_none()));
+    assert_eq!(contract.get_blend_by_id(blend_id).use_count, 1);
+}
+
+#[test]
+#[should_panic(expected = "This Blend has reached its max uses")]
+fn blend_stops_at_max_uses() {
+    let mut contract = setup_contract();
+    let ingredients = token_ids(&mint(&mut contract, alice(), 4));
+    let blend_id = create_blend(&mut contract, 0, Some(1));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.blend(blend_id, ingredients[..2].to_vec());
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.blend(blend_id, ingredients[2..].to_vec());
+}
+
+#[test]
+fn blend_in_a_frozen_collection_keeps_the_ingredients() {
+    let mut contract = setup_contract();
+    let ingredients = token_ids(&mint(&mut contract, alice(), 2));
+    let blend_id = create_blend(&mut contract, 0, None);
+
+    set_caller(owner(), 1);
+    contract.set_collection_frozen("Game".to_string(), true, None);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let result = catch_unwind(AssertUnwindSafe(|| contract.blend(blend_id, ingredients.clone())));
+
+    assert!(result.is_err());
+    assert!(ingredients
+        .iter()
+        .all(|token_id| contract.nft_token_detail(*token_id).is_some()));
+}
+
+#[test]
+fn blend_while_mint_is_paused_keeps_the_ingredients() {
+    let mut contract = setup_contract();
+    let ingredients = token_ids(&mint(&mut contract, alice(), 2));
+    let blend_id = create_blend(&mut contract, 0, None);
+
+    set_caller(owner(), 1);
+    contract.pause(vec![PauseFeature::Mint], None);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let result = catch_unwind(AssertUnwindSafe(|| contract.blend(blend_id, ingredients.clone())));
+
+    assert!(result.is_err());
+    assert!(ingredients
+        .iter()
+        .all(|token_id| contract.nft_token_detail(*token_id).is_some()));
+}
+
+#[test]
+fn blends_are_listed_per_collection() {
+    let mut contract = setup_contract();
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_collection(
+        "Other".to_string(),
+        0.05,
+        CollectionExtraData {
+            name: "Other".to_string(),
+            img: None,
+        },
+    );
+
+    create_blend(&mut contract, 0, None);
+    create_blend(&mut contract, 0, Some(5));
+
+    let page = contract.get_all_blends_by_collection("Game".to_string(), None, Some(1));
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].blend_id, 0);
+
+    let page = contract.get_all_blends_by_collection("Game".to_string(), page.next_cursor, Some(1));
+    assert_eq!(page.items[0].blend_id, 1);
+
+    let other = contract.get_all_blends_by_collection("Other".to_string(), None, None);
+    assert!(other.items.is_empty());
+    assert!(other.next_cursor.is_none());
+}
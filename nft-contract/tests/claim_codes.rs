@@ -0,0 +1,94 @@
+// Claim-code Drop Sales: the owner prepays the storage, anyone with a code claims for free
+mod common;
+
+use common::*;
+use near_sdk::env;
+use near_sdk::json_types::Base64VecU8;
+use nft_contract::*;
+
+fn code_hash(code: &str) -> Base64VecU8 {
+    Base64VecU8(env::sha256(code.as_bytes()))
+}
+
+// Claim-code Drop Sale 0 of Template 0 with the codes "code-0", "code-1", ...
+fn setup_claim_code_drop(codes: usize) -> NFTContract {
+    let mut contract = setup_contract();
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        "Game".to_string(),
+        DropSaleArgs {
+            max_supply: 100,
+            account_limit: 1,
+            claim_mode: Some(DropClaimMode::ClaimCode),
+            ..drop_args(vec![0], 0)
+        },
+    );
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.drop_add_claim_codes(
+        0,
+        (0..codes).map(|i| code_hash(&format!("code-{}", i))).collect(),
+    );
+
+    contract
+}
+
+#[test]
+fn every_code_can_be_claimed_by_a_new_account() {
+    let codes = 5;
+    let mut contract = setup_claim_code_drop(codes);
+
+    // Fresh receivers, up to the longest account id
+    let receivers = vec![
+        "a.testnet".to_string(),
+        alice(),
+        bob(),
+        format!("{}.testnet", "b".repeat(40)),
+        format!("{}.testnet", "c".repeat(56)),
+    ];
+    for (i, receiver_id) in receivers.into_iter().enumerate() {
+        set_caller(receiver_id.clone(), 0);
+        let receipt = contract.claim_drop_with_code(0, format!("code-{}", i), receiver_id.clone());
+        assert_eq!(receipt.claimed_tokens.len(), 1);
+        assert_eq!(contract.nft_supply_for_owner(receiver_id).0, 1);
+    }
+
+    assert_eq!(contract.get_drop_by_id(0).issued_supply, codes as u32);
+}
+
+#[test]
+fn codes_claimed_by_the_same_receiver_use_less_storage() {
+    let mut contract = setup_claim_code_drop(2);
+    let balance = contract.get_drop_by_id(0).storage_balance.0;
+
+    set_caller(alice(), 0);
+    let first = contract.claim_drop_with_code(0, "code-0".to_string(), alice());
+    set_caller(alice(), 0);
+    let second = contract.claim_drop_with_code(0, "code-1".to_string(), alice());
+
+    assert!(second.storage_cost.0 < first.storage_cost.0);
+    assert_eq!(
+        contract.get_drop_by_id(0).storage_balance.0,
+        balance - first.storage_cost.0 - second.storage_cost.0
+    );
+}
+
+#[test]
+#[should_panic(expected = "Claim code has already been used")]
+fn code_cannot_be_claimed_twice() {
+    let mut contract = setup_claim_code_drop(1);
+
+    set_caller(alice(), 0);
+    contract.claim_drop_with_code(0, "code-0".to_string(), alice());
+    set_caller(bob(), 0);
+    contract.claim_drop_with_code(0, "code-0".to_string(), bob());
+}
+
+#[test]
+#[should_panic(expected = "Invalid claim code")]
+fn unknown_code_is_rejected() {
+    let mut contract = setup_claim_code_drop(1);
+
+    set_caller(alice(), 0);
+    contract.claim_drop_with_code(0, "wrong".to_string(), alice());
+}
@@ -0,0 +1,76 @@
+// Collection-wide approvals (nft_approve_for_collection)
+mod common;
+
+use common::*;
+use nft_contract::{NonFungibleTokenApproval, NonFungibleTokenCore};
+
+#[test]
+fn collection_approval_authorizes_transfers_of_the_owner_tokens() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, owner(), 2);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_approve_for_collection("Game".to_string(), market(), None);
+    assert!(contract.nft_is_approved_for_collection("Game".to_string(), market(), Some(0)));
+
+    set_caller(market(), 1);
+    contract.nft_transfer(alice(), token_ids[0].clone(), Some(0), None);
+    contract.nft_transfer(bob(), token_ids[1].clone(), None, None);
+
+    assert_eq!(
+        contract.nft_token(token_ids[0].clone()).unwrap().owner_id,
+        alice()
+    );
+    assert_eq!(
+        contract.nft_token(token_ids[1].clone()).unwrap().owner_id,
+        bob()
+    );
+}
+
+#[test]
+#[should_panic(expected = "The actual approval id 0 is different from the given approval id 1")]
+fn collection_approval_checks_approval_id() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, owner(), 1);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_approve_for_collection("Game".to_string(), market(), None);
+
+    set_caller(market(), 1);
+    contract.nft_transfer(alice(), token_ids[0].clone(), Some(1), None);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn collection_approval_does_not_cover_other_holders() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_approve_for_collection("Game".to_string(), market(), None);
+
+    set_caller(market(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn revoked_collection_approval_cannot_transfer() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, owner(), 1);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_approve_for_collection("Game".to_string(), market(), None);
+    contract.nft_approve_for_collection("Game".to_string(), bob(), None);
+
+    set_caller(owner(), 1);
+    contract.nft_revoke_for_collection("Game".to_string(), bob());
+    assert!(!contract.nft_is_approved_for_collection("Game".to_string(), bob(), None));
+    assert!(contract.nft_is_approved_for_collection("Game".to_string(), market(), None));
+
+    contract.nft_revoke_all_for_collection("Game".to_string());
+    assert!(!contract.nft_is_approved_for_collection("Game".to_string(), market(), None));
+
+    set_caller(market(), 1);
+    contract.nft_transfer(alice(), token_ids[0].clone(), None, None);
+}
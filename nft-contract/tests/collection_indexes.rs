@@ -0,0 +1,171 @@
+// Per-Collection indexes of Schemas, Templates, Lootboxes and Drop Sales
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+fn immutable_data(name: &str) -> ImmutableData {
+    ImmutableData {
+        name: name.to_string(),
+        img: None,
+        rarity: None,
+        extra_immutable_data: None,
+        img_hash: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+fn create_drop(
+    contract: &mut NFTContract,
+    collection_name: &str,
+    template_id: TemplateId,
+) -> DropSale {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        collection_name.to_string(),
+        DropSaleArgs {
+            account_limit: 1,
+            ..drop_args(vec![template_id], 1_000)
+        },
+    )
+}
+
+// Collections "Game" and "Art" with interleaved Schemas, Templates, Lootboxes and Drop Sales
+fn setup_two_collections() -> NFTContract {
+    let mut contract = setup_contract();
+    contract.create_collection(
+        "Art".to_string(),
+        0.05,
+        CollectionExtraData {
+            name: "Art".to_string(),
+            img: None,
+        },
+    );
+    contract.create_schema("Art".to_string(), "Painting".to_string(), vec![]);
+    contract.create_schema("Game".to_string(), "Armor".to_string(), vec![]);
+    contract.create_template(
+        "Art".to_string(),
+        TemplateArgs {
+            schema_id: 1,
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            immutable_data: immutable_data("Mona"),
+            validity_duration: None,
+        },
+    );
+    contract.create_template(
+        "Game".to_string(),
+        TemplateArgs {
+            schema_id: 2,
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            immutable_data: immutable_data("Shield"),
+            validity_duration: None,
+        },
+    );
+    contract.create_lootbox(
+        "Game".to_string(),
+        0,
+        true,
+        true,
+        10,
+        immutable_data("Chest"),
+        None,
+        Some(vec![Slot {
+            total_odds: 100,
+            outcomes: vec![Outcome {
+                template_id: 0,
+                odds: 100,
+            }],
+        }]),
+    );
+    create_drop(&mut contract, "Art", 1);
+    create_drop(&mut contract, "Game", 0);
+    create_drop(&mut contract, "Game", 2);
+
+    contract
+}
+
+#[test]
+fn supply_by_collection_uses_the_indexes() {
+    let contract = setup_two_collections();
+
+    assert_eq!(
+        contract.schema_supply_by_collection("Game".to_string()).0,
+        2
+    );
+    assert_eq!(contract.schema_supply_by_collection("Art".to_string()).0, 1);
+    // Lootboxes are Templates too
+    assert_eq!(
+        contract.template_supply_by_collection("Game".to_string()).0,
+        3
+    );
+    assert_eq!(
+        contract.template_supply_by_collection("Art".to_string()).0,
+        1
+    );
+    assert_eq!(
+        contract.lootbox_supply_by_collection("Game".to_string()).0,
+        1
+    );
+    assert_eq!(
+        contract.lootbox_supply_by_collection("Art".to_string()).0,
+        0
+    );
+    assert_eq!(contract.lootbox_total_supply().0, 1);
+    assert_eq!(contract.drop_supply_by_collection("Game".to_string()).0, 2);
+    assert_eq!(contract.drop_supply_by_collection("Art".to_string()).0, 1);
+}
+
+#[test]
+fn views_by_collection_paginate_correctly() {
+    let contract = setup_two_collections();
+
+    let schema_ids: Vec<SchemaId> = contract
+        .get_all_schemas_by_collection("Game".to_string(), Some("0".to_string()), Some(1))
+        .items
+        .iter()
+        .map(|schema| schema.schema_id)
+        .collect();
+    assert_eq!(schema_ids, vec![2]);
+
+    let template_ids: Vec<TemplateId> = contract
+        .get_all_templates_by_collection("Game".to_string(), Some("0".to_string()), Some(10))
+        .items
+        .iter()
+        .map(|template| template.template_id)
+        .collect();
+    assert_eq!(template_ids, vec![2, 3]);
+
+    let drop_ids: Vec<DropId> = contract
+        .get_all_drops_by_collection("Game".to_string(), None, Some(1))
+        .items
+        .iter()
+        .map(|drop| drop.drop_id)
+        .collect();
+    assert_eq!(drop_ids, vec![1]);
+
+    let lootboxes = contract
+        .get_all_lootboxes_by_collection("Game".to_string(), None, None)
+        .items;
+    assert_eq!(lootboxes.len(), 1);
+    assert!(lootboxes[0].is_lootbox);
+    assert_eq!(contract.get_all_lootboxes(None, None).items.len(), 1);
+}
+
+#[test]
+fn cancel_drop_removes_it_from_the_index() {
+    let mut contract = setup_two_collections();
+
+    set_caller(owner(), 1);
+    contract.cancel_drop(1);
+
+    assert_eq!(contract.drop_supply_by_collection("Game".to_string()).0, 1);
+    let drops = contract
+        .get_all_drops_by_collection("Game".to_string(), None, None)
+        .items;
+    assert_eq!(drops[0].drop_id, 2);
+}
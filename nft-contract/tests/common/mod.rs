@@ -0,0 +1,158 @@
+// Shared setup of the NEP conformance tests: 1 Collection, 1 Schema, 1 Template
+#![allow(dead_code)]
+
+use near_sdk::json_types::{Base64VecU8, ValidAccountId, U128};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{env, testing_env, AccountId, Balance, MockedBlockchain};
+use nft_contract::*;
+use std::convert::TryFrom;
+
+pub const STORAGE_DEPOSIT: Balance = 10_000_000_000_000_000_000_000_000; // 10 NEAR
+
+pub fn owner() -> AccountId {
+    "owner.testnet".to_string()
+}
+
+pub fn alice() -> AccountId {
+    "alice.testnet".to_string()
+}
+
+pub fn bob() -> AccountId {
+    "bob.testnet".to_string()
+}
+
+pub fn market() -> AccountId {
+    "market.testnet".to_string()
+}
+
+// Set the caller and the attached deposit of the next calls
+pub fn set_caller(account_id: AccountId, attached_deposit: Balance) {
+    set_caller_at(account_id, attached_deposit, 0);
+}
+
+// Same as set_caller, at a block time in milliseconds
+pub fn set_caller_at(account_id: AccountId, attached_deposit: Balance, time_ms: u64) {
+    let account_id = ValidAccountId::try_from(account_id).unwrap();
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(ValidAccountId::try_from("nft.testnet").unwrap())
+        .signer_account_id(account_id.clone())
+        .predecessor_account_id(account_id)
+        .attached_deposit(attached_deposit)
+        .block_timestamp(time_ms * 1_000_000);
+    testing_env!(context.build());
+}
+
+// Base64-encoded sha256 hash of some content (media_hash, reference_hash)
+pub fn content_hash(content: &str) -> Base64VecU8 {
+    Base64VecU8(env::sha256(content.as_bytes()))
+}
+
+// New Contract with the Collection "Game", Schema 0 and Template 0
+pub fn setup_contract() -> NFTContract {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let mut contract = NFTContract::new_default_metadata(owner());
+
+    contract.create_collection(
+        "Game".to_string(),
+        0.05,
+        CollectionExtraData {
+            name: "Game".to_string(),
+            img: None,
+        },
+    );
+    contract.create_schema(
+        "Game".to_string(),
+        "Weapon".to_string(),
+        vec![SchemaFormat {
+            attribute_name: "attack".to_string(),
+            attribute_type: "string".to_string(),
+        }],
+    );
+    contract.create_template(
+        "Game".to_string(),
+        TemplateArgs {
+            schema_id: 0,
+            transferable: true,
+            burnable: true,
+            max_supply: 100,
+            immutable_data: ImmutableData {
+                name: "Lightsaber".to_string(),
+                img: Some("https://nearhub.club/lightsaber.png".to_string()),
+                rarity: Some("uncommon".to_string()),
+                extra_immutable_data: Some("{\"attack\": \"10\"}".to_string()),
+                img_hash: Some(content_hash("lightsaber.png")),
+                reference: None,
+                reference_hash: None,
+            },
+            validity_duration: None,
+        },
+    );
+
+    contract
+}
+
+// New Template of Schema 0 in the Collection "Game"
+pub fn create_template(contract: &mut NFTContract, name: &str, max_supply: u32) -> TemplateId {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract
+        .create_template(
+            "Game".to_string(),
+            TemplateArgs {
+                schema_id: 0,
+                transferable: true,
+                burnable: true,
+                max_supply,
+                immutable_data: ImmutableData {
+                    name: name.to_string(),
+                    img: None,
+                    rarity: None,
+                    extra_immutable_data: None,
+                    img_hash: None,
+                    reference: None,
+                    reference_hash: None,
+                },
+                validity_duration: None,
+            },
+        )
+        .template_id
+}
+
+// Mint `mint_number` NFTs of Template 0 to receiver_id, return their string token ids
+pub fn mint(contract: &mut NFTContract, receiver_id: AccountId, mint_number: u32) -> Vec<String> {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let before = contract.nft_supply_for_owner(receiver_id.clone());
+    contract.nft_mint(
+        "Game".to_string(),
+        0,
+        0,
+        Some(mint_number),
+        receiver_id.clone(),
+    );
+
+    contract
+        .nft_tokens_for_owner(receiver_id, Some(before), Some(mint_number as u64))
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect()
+}
+
+// Args of a public fixed-price Drop Sale of the Collection "Game", tests override the fields they need
+pub fn drop_args(template_ids: Vec<TemplateId>, price: Balance) -> DropSaleArgs {
+    DropSaleArgs {
+        template_ids,
+        price: U128(price),
+        price_type: "NEAR".to_string(),
+        is_public: true,
+        max_supply: 10,
+        account_limit: 10,
+        account_limit_cooldown: 0,
+        start_time: 0,
+        end_time: 0,
+        display_data: None,
+        pricing_mode: None,
+        distribution_mode: None,
+        claim_mode: None,
+        access_rule: None,
+    }
+}
@@ -0,0 +1,137 @@
+// drop_claim_cost: the quote is the amount that claim_drop charges (price + storage)
+mod common;
+
+use common::*;
+use near_sdk::json_types::U128;
+use nft_contract::*;
+
+const PRICE: u128 = 1_000;
+
+fn create_drop(contract: &mut NFTContract, args: DropSaleArgs) {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop("Game".to_string(), args);
+}
+
+// Claim and return (quote before the claim, amount charged by the claim)
+fn quote_and_claim(contract: &mut NFTContract, account_id: String, claim_amount: u32) -> (u128, u128) {
+    set_caller(account_id.clone(), STORAGE_DEPOSIT);
+    let quote = contract.drop_claim_cost(0, claim_amount, Some(account_id)).0;
+    let receipt = contract.claim_drop(0, claim_amount);
+    let charged = receipt.unit_price.0 * claim_amount as u128 + receipt.storage_cost.0;
+    (quote, charged)
+}
+
+#[test]
+fn quote_matches_the_first_claim_of_a_new_owner() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], PRICE));
+
+    // First NFT of the Template, the Collection and the owner
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 1);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_matches_the_claims_of_an_existing_holder() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 1);
+    create_drop(&mut contract, drop_args(vec![0], PRICE));
+
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 3);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_matches_the_claim_of_a_long_account_id() {
+    let mut contract = setup_contract();
+    mint(&mut contract, bob(), 1);
+    create_drop(&mut contract, drop_args(vec![0], PRICE));
+
+    let account_id = format!("{}.testnet", "a".repeat(56));
+    let (quote, charged) = quote_and_claim(&mut contract, account_id, 2);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_matches_a_claim_of_all_templates() {
+    let mut contract = setup_contract();
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let sword = contract.create_template(
+        "Game".to_string(),
+        TemplateArgs {
+            schema_id: 0,
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            immutable_data: ImmutableData {
+                name: "Sword".to_string(),
+                img: None,
+                rarity: None,
+                extra_immutable_data: Some("{\"attack\": \"100\"}".to_string()),
+                img_hash: None,
+                reference: None,
+                reference_hash: None,
+            },
+            validity_duration: Some(86_400_000),
+        },
+    );
+    create_drop(&mut contract, drop_args(vec![0, sword.template_id], PRICE));
+
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 2);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_matches_the_claims_of_a_rebate_auction() {
+    let mut contract = setup_contract();
+    create_drop(
+        &mut contract,
+        DropSaleArgs {
+            pricing_mode: Some(DropPricingMode::LinearDutchAuction {
+                floor_price: U128(PRICE / 2),
+                start_time: 0,
+                end_time: 1_000,
+                rebate: true,
+            }),
+            ..drop_args(vec![0], PRICE)
+        },
+    );
+
+    // First claim also stores the payment of the claimer, the next ones update it
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 1);
+    assert_eq!(quote, charged);
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 2);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_matches_a_claim_of_a_single_use_token_gated_drop() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 4);
+    create_drop(
+        &mut contract,
+        DropSaleArgs {
+            access_rule: Some(DropAccessRule {
+                requirement: DropTokenRequirement::Template { template_id: 0 },
+                min_amount: 2,
+                is_single_use: true,
+            }),
+            ..drop_args(vec![0], PRICE)
+        },
+    );
+
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 1);
+    assert_eq!(quote, charged);
+    let (quote, charged) = quote_and_claim(&mut contract, alice(), 1);
+    assert_eq!(quote, charged);
+}
+
+#[test]
+fn quote_without_account_covers_any_claimer() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], PRICE));
+
+    let quote = contract.drop_claim_cost(0, 2, None).0;
+    let (_, charged) = quote_and_claim(&mut contract, alice(), 2);
+    assert!(quote >= charged);
+}
@@ -0,0 +1,155 @@
+// Drop Sale lifecycle: update, pause / resume, cancel
+mod common;
+
+use common::*;
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::{self, json};
+use nft_contract::*;
+
+fn create_drop(contract: &mut NFTContract, args: DropSaleArgs) -> DropSale {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop("Game".to_string(), args)
+}
+
+fn holder_rule() -> DropAccessRule {
+    DropAccessRule {
+        requirement: DropTokenRequirement::Template { template_id: 0 },
+        min_amount: 1,
+        is_single_use: false,
+    }
+}
+
+#[test]
+fn update_drop_changes_only_the_passed_fields() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let drop = contract.update_drop(
+        0,
+        DropSaleUpdateArgs {
+            price: Some(U128(2_000)),
+            max_supply: Some(20),
+            display_data: Some("Game Drop v2".to_string()),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(drop.price.0, 2_000);
+    assert_eq!(drop.max_supply, 20);
+    assert_eq!(drop.display_data, Some("Game Drop v2".to_string()));
+    assert_eq!(drop.account_limit, 10);
+    assert_eq!(contract.get_drop_by_id(0).price.0, 2_000);
+}
+
+#[test]
+#[should_panic(expected = "Cannot change price or templates after the Drop Sale has been claimed")]
+fn update_drop_locks_the_price_after_the_first_claim() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+
+    set_caller(owner(), 1);
+    contract.update_drop(
+        0,
+        DropSaleUpdateArgs {
+            price: Some(U128(1)),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn update_drop_clears_the_access_rule_with_null() {
+    let mut contract = setup_contract();
+    create_drop(
+        &mut contract,
+        DropSaleArgs {
+            access_rule: Some(holder_rule()),
+            ..drop_args(vec![0], 1_000)
+        },
+    );
+
+    // Missing access_rule -> unchanged
+    let unchanged: DropSaleUpdateArgs = serde_json::from_value(json!({ "max_supply": 5 })).unwrap();
+    set_caller(owner(), 1);
+    assert!(contract.update_drop(0, unchanged).access_rule.is_some());
+
+    // access_rule: null -> removed, anyone can claim again
+    let cleared: DropSaleUpdateArgs = serde_json::from_value(json!({ "access_rule": null })).unwrap();
+    set_caller(owner(), 1);
+    assert!(contract.update_drop(0, cleared).access_rule.is_none());
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+    assert_eq!(contract.nft_supply_for_owner(alice()).0, 1);
+}
+
+#[test]
+#[should_panic(expected = "This Drop Sale is paused")]
+fn paused_drop_cannot_be_claimed() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(owner(), 1);
+    contract.pause_drop(0);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
+
+#[test]
+fn resumed_drop_can_be_claimed() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(owner(), 1);
+    contract.pause_drop(0);
+    contract.resume_drop(0);
+    assert!(!contract.get_drop_by_id(0).is_paused);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+    assert_eq!(contract.get_drop_by_id(0).issued_supply, 1);
+}
+
+#[test]
+fn owner_can_pause_and_cancel_while_drops_are_paused() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(owner(), 1);
+    contract.pause(vec![PauseFeature::Drop], Some("Incident".to_string()));
+
+    contract.pause_drop(0);
+    contract.resume_drop(0);
+    contract.cancel_drop(1);
+
+    assert_eq!(contract.drop_total_supply().0, 1);
+    assert_eq!(contract.drop_supply_by_collection("Game".to_string()).0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Drop does not exist")]
+fn cancel_drop_deletes_the_drop() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(owner(), 1);
+    contract.cancel_drop(0);
+
+    contract.get_drop_by_id(0);
+}
+
+#[test]
+#[should_panic(expected = "Only owner of the Drop Sale can cancel it")]
+fn only_owner_can_cancel_a_drop() {
+    let mut contract = setup_contract();
+    create_drop(&mut contract, drop_args(vec![0], 1_000));
+
+    set_caller(alice(), 1);
+    contract.cancel_drop(0);
+}
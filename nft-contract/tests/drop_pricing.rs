@@ -0,0 +1,180 @@
+// Dutch auction pricing of Drop Sales: linear, stepped and rebate
+mod common;
+
+use common::*;
+use near_sdk::json_types::U128;
+use nft_contract::*;
+
+const NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+const HOUR: u64 = 3_600_000; // milliseconds
+
+fn linear(floor_price: u128, start_time: u64, end_time: u64, rebate: bool) -> DropPricingMode {
+    DropPricingMode::LinearDutchAuction {
+        floor_price: U128(floor_price),
+        start_time,
+        end_time,
+        rebate,
+    }
+}
+
+fn create_auction(contract: &mut NFTContract, price: u128, pricing_mode: DropPricingMode, max_supply: u32) {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        "Game".to_string(),
+        DropSaleArgs {
+            max_supply,
+            pricing_mode: Some(pricing_mode),
+            ..drop_args(vec![0], price)
+        },
+    );
+}
+
+fn price_at(contract: &NFTContract, time_ms: u64) -> u128 {
+    set_caller_at(alice(), 0, time_ms);
+    contract.drop_current_price(0).0
+}
+
+#[test]
+fn linear_auction_falls_to_the_floor() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 1_000, 9_000, false), 10);
+
+    assert_eq!(price_at(&contract, 0), 1_000);
+    assert_eq!(price_at(&contract, 1_000), 1_000);
+    assert_eq!(price_at(&contract, 3_000), 800);
+    assert_eq!(price_at(&contract, 5_000), 600);
+    assert_eq!(price_at(&contract, 9_000), 200);
+    assert_eq!(price_at(&contract, 100_000), 200);
+}
+
+#[test]
+fn linear_auction_with_large_prices_does_not_overflow() {
+    // 5 NEAR -> 1 NEAR over 24 hours
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 5 * NEAR, linear(NEAR, 0, 24 * HOUR, false), 10);
+
+    assert_eq!(price_at(&contract, 12 * HOUR), 3 * NEAR);
+    assert_eq!(price_at(&contract, 18 * HOUR), 2 * NEAR);
+
+    let expected = 5 * NEAR - 4 * NEAR * 22 / 24; // No overflow in the test: 4 NEAR * 22 fits u128
+    assert_eq!(price_at(&contract, 22 * HOUR), expected);
+}
+
+#[test]
+fn stepped_auction_drops_once_per_step() {
+    let mut contract = setup_contract();
+    create_auction(
+        &mut contract,
+        1_000,
+        DropPricingMode::SteppedDutchAuction {
+            floor_price: U128(200),
+            start_time: 0,
+            end_time: 4 * HOUR,
+            step_duration: HOUR,
+            rebate: false,
+        },
+        10,
+    );
+
+    assert_eq!(price_at(&contract, 0), 1_000);
+    assert_eq!(price_at(&contract, HOUR - 1), 1_000);
+    assert_eq!(price_at(&contract, HOUR), 800);
+    assert_eq!(price_at(&contract, 2 * HOUR + HOUR / 2), 600);
+    assert_eq!(price_at(&contract, 4 * HOUR), 200);
+    assert_eq!(price_at(&contract, 10 * HOUR), 200);
+}
+
+#[test]
+fn claim_drop_charges_the_current_auction_price() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, false), 10);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 4_000);
+    let receipt = contract.claim_drop(0, 2);
+
+    assert_eq!(receipt.unit_price.0, 600);
+    assert_eq!(contract.get_drop_by_id(0).last_claim_price.0, 600);
+}
+
+#[test]
+fn rebate_after_the_auction_ends_uses_the_floor_price() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, true), 10);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.claim_drop(0, 2); // 2 x 1000
+    set_caller_at(bob(), STORAGE_DEPOSIT, 4_000);
+    contract.claim_drop(0, 1); // 1 x 600
+
+    set_caller_at(alice(), 0, 8_000);
+    assert_eq!(contract.claim_rebate(0).0, 2 * (1_000 - 200));
+    set_caller_at(bob(), 0, 8_000);
+    assert_eq!(contract.claim_rebate(0).0, 600 - 200);
+}
+
+#[test]
+fn rebate_when_sold_out_uses_the_last_claim_price() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, true), 2);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.claim_drop(0, 1); // 1000
+    set_caller_at(bob(), STORAGE_DEPOSIT, 4_000);
+    contract.claim_drop(0, 1); // 600 -> sold out
+
+    // Before end_time, but the auction is sold out
+    set_caller_at(alice(), 0, 5_000);
+    assert_eq!(contract.claim_rebate(0).0, 1_000 - 600);
+}
+
+#[test]
+#[should_panic(expected = "The auction of this Drop Sale has not finished yet")]
+fn rebate_before_the_end_of_the_auction_fails() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, true), 10);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.claim_drop(0, 1);
+
+    set_caller_at(alice(), 0, 7_999);
+    contract.claim_rebate(0);
+}
+
+#[test]
+#[should_panic(expected = "Cannot lower the max supply of a Dutch auction Drop Sale after it has been claimed")]
+fn auction_max_supply_cannot_be_lowered_after_a_claim() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, true), 10);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.claim_drop(0, 1);
+
+    // Lowering the supply to 1 would make the auction "sold out" at 1000
+    set_caller(owner(), 1);
+    contract.update_drop(
+        0,
+        DropSaleUpdateArgs {
+            max_supply: Some(1),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn auction_max_supply_can_still_be_raised_after_a_claim() {
+    let mut contract = setup_contract();
+    create_auction(&mut contract, 1_000, linear(200, 0, 8_000, true), 10);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.claim_drop(0, 1);
+
+    set_caller(owner(), 1);
+    let drop = contract.update_drop(
+        0,
+        DropSaleUpdateArgs {
+            max_supply: Some(20),
+            ..Default::default()
+        },
+    );
+    assert_eq!(drop.max_supply, 20);
+}
@@ -0,0 +1,369 @@
+// `nearhub` NEP-297 events: serialization against nearhub-events.schema.json + emission
+mod common;
+
+use common::*;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::get_logs;
+use nft_contract::*;
+
+const SCHEMA: &str = include_str!("../nearhub-events.schema.json");
+
+// Parse an `EVENT_JSON:` log
+fn parse_event(log: &str) -> Value {
+    let json = log
+        .strip_prefix("EVENT_JSON:")
+        .expect("Log is not an event");
+    serde_json::from_str(json).unwrap()
+}
+
+fn nearhub_log(event: EventLogVariant) -> Value {
+    let log = EventLog {
+        standard: "nearhub".to_string(),
+        version: "1.0.0".to_string(),
+        event,
+    };
+    parse_event(&log.to_string())
+}
+
+fn json_type_matches(value: &Value, json_type: &str) -> bool {
+    match json_type {
+        "string" => value.is_string(),
+        "integer" => value.is_u64(),
+        "array" => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(Value::is_string)),
+        _ => false,
+    }
+}
+
+// Check an event against its entry in the JSON schema
+fn assert_matches_schema(event: &Value) {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    assert_eq!(event["standard"], schema["properties"]["standard"]["const"]);
+    assert_eq!(event["version"], schema["properties"]["version"]["const"]);
+
+    let name = event["event"].as_str().unwrap();
+    let entry = schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["properties"]["event"]["const"] == name)
+        .unwrap_or_else(|| panic!("Event {} is not in the schema", name));
+    let item_schema = &entry["properties"]["data"]["items"];
+
+    let data = event["data"].as_array().unwrap();
+    assert!(!data.is_empty());
+    for item in data {
+        let item = item.as_object().unwrap();
+        for field in item_schema["required"].as_array().unwrap() {
+            let field = field.as_str().unwrap();
+            assert!(item.contains_key(field), "{}: missing {}", name, field);
+        }
+        for (field, value) in item {
+            let property = &item_schema["properties"][field];
+            assert!(!property.is_null(), "{}: unknown field {}", name, field);
+            assert!(
+                json_type_matches(value, property["type"].as_str().unwrap()),
+                "{}: wrong type of {}",
+                name,
+                field
+            );
+        }
+    }
+}
+
+// `nearhub` events logged by the last call
+fn nearhub_events() -> Vec<Value> {
+    get_logs()
+        .iter()
+        .filter(|log| log.starts_with("EVENT_JSON:"))
+        .map(|log| parse_event(log))
+        .filter(|event| event["standard"] == "nearhub")
+        .collect()
+}
+
+fn last_event(name: &str) -> Value {
+    let event = nearhub_events()
+        .pop()
+        .unwrap_or_else(|| panic!("No {} event", name));
+    assert_eq!(event["event"], name);
+    assert_matches_schema(&event);
+    event
+}
+
+fn template_create_log() -> TemplateCreateLog {
+    TemplateCreateLog {
+        owner_id: owner(),
+        collection_name: "Game".to_string(),
+        schema_id: "0".to_string(),
+        template_id: "1".to_string(),
+        max_supply: 10,
+        memo: None,
+    }
+}
+
+fn create_private_drop(contract: &mut NFTContract) -> DropSale {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        "Game".to_string(),
+        DropSaleArgs {
+            is_public: false,
+            account_limit: 5,
+            ..drop_args(vec![0], 1_000)
+        },
+    )
+}
+
+#[test]
+fn collection_create_serializes() {
+    let event = nearhub_log(EventLogVariant::CollectionCreate(vec![
+        CollectionCreateLog {
+            owner_id: owner(),
+            collection_id: "0".to_string(),
+            collection_name: "Game".to_string(),
+            memo: None,
+        },
+    ]));
+
+    assert_eq!(
+        event,
+        serde_json::json!({
+            "standard": "nearhub",
+            "version": "1.0.0",
+            "event": "collection_create",
+            "data": [{"owner_id": "owner.testnet", "collection_id": "0", "collection_name": "Game"}]
+        })
+    );
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn schema_create_serializes() {
+    let event = nearhub_log(EventLogVariant::SchemaCreate(vec![SchemaCreateLog {
+        owner_id: owner(),
+        collection_name: "Game".to_string(),
+        schema_id: "0".to_string(),
+        schema_name: "Weapon".to_string(),
+        memo: Some("first schema".to_string()),
+    }]));
+
+    assert_eq!(event["event"], "schema_create");
+    assert_eq!(event["data"][0]["memo"], "first schema");
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn template_and_lootbox_create_serialize() {
+    let event = nearhub_log(EventLogVariant::TemplateCreate(vec![template_create_log()]));
+    assert_eq!(event["event"], "template_create");
+    assert_eq!(event["data"][0]["max_supply"], 10);
+    assert_matches_schema(&event);
+
+    let event = nearhub_log(EventLogVariant::LootboxCreate(vec![template_create_log()]));
+    assert_eq!(event["event"], "lootbox_create");
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn drop_create_serializes() {
+    let event = nearhub_log(EventLogVariant::DropCreate(vec![DropCreateLog {
+        drop_id: "0".to_string(),
+        owner_id: owner(),
+        collection_name: "Game".to_string(),
+        template_ids: vec!["0".to_string(), "1".to_string()],
+        price: "1000000000000000000000000".to_string(),
+        memo: None,
+    }]));
+
+    assert_eq!(event["event"], "drop_create");
+    // Balances are strings, like U128
+    assert_eq!(event["data"][0]["price"], "1000000000000000000000000");
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn drop_whitelist_events_serialize() {
+    let whitelist_log = || DropWhitelistLog {
+        drop_id: "0".to_string(),
+        owner_id: owner(),
+        account_ids: vec![alice(), bob()],
+        memo: None,
+    };
+
+    let event = nearhub_log(EventLogVariant::DropWhitelistAdd(vec![whitelist_log()]));
+    assert_eq!(event["event"], "drop_whitelist_add");
+    assert_matches_schema(&event);
+
+    let event = nearhub_log(EventLogVariant::DropWhitelistRemove(vec![whitelist_log()]));
+    assert_eq!(event["event"], "drop_whitelist_remove");
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn drop_claim_serializes() {
+    let event = nearhub_log(EventLogVariant::DropClaim(vec![DropClaimLog {
+        drop_id: "0".to_string(),
+        owner_id: alice(),
+        token_ids: vec!["1".to_string(), "2".to_string()],
+        unit_price: "1000".to_string(),
+        memo: None,
+    }]));
+
+    assert_eq!(event["event"], "drop_claim");
+    assert_matches_schema(&event);
+}
+
+#[test]
+fn older_nearhub_events_match_the_schema() {
+    let drop_log = || DropLog {
+        drop_id: "0".to_string(),
+        owner_id: owner(),
+        memo: None,
+    };
+    let pause_log = || ContractPauseLog {
+        account_id: owner(),
+        features: vec!["drop".to_string()],
+        memo: None,
+    };
+
+    let events = vec![
+        EventLogVariant::DropUpdate(vec![drop_log()]),
+        EventLogVariant::DropPause(vec![drop_log()]),
+        EventLogVariant::DropResume(vec![drop_log()]),
+        EventLogVariant::DropCancel(vec![drop_log()]),
+        EventLogVariant::BlendCreate(vec![BlendLog {
+            blend_id: "0".to_string(),
+            owner_id: owner(),
+            collection_name: "Game".to_string(),
+            memo: None,
+        }]),
+        EventLogVariant::NftBlend(vec![NftBlendLog {
+            blend_id: "0".to_string(),
+            owner_id: alice(),
+            burned_token_ids: vec!["1".to_string()],
+            minted_token_ids: vec!["2".to_string()],
+            memo: None,
+        }]),
+        EventLogVariant::NftUpgrade(vec![NftUpgradeLog {
+            owner_id: alice(),
+            token_id: "1".to_string(),
+            rule_id: "0".to_string(),
+            from_template_id: "0".to_string(),
+            to_template_id: "1".to_string(),
+            burned_token_ids: vec![],
+            memo: None,
+        }]),
+        EventLogVariant::ContractPause(vec![pause_log()]),
+        EventLogVariant::ContractUnpause(vec![pause_log()]),
+        EventLogVariant::CollectionModerate(vec![CollectionModerateLog {
+            moderator_id: owner(),
+            collection_name: "Game".to_string(),
+            action: "verify".to_string(),
+            reason: Some("Official".to_string()),
+        }]),
+    ];
+    for event in events {
+        assert_matches_schema(&nearhub_log(event));
+    }
+}
+
+#[test]
+fn create_actions_emit_events() {
+    // setup_contract() logs collection_create, schema_create, template_create
+    let mut contract = setup_contract();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_collection(
+        "Art".to_string(),
+        0.05,
+        CollectionExtraData {
+            name: "Art".to_string(),
+            img: None,
+        },
+    );
+    let event = last_event("collection_create");
+    assert_eq!(event["data"][0]["collection_name"], "Art");
+    assert_eq!(event["data"][0]["owner_id"], "owner.testnet");
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_schema("Art".to_string(), "Painting".to_string(), vec![]);
+    let event = last_event("schema_create");
+    assert_eq!(event["data"][0]["schema_name"], "Painting");
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let lootbox = contract.create_lootbox(
+        "Game".to_string(),
+        0,
+        true,
+        true,
+        10,
+        ImmutableData {
+            name: "Chest".to_string(),
+            img: None,
+            rarity: None,
+            extra_immutable_data: None,
+            img_hash: None,
+            reference: None,
+            reference_hash: None,
+        },
+        None,
+        Some(vec![Slot {
+            total_odds: 100,
+            outcomes: vec![Outcome {
+                template_id: 0,
+                odds: 100,
+            }],
+        }]),
+    );
+    let event = last_event("lootbox_create");
+    assert_eq!(
+        event["data"][0]["template_id"],
+        lootbox.template_id.to_string()
+    );
+    assert_eq!(event["data"][0]["max_supply"], 10);
+}
+
+#[test]
+fn drop_actions_emit_events() {
+    let mut contract = setup_contract();
+
+    let drop = create_private_drop(&mut contract);
+    let event = last_event("drop_create");
+    assert_eq!(event["data"][0]["drop_id"], drop.drop_id.to_string());
+    assert_eq!(event["data"][0]["template_ids"], serde_json::json!(["0"]));
+    assert_eq!(event["data"][0]["price"], "1000");
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.drop_add_whitelist_account(drop.drop_id, vec![alice(), bob()]);
+    let event = last_event("drop_whitelist_add");
+    assert_eq!(
+        event["data"][0]["account_ids"],
+        serde_json::json!(["alice.testnet", "bob.testnet"])
+    );
+
+    set_caller(owner(), 1);
+    contract.drop_revoke(drop.drop_id, bob());
+    let event = last_event("drop_whitelist_remove");
+    assert_eq!(
+        event["data"][0]["account_ids"],
+        serde_json::json!(["bob.testnet"])
+    );
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let receipt = contract.claim_drop(drop.drop_id, 2);
+    let event = last_event("drop_claim");
+    assert_eq!(event["data"][0]["owner_id"], "alice.testnet");
+    assert_eq!(event["data"][0]["unit_price"], "1000");
+    assert_eq!(
+        event["data"][0]["token_ids"].as_array().unwrap().len(),
+        receipt.claimed_tokens.len()
+    );
+
+    set_caller(owner(), 1);
+    contract.drop_revoke_all(drop.drop_id);
+    let event = last_event("drop_whitelist_remove");
+    assert_eq!(
+        event["data"][0]["account_ids"],
+        serde_json::json!(["alice.testnet"])
+    );
+}
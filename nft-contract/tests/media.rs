@@ -0,0 +1,151 @@
+// Media / reference links and their sha256 hashes in the token metadata
+mod common;
+
+use common::*;
+use near_sdk::json_types::Base64VecU8;
+use nft_contract::*;
+
+fn immutable_data(img: Option<&str>, img_hash: Option<Base64VecU8>) -> ImmutableData {
+    ImmutableData {
+        name: "Shield".to_string(),
+        img: img.map(|img| img.to_string()),
+        rarity: None,
+        extra_immutable_data: None,
+        img_hash,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+fn create_template(contract: &mut NFTContract, immutable_data: ImmutableData) -> Template {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_template(
+        "Game".to_string(),
+        TemplateArgs {
+            schema_id: 0,
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            immutable_data,
+            validity_duration: None,
+        },
+    )
+}
+
+fn mint_template(contract: &mut NFTContract, template_id: TemplateId) -> TokenMetadata {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_mint("Game".to_string(), 0, template_id, Some(1), alice());
+    contract
+        .nft_tokens_for_template(template_id, None, None)
+        .items
+        .remove(0)
+        .metadata
+}
+
+#[test]
+fn media_hash_is_the_sha256_of_the_template_image() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    let metadata = contract.nft_token(token_ids[0].clone()).unwrap().metadata;
+    assert_eq!(metadata.media_hash, Some(content_hash("lightsaber.png")));
+    assert_eq!(metadata.media_hash.unwrap().0.len(), 32);
+}
+
+#[test]
+fn templates_without_image_can_mint() {
+    let mut contract = setup_contract();
+    let template = create_template(&mut contract, immutable_data(None, None));
+
+    let metadata = mint_template(&mut contract, template.template_id);
+    assert!(metadata.media.is_none());
+    assert!(metadata.media_hash.is_none());
+}
+
+#[test]
+fn reference_and_its_hash_are_copied_to_the_token() {
+    let mut contract = setup_contract();
+    let mut data = immutable_data(None, None);
+    data.reference = Some("https://nearhub.club/shield.json".to_string());
+    data.reference_hash = Some(content_hash("shield.json"));
+    let template = create_template(&mut contract, data);
+
+    let metadata = mint_template(&mut contract, template.template_id);
+    assert_eq!(
+        metadata.reference,
+        Some("https://nearhub.club/shield.json".to_string())
+    );
+    assert_eq!(metadata.reference_hash, Some(content_hash("shield.json")));
+}
+
+#[test]
+#[should_panic(expected = "img_hash must be a base64-encoded sha256 hash (32 bytes)")]
+fn image_hash_must_be_a_sha256() {
+    let mut contract = setup_contract();
+    create_template(
+        &mut contract,
+        immutable_data(
+            Some("https://nearhub.club/shield.png"),
+            Some(Base64VecU8(b"https://nearhub.club/shield.png".to_vec())),
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "img_hash is set without img")]
+fn image_hash_needs_an_image() {
+    let mut contract = setup_contract();
+    create_template(
+        &mut contract,
+        immutable_data(None, Some(content_hash("shield.png"))),
+    );
+}
+
+#[test]
+#[should_panic(expected = "reference_hash is required when reference is set")]
+fn reference_needs_its_hash() {
+    let mut contract = setup_contract();
+    let mut data = immutable_data(None, None);
+    data.reference = Some("https://nearhub.club/shield.json".to_string());
+    create_template(&mut contract, data);
+}
+
+#[test]
+#[should_panic(expected = "Relative img requires the contract base_uri")]
+fn relative_image_needs_a_base_uri() {
+    let mut contract = setup_contract();
+    create_template(
+        &mut contract,
+        immutable_data(
+            Some("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            Some(content_hash("shield.png")),
+        ),
+    );
+}
+
+#[test]
+fn relative_image_is_resolved_by_the_base_uri() {
+    let mut contract = setup_contract();
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.set_base_uri(Some("https://ipfs.io/ipfs".to_string()));
+    assert_eq!(
+        contract.nft_metadata().base_uri,
+        Some("https://ipfs.io/ipfs".to_string())
+    );
+
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let template = create_template(
+        &mut contract,
+        immutable_data(Some(cid), Some(content_hash("shield.png"))),
+    );
+    let metadata = mint_template(&mut contract, template.template_id);
+    assert_eq!(metadata.media, Some(cid.to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this contract can call this function")]
+fn only_the_owner_sets_the_base_uri() {
+    let mut contract = setup_contract();
+    set_caller(alice(), 1);
+    contract.set_base_uri(Some("https://ipfs.io/ipfs".to_string()));
+}
@@ -0,0 +1,445 @@
+// Migrate the baseline (V1) state layout to the current layout
+mod common;
+
+use common::*;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::U128;
+use near_sdk::{env, AccountId, CryptoHash};
+use nft_contract::*;
+use std::collections::HashMap;
+
+fn storage_key(key: StorageKey) -> Vec<u8> {
+    key.try_to_vec().unwrap()
+}
+
+fn hash_account_id(account_id: &str) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
+    hash
+}
+
+// Write a V1 state (no STATE_VERSION key): 1 Collection, 1 Schema, 1 Template, 1 Drop Sale, 1 NFT of alice
+fn write_v1_state() {
+    set_caller(owner(), 0);
+
+    let collection = || CollectionV1 {
+        collection_id: 0,
+        owner_id: owner(),
+        collection_name: "Game".to_string(),
+        market_fee: 0.05,
+        data: CollectionExtraData {
+            name: "Game".to_string(),
+            img: None,
+        },
+        approved_account_ids: HashMap::default(),
+        next_approval_id: 0,
+    };
+
+    let mut collections_per_owner: LookupMap<AccountId, UnorderedSet<CollectionName>> =
+        LookupMap::new(storage_key(StorageKey::CollectionsPerOwnerKey));
+    let mut collection_set =
+        UnorderedSet::new(storage_key(StorageKey::CollectionsPerOwnerInnerKey {
+            account_id_hash: hash_account_id(&owner()),
+        }));
+    collection_set.insert(&"Game".to_string());
+    collections_per_owner.insert(&owner(), &collection_set);
+
+    let mut collections_by_name = UnorderedMap::new(storage_key(StorageKey::CollectionsByNameKey));
+    collections_by_name.insert(&"Game".to_string(), &collection());
+    let mut collections_by_id = UnorderedMap::new(storage_key(StorageKey::CollectionsByIdKey));
+    collections_by_id.insert(&0, &collection());
+
+    let mut schemas_by_id = UnorderedMap::new(storage_key(StorageKey::SchemasByIdKey));
+    schemas_by_id.insert(
+        &0,
+        &Schema {
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            collection_id: 0,
+            collection_name: "Game".to_string(),
+            schema_format: vec![],
+        },
+    );
+
+    let mut templates_by_id = UnorderedMap::new(storage_key(StorageKey::TemplatesByIdKey));
+    templates_by_id.insert(
+        &0,
+        &TemplateV1 {
+            template_id: 0,
+            collection_id: 0,
+            collection_name: "Game".to_string(),
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            transferable: true,
+            burnable: true,
+            max_supply: 100,
+            issued_supply: 1,
+            immutable_data: ImmutableDataV1 {
+                name: "Lightsaber".to_string(),
+                img: Some("https://nearhub.club/lightsaber.png".to_string()),
+                rarity: None,
+                extra_immutable_data: None,
+            },
+            is_lootbox: false,
+            unlock_time: None,
+            config: None,
+        },
+    );
+    let mut token_by_template_id_counter =
+        UnorderedMap::new(storage_key(StorageKey::TokenByTemplateIdCounter));
+    token_by_template_id_counter.insert(&0, &1);
+
+    let token_id: TokenId = 1000000000;
+    let mut tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>> =
+        LookupMap::new(storage_key(StorageKey::TokensPerOwnerKey));
+    let mut token_set = UnorderedSet::new(storage_key(StorageKey::TokensPerOwnerInnerKey {
+        account_id_hash: hash_account_id(&alice()),
+    }));
+    token_set.insert(&token_id);
+    tokens_per_owner.insert(&alice(), &token_set);
+
+    let mut tokens_by_id = UnorderedMap::new(storage_key(StorageKey::TokensByIdKey));
+    tokens_by_id.insert(
+        &token_id,
+        &Token {
+            owner_id: alice(),
+            token_id,
+            token_by_template_id: 0,
+            collection_id: 0,
+            collection_name: "Game".to_string(),
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            template_id: 0,
+            approved_account_ids: HashMap::default(),
+            next_approval_id: 0,
+        },
+    );
+    let mut token_metadata_by_id = UnorderedMap::new(storage_key(StorageKey::TokenMetadataByIdKey));
+    token_metadata_by_id.insert(
+        &token_id,
+        &TokenMetadata {
+            title: Some("Lightsaber".to_string()),
+            description: None,
+            media: Some("https://nearhub.club/lightsaber.png".to_string()),
+            // V1 media_hash: bytes of the media URL
+            media_hash: Some(b"https://nearhub.club/lightsaber.png".to_vec().into()),
+            copies: Some(100),
+            issued_at: Some(1_600_000_000_123_456_789), // nanoseconds
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+            nft_type: "NFT".to_string(),
+        },
+    );
+
+    let mut drops_by_id = UnorderedMap::new(storage_key(StorageKey::DropsByIdKey));
+    drops_by_id.insert(
+        &0,
+        &DropSaleV1 {
+            drop_id: 0,
+            owner_id: owner(),
+            collection_name: "Game".to_string(),
+            template_ids: vec![0],
+            price: U128(1_000),
+            price_type: "NEAR".to_string(),
+            is_public: true,
+            max_supply: 10,
+            issued_supply: 0,
+            account_limit: 1,
+            account_limit_cooldown: 0,
+            start_time: 0,
+            end_time: 0,
+            display_data: None,
+            approved_account_ids: HashMap::default(),
+            next_approval_id: 0,
+        },
+    );
+
+    let contract = NFTContractV1 {
+        owner_id: owner(),
+        collections_per_owner,
+        tokens_per_owner,
+        collections_by_name,
+        collections_by_id,
+        schemas_by_id,
+        templates_by_id,
+        token_id_counter: 1,
+        token_by_template_id_counter,
+        tokens_by_id,
+        lootbox_nft_by_lootbox_id_counter: UnorderedMap::new(storage_key(
+            StorageKey::LootboxNftByLootboxIdCounter,
+        )),
+        drops_by_id,
+        token_metadata_by_id,
+        metadata: LazyOption::new(
+            storage_key(StorageKey::ContractMetadataKey),
+            Some(&NFTContractMetadata {
+                spec: "nearhub-nft-1.0.0".to_string(),
+                name: "Nearhub NFT".to_string(),
+                symbol: "NHT".to_string(),
+                icon: None,
+                base_uri: None,
+                reference: None,
+                reference_hash: None,
+            }),
+        ),
+    };
+    env::state_write(&contract);
+}
+
+// Add 1 Lootbox Template with an unlock time and 1 Lootbox NFT of alice to the V1 state
+fn write_v1_lootbox(unlock_time: u64) {
+    let mut contract: NFTContractV1 = env::state_read().unwrap();
+    contract.templates_by_id.insert(
+        &1,
+        &TemplateV1 {
+            template_id: 1,
+            collection_id: 0,
+            collection_name: "Game".to_string(),
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            issued_supply: 1,
+            immutable_data: ImmutableDataV1 {
+                name: "Chest".to_string(),
+                img: None,
+                rarity: None,
+                extra_immutable_data: None,
+            },
+            is_lootbox: true,
+            unlock_time: Some(unlock_time), // milliseconds
+            config: Some(vec![]),
+        },
+    );
+    contract.token_by_template_id_counter.insert(&1, &1);
+
+    let token_id: TokenId = 1000000001;
+    let mut token_set = contract.tokens_per_owner.get(&alice()).unwrap();
+    token_set.insert(&token_id);
+    contract.tokens_per_owner.insert(&alice(), &token_set);
+    contract.tokens_by_id.insert(
+        &token_id,
+        &Token {
+            owner_id: alice(),
+            token_id,
+            token_by_template_id: 0,
+            collection_id: 0,
+            collection_name: "Game".to_string(),
+            schema_id: 0,
+            schema_name: "Weapon".to_string(),
+            template_id: 1,
+            approved_account_ids: HashMap::default(),
+            next_approval_id: 0,
+        },
+    );
+    contract.token_metadata_by_id.insert(
+        &token_id,
+        &TokenMetadata {
+            title: Some("Chest".to_string()),
+            description: None,
+            media: None,
+            media_hash: None,
+            copies: Some(10),
+            issued_at: Some(1_600_000_000_123_456_789), // nanoseconds
+            expires_at: None,
+            starts_at: Some(unlock_time), // V1 starts_at = unlock_time of the Lootbox
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+            nft_type: "Lootbox".to_string(),
+        },
+    );
+    contract.token_id_counter = 2;
+    env::state_write(&contract);
+}
+
+// Upgrade only: the V1 values are migrated by migrate_state
+fn upgrade_v1_state() -> NFTContract {
+    write_v1_state();
+    set_caller("nft.testnet".to_string(), 0);
+    NFTContract::migrate()
+}
+
+fn migrate_v1_state() -> NFTContract {
+    let mut contract = upgrade_v1_state();
+    set_caller(owner(), 0);
+    while contract.migrate_state(None).step != MigrationStep::Done {}
+    contract
+}
+
+#[test]
+fn new_contract_uses_the_current_state_version() {
+    let contract = setup_contract();
+    assert_eq!(contract.get_state_version(), CURRENT_STATE_VERSION);
+}
+
+#[test]
+fn migrate_converts_collections_templates_and_drops() {
+    let contract = migrate_v1_state();
+    assert_eq!(contract.get_state_version(), CURRENT_STATE_VERSION);
+
+    let collections = contract.get_all_collections(None, None).items;
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].collection_name, "Game");
+    assert!(collections[0].allow_expired_transfer);
+    assert!(collections[0].voucher_public_key.is_none());
+
+    let template = contract.get_template_by_id(0);
+    assert_eq!(template.issued_supply, 1);
+    assert_eq!(template.burned_supply, 0);
+    assert!(template.validity_duration.is_none());
+    // V1 media_hash was not a real hash -> No img_hash after the migration
+    assert!(template.immutable_data.img_hash.is_none());
+
+    let drop = contract.get_drop_by_id(0);
+    assert_eq!(drop.price, U128(1_000));
+    assert_eq!(drop.last_claim_price, U128(1_000));
+    assert!(matches!(drop.pricing_mode, DropPricingMode::Fixed));
+    assert!(drop.claim_mode == DropClaimMode::Purchase);
+    assert!(drop.access_rule.is_none());
+    // The per-Collection indexes are built from the V1 state
+    assert_eq!(
+        contract.schema_supply_by_collection("Game".to_string()).0,
+        1
+    );
+    assert_eq!(
+        contract.template_supply_by_collection("Game".to_string()).0,
+        1
+    );
+    assert_eq!(contract.drop_supply_by_collection("Game".to_string()).0, 1);
+    assert_eq!(
+        contract
+            .search("light".to_string(), None, None, None)
+            .items
+            .len(),
+        1
+    );
+    // Drop Sales created after the migration don't reuse the old ids
+    assert_eq!(contract.drop_id_counter, 1);
+    assert!(contract.get_migration_progress().is_none());
+}
+
+#[test]
+fn migrate_converts_token_timestamps_and_clears_media_hash() {
+    let contract = migrate_v1_state();
+
+    let metadata = contract
+        .nft_token("1000000000".to_string())
+        .unwrap()
+        .metadata;
+    assert_eq!(metadata.issued_at, Some(1_600_000_000_123));
+    assert!(metadata.media_hash.is_none());
+    assert_eq!(
+        metadata.media,
+        Some("https://nearhub.club/lightsaber.png".to_string())
+    );
+}
+
+#[test]
+fn migrate_keeps_lootbox_unlock_times_in_milliseconds() {
+    let unlock_time = 1_700_000_000_000;
+    write_v1_state();
+    write_v1_lootbox(unlock_time);
+    set_caller("nft.testnet".to_string(), 0);
+    let mut contract = NFTContract::migrate();
+    set_caller(owner(), 0);
+    while contract.migrate_state(None).step != MigrationStep::Done {}
+
+    assert_eq!(
+        contract.get_template_by_id(1).unlock_time,
+        Some(unlock_time)
+    );
+    let metadata = contract
+        .nft_token("1000000001".to_string())
+        .unwrap()
+        .metadata;
+    assert_eq!(metadata.starts_at, Some(unlock_time));
+    assert_eq!(metadata.issued_at, Some(1_600_000_000_123));
+}
+
+#[test]
+fn migrated_tokens_can_be_transferred() {
+    let mut contract = migrate_v1_state();
+    env::state_write(&contract);
+
+    let token = contract.nft_token("1000000000".to_string()).unwrap();
+    assert_eq!(token.owner_id, alice());
+    assert_eq!(token.metadata.title, Some("Lightsaber".to_string()));
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), "1000000000".to_string(), None, None);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 1);
+}
+
+#[test]
+fn migrate_state_runs_in_batches() {
+    let mut contract = upgrade_v1_state();
+    assert_eq!(contract.nft_supply_for_collection("Game".to_string()).0, 0);
+
+    // 1 Collection, 1 Schema, 1 Template, 1 Drop Sale, 1 NFT -> 2 entries per call
+    set_caller(owner(), 0);
+    let progress = contract.migrate_state(Some(2));
+    assert_eq!(progress.step, MigrationStep::Templates);
+    assert_eq!(progress.next_index, 0);
+    let progress = contract.migrate_state(Some(2));
+    assert_eq!(progress.step, MigrationStep::Tokens);
+    assert_eq!(
+        contract.get_migration_progress().unwrap().step,
+        MigrationStep::Tokens
+    );
+    let progress = contract.migrate_state(Some(2));
+    assert_eq!(progress.step, MigrationStep::Done);
+
+    assert_eq!(contract.nft_supply_for_template(0).0, 1);
+    assert_eq!(contract.nft_supply_for_collection("Game".to_string()).0, 1);
+    let holders = contract
+        .collection_holders("Game".to_string(), None, None)
+        .items;
+    assert_eq!(holders.len(), 1);
+    assert_eq!(holders[0].account_id, alice());
+    assert_eq!(holders[0].token_count, 1);
+}
+
+#[test]
+#[should_panic(expected = "The contract state is being migrated")]
+fn tokens_cannot_be_transferred_until_the_state_is_migrated() {
+    let mut contract = upgrade_v1_state();
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), "1000000000".to_string(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "The state has already been migrated")]
+fn migrate_state_stops_once_done() {
+    let mut contract = migrate_v1_state();
+
+    contract.migrate_state(None);
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this contract can migrate the state")]
+fn only_owner_can_migrate_the_state() {
+    let mut contract = upgrade_v1_state();
+
+    set_caller(alice(), 0);
+    contract.migrate_state(None);
+}
+
+#[test]
+fn migrate_twice_keeps_the_current_state() {
+    let contract = migrate_v1_state();
+    env::state_write(&contract);
+
+    let contract = NFTContract::migrate();
+    assert_eq!(contract.get_all_collections(None, None).items.len(), 1);
+    assert_eq!(contract.get_template_by_id(0).issued_supply, 1);
+    assert_eq!(contract.drop_id_counter, 1);
+}
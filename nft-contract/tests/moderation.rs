@@ -0,0 +1,93 @@
+// Platform moderation of the Collections: freeze, hide and verify
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+#[test]
+#[should_panic(expected = "Collection Game is frozen")]
+fn frozen_collection_cannot_mint() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Moderator);
+
+    set_caller(alice(), 1);
+    contract.set_collection_frozen("Game".to_string(), true, Some("Scam".to_string()));
+
+    mint(&mut contract, bob(), 1);
+}
+
+#[test]
+fn unfrozen_collection_can_mint_again() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(owner(), 1);
+    contract.set_collection_frozen("Game".to_string(), true, Some("Scam".to_string()));
+    let collection = contract.get_collection("Game".to_string()).unwrap();
+    assert!(collection.moderation.frozen);
+    assert_eq!(
+        collection.moderation.frozen_reason,
+        Some("Scam".to_string())
+    );
+
+    // NFTs already minted can still be transferred
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+
+    set_caller(owner(), 1);
+    contract.set_collection_frozen("Game".to_string(), false, None);
+    assert!(contract
+        .get_collection("Game".to_string())
+        .unwrap()
+        .moderation
+        .frozen_reason
+        .is_none());
+
+    mint(&mut contract, bob(), 1);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 2);
+}
+
+#[test]
+fn hidden_collection_is_excluded_from_listings() {
+    let mut contract = setup_contract();
+
+    set_caller(owner(), 1);
+    contract.set_collection_hidden("Game".to_string(), true, Some("Copyright".to_string()));
+
+    assert!(contract.get_all_collections(None, None).items.is_empty());
+    assert!(contract
+        .get_collections_by_name("gam".to_string(), None, None)
+        .items
+        .is_empty());
+    let collection = contract.get_collection("Game".to_string()).unwrap();
+    assert!(collection.moderation.hidden);
+    assert_eq!(
+        collection.moderation.hidden_reason,
+        Some("Copyright".to_string())
+    );
+}
+
+#[test]
+fn moderator_can_verify_collection() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Moderator);
+
+    set_caller(alice(), 1);
+    contract.set_collection_verified("Game".to_string(), true, None);
+
+    assert!(
+        contract.get_all_collections(None, None).items[0]
+            .moderation
+            .verified
+    );
+}
+
+#[test]
+#[should_panic(expected = "Account does not have the required role")]
+fn pauser_cannot_moderate() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Pauser);
+
+    set_caller(alice(), 1);
+    contract.set_collection_hidden("Game".to_string(), true, None);
+}
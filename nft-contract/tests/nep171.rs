@@ -0,0 +1,222 @@
+// NEP-171 Core: https://nomicon.io/Standards/Tokens/NonFungibleToken/Core
+mod common;
+
+use common::*;
+use nft_contract::{NonFungibleTokenApproval, NonFungibleTokenCore, NonFungibleTokenResolver};
+use near_sdk::serde_json::{self, json};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, MockedBlockchain, PromiseResult};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+// Callback nft_resolve_transfer (called by the Contract itself) with the result of nft_on_transfer
+fn set_resolve_caller(result: PromiseResult) {
+    let contract_id = near_sdk::json_types::ValidAccountId::try_from("nft.testnet").unwrap();
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(contract_id.clone())
+        .predecessor_account_id(contract_id);
+    testing_env!(
+        context.build(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        vec![result]
+    );
+}
+
+// alice approves market, market transfers the NFT to bob with nft_transfer_call
+fn transfer_call_to_bob(contract: &mut nft_contract::NFTContract) -> (String, HashMap<String, u64>) {
+    let token_ids = mint(contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+    let approved_account_ids = contract
+        .nft_token(token_ids[0].clone())
+        .unwrap()
+        .approved_account_ids;
+
+    set_caller(market(), 1);
+    let _ = contract.nft_transfer_call(bob(), token_ids[0].clone(), Some(0), None, "".to_string());
+    assert_eq!(contract.nft_token(token_ids[0].clone()).unwrap().owner_id, bob());
+
+    (token_ids[0].clone(), approved_account_ids)
+}
+
+fn resolve(
+    contract: &mut nft_contract::NFTContract,
+    token_id: &str,
+    approved_account_ids: HashMap<String, u64>,
+    result: PromiseResult,
+) -> bool {
+    set_resolve_caller(result);
+    contract.nft_resolve_transfer(
+        Some(market()),
+        alice(),
+        bob(),
+        token_id.to_string(),
+        approved_account_ids,
+        None,
+    )
+}
+
+#[test]
+fn nft_token_has_the_standard_shape() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    let token = contract.nft_token(token_ids[0].clone()).unwrap();
+    let value = serde_json::to_value(&token).unwrap();
+
+    // token_id is a string, owner_id / metadata / approved_account_ids are at the top level
+    assert_eq!(value["token_id"], json!(token_ids[0]));
+    assert_eq!(value["owner_id"], json!(alice()));
+    assert!(value["metadata"].is_object());
+    assert_eq!(value["approved_account_ids"], json!({}));
+    assert!(value.get("collection_name").is_none());
+}
+
+#[test]
+fn nft_token_returns_none_for_unknown_token() {
+    let contract = setup_contract();
+    assert!(contract.nft_token("4294967295".to_string()).is_none());
+}
+
+#[test]
+fn nft_transfer_without_approval_id_moves_the_token() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+
+    assert_eq!(contract.nft_token(token_ids[0].clone()).unwrap().owner_id, bob());
+    assert_eq!(contract.nft_supply_for_owner(alice()).0, 0);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 1);
+}
+
+#[test]
+fn nft_transfer_by_approved_account_checks_approval_id() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+
+    set_caller(market(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), Some(0), None);
+
+    let token = contract.nft_token(token_ids[0].clone()).unwrap();
+    assert_eq!(token.owner_id, bob());
+    // Approvals are cleared after a transfer
+    assert!(token.approved_account_ids.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Required attached deposit of exact 1 yoctoNear")]
+fn nft_transfer_requires_one_yocto() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 0);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn nft_transfer_by_stranger_panics() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(bob(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid token id")]
+fn nft_transfer_rejects_non_numeric_token_id() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), "abc".to_string(), None, None);
+}
+
+#[test]
+fn nft_resolve_transfer_keeps_token_when_receiver_returns_false() {
+    let mut contract = setup_contract();
+    let (token_id, approved_account_ids) = transfer_call_to_bob(&mut contract);
+
+    let is_transferred = resolve(
+        &mut contract,
+        &token_id,
+        approved_account_ids,
+        PromiseResult::Successful(b"false".to_vec()),
+    );
+
+    assert!(is_transferred);
+    let token = contract.nft_token(token_id).unwrap();
+    assert_eq!(token.owner_id, bob());
+    assert!(token.approved_account_ids.is_empty());
+}
+
+#[test]
+fn nft_resolve_transfer_returns_token_when_receiver_returns_true() {
+    let mut contract = setup_contract();
+    let (token_id, approved_account_ids) = transfer_call_to_bob(&mut contract);
+
+    let is_transferred = resolve(
+        &mut contract,
+        &token_id,
+        approved_account_ids.clone(),
+        PromiseResult::Successful(b"true".to_vec()),
+    );
+
+    assert!(!is_transferred);
+    let token = contract.nft_token(token_id.clone()).unwrap();
+    assert_eq!(token.owner_id, alice());
+    // Approvals cũ được khôi phục
+    assert_eq!(token.approved_account_ids, approved_account_ids);
+    assert_eq!(contract.nft_supply_for_owner(alice()).0, 1);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 0);
+    assert!(contract
+        .nft_tokens_for_owner(alice(), None, None)
+        .iter()
+        .any(|token| token.token_id == token_id));
+}
+
+#[test]
+fn nft_resolve_transfer_returns_token_when_receiver_fails() {
+    let mut contract = setup_contract();
+    let (token_id, approved_account_ids) = transfer_call_to_bob(&mut contract);
+
+    let is_transferred = resolve(
+        &mut contract,
+        &token_id,
+        approved_account_ids,
+        PromiseResult::Failed,
+    );
+
+    assert!(!is_transferred);
+    assert_eq!(contract.nft_token(token_id).unwrap().owner_id, alice());
+}
+
+#[test]
+fn nft_resolve_transfer_cannot_return_token_moved_by_receiver() {
+    let mut contract = setup_contract();
+    let (token_id, approved_account_ids) = transfer_call_to_bob(&mut contract);
+
+    // bob transfer NFT đi trước khi callback chạy
+    set_caller(bob(), 1);
+    contract.nft_transfer(owner(), token_id.clone(), None, None);
+
+    let is_transferred = resolve(
+        &mut contract,
+        &token_id,
+        approved_account_ids,
+        PromiseResult::Successful(b"true".to_vec()),
+    );
+
+    assert!(is_transferred);
+    assert_eq!(contract.nft_token(token_id).unwrap().owner_id, owner());
+}
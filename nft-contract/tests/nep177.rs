@@ -0,0 +1,56 @@
+// NEP-177 Metadata: https://nomicon.io/Standards/Tokens/NonFungibleToken/Metadata
+mod common;
+
+use common::*;
+use nft_contract::NonFungibleTokenMetadata;
+use near_sdk::serde_json;
+
+#[test]
+fn nft_metadata_has_the_standard_spec() {
+    let contract = setup_contract();
+    let metadata = contract.nft_metadata();
+
+    assert!(metadata.spec.starts_with("nft-"));
+    assert!(!metadata.name.is_empty());
+    assert!(!metadata.symbol.is_empty());
+}
+
+#[test]
+fn token_metadata_is_generated_from_the_template() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    let metadata = contract.nft_token(token_ids[0].clone()).unwrap().metadata;
+    assert_eq!(metadata.title, Some("Lightsaber".to_string()));
+    assert_eq!(
+        metadata.media,
+        Some("https://nearhub.club/lightsaber.png".to_string())
+    );
+    assert_eq!(metadata.copies, Some(100));
+    assert_eq!(metadata.extra, Some("{\"attack\": \"10\"}".to_string()));
+}
+
+#[test]
+fn token_metadata_serializes_the_standard_fields() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    let metadata = contract.nft_token(token_ids[0].clone()).unwrap().metadata;
+    let value = serde_json::to_value(&metadata).unwrap();
+    for field in [
+        "title",
+        "description",
+        "media",
+        "media_hash",
+        "copies",
+        "issued_at",
+        "expires_at",
+        "starts_at",
+        "updated_at",
+        "extra",
+        "reference",
+        "reference_hash",
+    ] {
+        assert!(value.get(field).is_some(), "Missing field {}", field);
+    }
+}
@@ -0,0 +1,85 @@
+// NEP-178 Approval Management: https://nomicon.io/Standards/Tokens/NonFungibleToken/ApprovalManagement
+mod common;
+
+use common::*;
+use nft_contract::NonFungibleTokenApproval;
+
+#[test]
+fn nft_approve_adds_the_account_with_an_approval_id() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+
+    let token = contract.nft_token(token_ids[0].clone()).unwrap();
+    assert_eq!(token.approved_account_ids.get(&market()), Some(&0));
+}
+
+#[test]
+fn nft_is_approved_without_approval_id() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+
+    assert!(contract.nft_is_approved(token_ids[0].clone(), market(), None));
+    assert!(!contract.nft_is_approved(token_ids[0].clone(), bob(), None));
+}
+
+#[test]
+fn nft_is_approved_with_approval_id() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+    contract.nft_approve(token_ids[0].clone(), bob(), None);
+
+    assert!(contract.nft_is_approved(token_ids[0].clone(), market(), Some(0)));
+    assert!(!contract.nft_is_approved(token_ids[0].clone(), market(), Some(1)));
+    assert!(contract.nft_is_approved(token_ids[0].clone(), bob(), Some(1)));
+}
+
+#[test]
+fn nft_revoke_removes_one_account() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+    contract.nft_approve(token_ids[0].clone(), bob(), None);
+
+    set_caller(alice(), 1);
+    contract.nft_revoke(token_ids[0].clone(), market());
+
+    assert!(!contract.nft_is_approved(token_ids[0].clone(), market(), None));
+    assert!(contract.nft_is_approved(token_ids[0].clone(), bob(), None));
+}
+
+#[test]
+fn nft_revoke_all_removes_every_account() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+    contract.nft_approve(token_ids[0].clone(), bob(), None);
+
+    set_caller(alice(), 1);
+    contract.nft_revoke_all(token_ids[0].clone());
+
+    let token = contract.nft_token(token_ids[0].clone()).unwrap();
+    assert!(token.approved_account_ids.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Predecessor must be the token owner")]
+fn nft_approve_by_stranger_panics() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_ids[0].clone(), market(), None);
+}
@@ -0,0 +1,62 @@
+// NEP-181 Enumeration: https://nomicon.io/Standards/Tokens/NonFungibleToken/Enumeration
+mod common;
+
+use common::*;
+
+#[test]
+fn nft_total_supply_counts_tokens() {
+    let mut contract = setup_contract();
+    assert_eq!(contract.nft_total_supply().0, 0);
+
+    mint(&mut contract, alice(), 2);
+    mint(&mut contract, bob(), 1);
+    assert_eq!(contract.nft_total_supply().0, 3);
+}
+
+#[test]
+fn nft_tokens_paginates_with_string_token_ids() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 3);
+
+    let first_page = contract.nft_tokens(None, Some(2));
+    let second_page = contract.nft_tokens(Some(2.into()), Some(2));
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+
+    let all_ids: Vec<String> = first_page
+        .iter()
+        .chain(second_page.iter())
+        .map(|token| token.token_id.clone())
+        .collect();
+    for token_id in all_ids.iter() {
+        assert!(token_id.parse::<u32>().is_ok());
+        assert!(contract.nft_token(token_id.clone()).is_some());
+    }
+}
+
+#[test]
+fn nft_supply_and_tokens_for_owner() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 2);
+    mint(&mut contract, bob(), 1);
+
+    assert_eq!(contract.nft_supply_for_owner(alice()).0, 2);
+    assert_eq!(contract.nft_supply_for_owner(market()).0, 0);
+
+    let tokens = contract.nft_tokens_for_owner(alice(), None, None);
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens.iter().all(|token| token.owner_id == alice()));
+    assert!(contract.nft_tokens_for_owner(market(), None, None).is_empty());
+}
+
+#[test]
+fn rich_views_keep_the_nearhub_fields() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    let token_id: u32 = token_ids[0].parse().unwrap();
+    let detail = contract.nft_token_detail(token_id).unwrap();
+    assert_eq!(detail.collection_name, "Game");
+    assert_eq!(detail.template_id, 0);
+    assert_eq!(contract.nft_tokens_detail_for_owner(alice(), None, None).items.len(), 1);
+}
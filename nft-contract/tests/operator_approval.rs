@@ -0,0 +1,63 @@
+// Operator approvals (nft_approve_operator)
+mod common;
+
+use common::*;
+use nft_contract::{NonFungibleTokenApproval, NonFungibleTokenCore};
+
+#[test]
+fn operator_can_transfer_any_token_of_the_owner() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 2);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve_operator(market(), None, None);
+    assert!(contract.nft_is_approved_operator(alice(), market(), None, Some(0)));
+    assert!(contract.nft_is_approved_operator(alice(), market(), Some("Game".to_string()), None));
+
+    set_caller(market(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), Some(0), None);
+    contract.nft_transfer(bob(), token_ids[1].clone(), None, None);
+
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 2);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn operator_scoped_to_another_collection_cannot_transfer() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.create_collection(
+        "Other".to_string(),
+        0.05,
+        nft_contract::CollectionExtraData {
+            name: "Other".to_string(),
+            img: None,
+        },
+    );
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve_operator(market(), Some("Other".to_string()), None);
+    assert!(!contract.nft_is_approved_operator(alice(), market(), Some("Game".to_string()), None));
+
+    set_caller(market(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Sender must be the token owner or the approved account")]
+fn revoked_operator_cannot_transfer() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve_operator(market(), None, None);
+
+    set_caller(alice(), 1);
+    contract.nft_revoke_operator(market());
+    assert!(!contract.nft_is_approved_operator(alice(), market(), None, None));
+
+    set_caller(market(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
@@ -0,0 +1,145 @@
+// Cursor pagination of the list views
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+fn token_ids(page: &Page<JsonTokenDetail>) -> Vec<String> {
+    page.items
+        .iter()
+        .map(|token| token.token_id.to_string())
+        .collect()
+}
+
+#[test]
+fn pages_use_the_default_limit_and_end_without_a_cursor() {
+    let mut contract = setup_contract();
+    let minted = mint(&mut contract, alice(), 12);
+
+    let first_page = contract.nft_tokens_detail(None, None);
+    assert_eq!(token_ids(&first_page), minted[..10].to_vec());
+    assert_eq!(first_page.next_cursor, Some(minted[9].clone()));
+
+    let last_page = contract.nft_tokens_detail(first_page.next_cursor, None);
+    assert_eq!(token_ids(&last_page), minted[10..].to_vec());
+    assert!(last_page.next_cursor.is_none());
+
+    // The limit is clamped to [1, 100]
+    assert_eq!(contract.nft_tokens_detail(None, Some(0)).items.len(), 1);
+    assert_eq!(
+        contract.nft_tokens_detail(None, Some(1_000)).items.len(),
+        12
+    );
+}
+
+#[test]
+fn cursor_is_stable_when_items_are_removed() {
+    let mut contract = setup_contract();
+    let minted = mint(&mut contract, alice(), 4);
+
+    let first_page = contract.nft_tokens_detail_for_owner(alice(), None, Some(2));
+    assert_eq!(token_ids(&first_page), minted[..2].to_vec());
+
+    // Removing a token swaps the last token of the owner's set into its place
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), minted[0].clone(), None, None);
+
+    let second_page =
+        contract.nft_tokens_detail_for_owner(alice(), first_page.next_cursor, Some(2));
+    assert_eq!(token_ids(&second_page), minted[2..].to_vec());
+    assert!(second_page.next_cursor.is_none());
+}
+
+#[test]
+fn cursor_skips_removed_drops() {
+    let mut contract = setup_contract();
+    set_caller(owner(), STORAGE_DEPOSIT);
+    for _ in 0..3 {
+        contract.create_drop(
+            "Game".to_string(),
+            DropSaleArgs {
+                account_limit: 1,
+                ..drop_args(vec![0], 1_000)
+            },
+        );
+    }
+
+    let first_page = contract.get_all_drops(None, Some(1));
+    assert_eq!(first_page.items[0].drop_id, 0);
+    assert_eq!(first_page.next_cursor, Some("0".to_string()));
+
+    set_caller(owner(), 1);
+    contract.cancel_drop(1);
+
+    let second_page = contract.get_all_drops(first_page.next_cursor, Some(1));
+    assert_eq!(second_page.items[0].drop_id, 2);
+    assert!(second_page.next_cursor.is_none());
+}
+
+#[test]
+fn cursor_is_stable_when_a_holder_leaves() {
+    let mut contract = setup_contract();
+    let alice_tokens = mint(&mut contract, alice(), 2);
+    mint(&mut contract, bob(), 1);
+    mint(&mut contract, market(), 1);
+
+    // Holders are ordered by account id
+    let first_page = contract.collection_holders("Game".to_string(), None, Some(1));
+    assert_eq!(first_page.items[0].account_id, alice());
+    assert_eq!(first_page.next_cursor, Some(alice()));
+
+    // alice is no longer a holder, the cursor still points to the next account id
+    set_caller(alice(), 1);
+    for token_id in alice_tokens {
+        contract.nft_transfer(market(), token_id, None, None);
+    }
+
+    let second_page =
+        contract.collection_holders("Game".to_string(), first_page.next_cursor, Some(1));
+    assert_eq!(second_page.items[0].account_id, bob());
+    assert_eq!(second_page.next_cursor, Some(bob()));
+
+    let last_page =
+        contract.collection_holders("Game".to_string(), second_page.next_cursor, Some(1));
+    assert_eq!(last_page.items[0].account_id, market());
+    assert_eq!(last_page.items[0].token_count, 3);
+    assert!(last_page.next_cursor.is_none());
+}
+
+#[test]
+fn max_id_cursor_returns_an_empty_page() {
+    let contract = setup_contract();
+    let page = contract.get_all_collections(Some(u32::MAX.to_string()), None);
+    assert!(page.items.is_empty());
+    assert!(page.next_cursor.is_none());
+}
+
+#[test]
+fn page_stops_after_scanning_the_max_number_of_ids() {
+    let mut contract = setup_contract();
+    for _ in 0..505 {
+        set_caller(owner(), STORAGE_DEPOSIT);
+        contract.create_drop("Game".to_string(), drop_args(vec![0], 1_000));
+    }
+    // 502 Drop Sales đầu tiên bị cancel -> Trang đầu chỉ đọc 500 ids đã bị xoá
+    for drop_id in 0..502 {
+        set_caller(owner(), 1);
+        contract.cancel_drop(drop_id);
+    }
+
+    let first_page = contract.get_all_drops(None, None);
+    assert!(first_page.items.is_empty());
+    assert_eq!(first_page.next_cursor, Some("499".to_string()));
+
+    let second_page = contract.get_all_drops(first_page.next_cursor, None);
+    let drop_ids: Vec<DropId> = second_page.items.iter().map(|drop| drop.drop_id).collect();
+    assert_eq!(drop_ids, vec![502, 503, 504]);
+    assert!(second_page.next_cursor.is_none());
+}
+
+#[test]
+#[should_panic(expected = "Invalid cursor")]
+fn invalid_cursor_panics() {
+    let contract = setup_contract();
+    contract.get_all_drops(Some("first".to_string()), None);
+}
@@ -0,0 +1,78 @@
+// Roles and the granular pause switches of the Contract
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+#[test]
+#[should_panic(expected = "The mint feature is paused")]
+fn pauser_can_pause_mint() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Pauser);
+
+    set_caller(alice(), 1);
+    contract.pause(vec![PauseFeature::Mint], Some("Incident".to_string()));
+    assert!(contract.is_paused(PauseFeature::Mint));
+
+    mint(&mut contract, bob(), 1);
+}
+
+#[test]
+#[should_panic(expected = "The transfer feature is paused")]
+fn paused_transfer_blocks_nft_transfer() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 1);
+
+    set_caller(owner(), 1);
+    contract.pause(vec![PauseFeature::Transfer], None);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Account does not have the required role")]
+fn pauser_cannot_unpause() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Pauser);
+
+    set_caller(alice(), 1);
+    contract.pause(vec![PauseFeature::Mint], None);
+    contract.unpause(vec![PauseFeature::Mint], None);
+}
+
+#[test]
+fn admin_can_unpause() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Admin);
+
+    set_caller(alice(), 1);
+    contract.pause(vec![PauseFeature::Mint, PauseFeature::Drop], None);
+    contract.unpause(vec![PauseFeature::Mint], None);
+    assert_eq!(contract.get_paused_features(), vec![PauseFeature::Drop]);
+
+    mint(&mut contract, bob(), 1);
+    assert_eq!(contract.nft_supply_for_owner(bob()).0, 1);
+}
+
+#[test]
+fn revoke_role_removes_the_account() {
+    let mut contract = setup_contract();
+    contract.grant_role(alice(), Role::Moderator);
+    assert!(contract.has_role(alice(), Role::Moderator));
+    assert!(contract.has_role(owner(), Role::Admin));
+
+    set_caller(owner(), 1);
+    contract.revoke_role(alice(), Role::Moderator);
+    assert!(!contract.has_role(alice(), Role::Moderator));
+    assert!(contract.get_all_role_accounts(None, None).items.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this contract can call this function")]
+fn only_owner_can_grant_roles() {
+    let mut contract = setup_contract();
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.grant_role(bob(), Role::Admin);
+}
@@ -0,0 +1,185 @@
+// Random rolls: RandomWeighted Drop Sales and Lootbox unboxing
+mod common;
+
+use common::*;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::json_types::ValidAccountId;
+use near_sdk::{testing_env, AccountId, Balance, MockedBlockchain};
+use nft_contract::*;
+use std::convert::TryFrom;
+
+fn create_random_drop(
+    contract: &mut NFTContract,
+    template_ids: Vec<TemplateId>,
+    weights: Option<Vec<u32>>,
+    max_supply: u32,
+) {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        "Game".to_string(),
+        DropSaleArgs {
+            max_supply,
+            account_limit: max_supply,
+            distribution_mode: Some(DropDistributionMode::RandomWeighted { weights }),
+            ..drop_args(template_ids, 0)
+        },
+    );
+}
+
+// Same as set_caller, with another random seed for each block
+fn set_caller_with_seed(account_id: AccountId, attached_deposit: Balance, seed: u8) {
+    let account_id = ValidAccountId::try_from(account_id).unwrap();
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(ValidAccountId::try_from("nft.testnet").unwrap())
+        .predecessor_account_id(account_id)
+        .attached_deposit(attached_deposit)
+        .random_seed(vec![seed; 32]);
+    testing_env!(context.build());
+}
+
+fn claimed_templates(receipt: &DropClaimReceipt) -> Vec<TemplateId> {
+    receipt.claimed_tokens.iter().map(|token| token.template_id).collect()
+}
+
+#[test]
+fn random_drop_is_weighted_by_remaining_supply() {
+    let mut contract = setup_contract();
+    let common = create_template(&mut contract, "Common", 150);
+    let rare = create_template(&mut contract, "Rare", 50);
+    create_random_drop(&mut contract, vec![common, rare], None, 100);
+
+    // 10 claims of 10 units in different blocks
+    let mut templates = Vec::<TemplateId>::new();
+    for seed in 0..10 {
+        set_caller_with_seed(alice(), STORAGE_DEPOSIT, seed);
+        templates.extend(claimed_templates(&contract.claim_drop(0, 10)));
+    }
+
+    // 1 NFT per unit, ~75% Common (the weights move with the remaining supply)
+    assert_eq!(templates.len(), 100);
+    let common_count = templates.iter().filter(|id| **id == common).count();
+    assert!(
+        (60..=90).contains(&common_count),
+        "Common count out of range: {}",
+        common_count
+    );
+}
+
+#[test]
+fn random_drop_skips_exhausted_templates() {
+    let mut contract = setup_contract();
+    let sold_out = create_template(&mut contract, "Sold out", 2);
+    let available = create_template(&mut contract, "Available", 50);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_mint("Game".to_string(), 0, sold_out, Some(2), bob());
+
+    // Explicit weights favour the sold out Template, it must still never be picked
+    create_random_drop(&mut contract, vec![sold_out, available], Some(vec![1_000, 1]), 10);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let receipt = contract.claim_drop(0, 10);
+
+    assert!(claimed_templates(&receipt).iter().all(|id| *id == available));
+}
+
+#[test]
+fn random_drop_stops_picking_a_template_once_it_sells_out() {
+    let mut contract = setup_contract();
+    let limited = create_template(&mut contract, "Limited", 3);
+    let available = create_template(&mut contract, "Available", 50);
+    create_random_drop(&mut contract, vec![limited, available], Some(vec![1_000, 1]), 20);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let receipt = contract.claim_drop(0, 20);
+    let templates = claimed_templates(&receipt);
+
+    assert_eq!(templates.iter().filter(|id| **id == limited).count(), 3);
+    assert_eq!(templates.iter().filter(|id| **id == available).count(), 17);
+}
+
+#[test]
+#[should_panic(expected = "All the templates of this Drop Sale have issued all the NFTs")]
+fn random_drop_fails_when_every_template_is_exhausted() {
+    let mut contract = setup_contract();
+    let limited = create_template(&mut contract, "Limited", 1);
+    create_random_drop(&mut contract, vec![limited], None, 5);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 2);
+}
+
+#[test]
+fn unbox_rolls_each_slot_by_its_odds() {
+    let mut contract = setup_contract();
+    let sword = create_template(&mut contract, "Sword", 10);
+    let shield = create_template(&mut contract, "Shield", 10);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let lootbox = contract.create_lootbox(
+        "Game".to_string(),
+        0,
+        true,
+        true,
+        10,
+        ImmutableData {
+            name: "Chest".to_string(),
+            img: None,
+            rarity: None,
+            extra_immutable_data: None,
+            img_hash: None,
+            reference: None,
+            reference_hash: None,
+        },
+        None,
+        Some(vec![
+            // Single outcome -> always a Sword
+            Slot {
+                total_odds: 100,
+                outcomes: vec![Outcome {
+                    template_id: sword,
+                    odds: 100,
+                }],
+            },
+            // The Sword range is empty -> always a Shield
+            Slot {
+                total_odds: 100,
+                outcomes: vec![
+                    Outcome {
+                        template_id: sword,
+                        odds: 0,
+                    },
+                    Outcome {
+                        template_id: shield,
+                        odds: 100,
+                    },
+                ],
+            },
+            // No outcome covers the range -> no NFT from this slot
+            Slot {
+                total_odds: 100,
+                outcomes: vec![],
+            },
+        ]),
+    );
+    contract.mint_lootbox(lootbox.template_id, Some(1), alice());
+    let lootbox_token: TokenId = contract.nft_tokens_for_owner(alice(), None, None)[0]
+        .token_id
+        .parse()
+        .unwrap();
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.unbox_lootbox(lootbox_token);
+
+    let mut templates: Vec<TemplateId> = contract
+        .nft_tokens_for_owner(alice(), None, None)
+        .iter()
+        .map(|token| {
+            let token_id: TokenId = token.token_id.parse().unwrap();
+            contract.nft_token_detail(token_id).unwrap().template_id
+        })
+        .collect();
+    templates.sort();
+    assert_eq!(templates, vec![sword, shield]);
+}
@@ -0,0 +1,164 @@
+// Prefix search of Collections, Schemas and Templates
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+fn result_kinds(results: &[SearchResult]) -> Vec<&'static str> {
+    results
+        .iter()
+        .map(|result| match result {
+            SearchResult::Collection(_) => "collection",
+            SearchResult::Schema(_) => "schema",
+            SearchResult::Template(_) => "template",
+        })
+        .collect()
+}
+
+// Collection "Game" (display name "Game"), Schema "Weapon", Template "Lightsaber", + Schema "Wearable Items"
+fn setup_search() -> NFTContract {
+    let mut contract = setup_contract();
+    contract.create_schema("Game".to_string(), "Wearable Items".to_string(), vec![]);
+    contract
+}
+
+#[test]
+fn search_matches_word_prefixes_across_kinds() {
+    let contract = setup_search();
+
+    let results = contract.search("WE".to_string(), None, None, None).items;
+    assert_eq!(result_kinds(&results), vec!["schema", "schema"]);
+
+    let results = contract.search("light".to_string(), None, None, None).items;
+    assert_eq!(result_kinds(&results), vec!["template"]);
+
+    // Every word of the query must prefix a word of the name
+    let results = contract
+        .search("wea it".to_string(), None, None, None)
+        .items;
+    assert_eq!(results.len(), 1);
+    assert!(
+        matches!(&results[0], SearchResult::Schema(schema) if schema.schema_name == "Wearable Items")
+    );
+
+    // Substrings that are not a prefix of a word don't match
+    assert!(contract
+        .search("saber".to_string(), None, None, None)
+        .items
+        .is_empty());
+    // Words longer than the indexed prefixes are checked against the full names
+    assert_eq!(
+        contract
+            .search("lightsaber".to_string(), None, None, None)
+            .items
+            .len(),
+        1
+    );
+    assert!(contract
+        .search("lightsabers".to_string(), None, None, None)
+        .items
+        .is_empty());
+}
+
+#[test]
+fn search_filters_kinds_and_paginates() {
+    let contract = setup_search();
+
+    let results = contract
+        .search(
+            "w".to_string(),
+            Some(vec![SearchKind::Template]),
+            None,
+            None,
+        )
+        .items;
+    assert!(results.is_empty());
+
+    let first_page = contract.search("w".to_string(), None, None, Some(1));
+    assert_eq!(first_page.items.len(), 1);
+    let second_page = contract.search("w".to_string(), None, first_page.next_cursor, Some(1));
+    assert_eq!(second_page.items.len(), 1);
+    // 2 Schemas match "w" -> The second page is the last one
+    assert!(second_page.next_cursor.is_none());
+
+    assert_eq!(
+        contract
+            .get_schemas_by_name("wear".to_string(), None, None)
+            .items
+            .len(),
+        1
+    );
+    assert_eq!(
+        contract
+            .get_templates_by_name("li".to_string(), None, None)
+            .items
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn renaming_a_collection_updates_the_index() {
+    let mut contract = setup_search();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.update_collection_data(
+        "Game".to_string(),
+        CollectionExtraData {
+            name: "Space Odyssey".to_string(),
+            img: None,
+        },
+    );
+
+    assert_eq!(
+        contract
+            .get_collections_by_name("odys".to_string(), None, None)
+            .items
+            .len(),
+        1
+    );
+    assert_eq!(
+        contract
+            .get_collections_by_name("game".to_string(), None, None)
+            .items
+            .len(),
+        1
+    );
+    assert_eq!(
+        contract
+            .get_collection("Game".to_string())
+            .unwrap()
+            .data
+            .name,
+        "Space Odyssey"
+    );
+
+    contract.update_collection_data(
+        "Game".to_string(),
+        CollectionExtraData {
+            name: "Galaxy".to_string(),
+            img: None,
+        },
+    );
+    assert!(contract
+        .get_collections_by_name("space".to_string(), None, None)
+        .items
+        .is_empty());
+}
+
+#[test]
+fn hidden_collections_are_not_searchable() {
+    let mut contract = setup_search();
+
+    set_caller(owner(), 1);
+    contract.set_collection_hidden("Game".to_string(), true, None);
+
+    assert!(contract
+        .search("game".to_string(), None, None, None)
+        .items
+        .is_empty());
+    assert!(contract
+        .search("light".to_string(), None, None, None)
+        .items
+        .is_empty());
+}
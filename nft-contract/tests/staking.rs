@@ -0,0 +1,213 @@
+// Staking: locked NFTs cannot move, earn points per second until unlock_at
+mod common;
+
+use common::*;
+use near_sdk::json_types::U128;
+use nft_contract::*;
+
+const DAY: u64 = 86_400_000; // milliseconds
+
+// Template 0 ("uncommon") earns 2 points per second, alice owns 1 NFT of it
+fn setup_staking() -> (NFTContract, TokenId) {
+    let mut contract = setup_contract();
+    let token_id = mint(&mut contract, alice(), 1)[0].parse().unwrap();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.set_staking_rate(
+        "Game".to_string(),
+        StakingRateTarget::Template { template_id: 0 },
+        U128(2),
+    );
+
+    (contract, token_id)
+}
+
+fn points_of(contract: &NFTContract, account_id: String) -> u128 {
+    contract
+        .staking_points_of("Game".to_string(), account_id)
+        .0
+}
+
+#[test]
+fn locked_token_accrues_points_at_its_rate() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, DAY);
+    contract.nft_lock(vec![token_id], DAY);
+    assert!(contract.nft_is_locked(token_id));
+
+    // 1 giờ sau: 3_600 giây * 2 points
+    set_caller_at(alice(), 0, DAY + 3_600_000);
+    let info = contract.nft_lock_info(token_id).unwrap();
+    assert_eq!(info.lock.unlock_at, 2 * DAY);
+    assert_eq!(info.accrued_points.0, 7_200);
+
+    // Points dừng lại ở unlock_at
+    set_caller_at(alice(), 0, 10 * DAY);
+    let info = contract.nft_lock_info(token_id).unwrap();
+    assert_eq!(info.accrued_points.0, 86_400 * 2);
+}
+
+#[test]
+fn rarity_rate_is_used_when_template_has_no_rate() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.set_staking_rate(
+        "Game".to_string(),
+        StakingRateTarget::Rarity {
+            rarity: "uncommon".to_string(),
+        },
+        U128(5),
+    );
+    assert_eq!(contract.staking_rate_of(0).0, 2);
+
+    // Xoá rate của Template -> dùng rate của rarity
+    contract.set_staking_rate(
+        "Game".to_string(),
+        StakingRateTarget::Template { template_id: 0 },
+        U128(0),
+    );
+    assert_eq!(contract.staking_rate_of(0).0, 5);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.nft_lock(vec![token_id], DAY);
+    set_caller_at(alice(), 0, 1_000);
+    assert_eq!(contract.nft_lock_info(token_id).unwrap().accrued_points.0, 5);
+}
+
+#[test]
+#[should_panic(expected = "is locked")]
+fn locked_token_cannot_be_transferred() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_lock(vec![token_id], DAY);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), token_id.to_string(), None, None);
+}
+
+#[test]
+#[should_panic(expected = "is locked")]
+fn locked_token_cannot_be_approved() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_lock(vec![token_id], DAY);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_approve(token_id.to_string(), market(), None);
+}
+
+#[test]
+#[should_panic(expected = "is still locked")]
+fn token_cannot_be_unlocked_before_unlock_at() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.nft_lock(vec![token_id], DAY);
+
+    set_caller_at(alice(), 0, DAY - 1);
+    contract.nft_unlock(vec![token_id]);
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this token can lock it")]
+fn only_owner_can_lock_token() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.nft_lock(vec![token_id], DAY);
+}
+
+#[test]
+#[should_panic(expected = "is already locked")]
+fn token_cannot_be_locked_twice() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_lock(vec![token_id], DAY);
+    contract.nft_lock(vec![token_id], DAY);
+}
+
+#[test]
+fn unlock_adds_points_and_frees_token() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.nft_lock(vec![token_id], DAY);
+
+    set_caller_at(alice(), 0, 2 * DAY);
+    contract.nft_unlock(vec![token_id]);
+    assert!(!contract.nft_is_locked(token_id));
+    assert!(contract.nft_lock_info(token_id).is_none());
+    assert_eq!(points_of(&contract, alice()), 86_400 * 2);
+
+    // Token đã unlock có thể transfer
+    set_caller_at(alice(), 1, 2 * DAY);
+    contract.nft_transfer(bob(), token_id.to_string(), None, None);
+    assert_eq!(contract.nft_token(token_id.to_string()).unwrap().owner_id, bob());
+}
+
+#[test]
+fn claim_keeps_token_locked_and_does_not_count_twice() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.nft_lock(vec![token_id], DAY);
+
+    // Chỉ tính giây tròn: 10.5 giây -> 10 giây
+    set_caller_at(alice(), STORAGE_DEPOSIT, 10_500);
+    contract.claim_staking_points(vec![token_id]);
+    assert!(contract.nft_is_locked(token_id));
+    assert_eq!(points_of(&contract, alice()), 20);
+    let info = contract.nft_lock_info(token_id).unwrap();
+    assert_eq!(info.lock.claimed_until, 10_000);
+    assert_eq!(info.accrued_points.0, 0);
+
+    set_caller_at(alice(), 0, 20_000);
+    contract.claim_staking_points(vec![token_id]);
+    assert_eq!(points_of(&contract, alice()), 40);
+
+    set_caller_at(alice(), 0, DAY);
+    contract.nft_unlock(vec![token_id]);
+    assert_eq!(points_of(&contract, alice()), 86_400 * 2);
+}
+
+#[test]
+fn authorized_consumer_can_consume_points() {
+    let (mut contract, token_id) = setup_staking();
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 0);
+    contract.nft_lock(vec![token_id], DAY);
+    set_caller_at(alice(), STORAGE_DEPOSIT, 100_000);
+    contract.claim_staking_points(vec![token_id]);
+    assert_eq!(points_of(&contract, alice()), 200);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.set_staking_points_consumer("Game".to_string(), market(), true);
+
+    set_caller(market(), 0);
+    let remaining = contract.consume_staking_points("Game".to_string(), alice(), U128(150));
+    assert_eq!(remaining.0, 50);
+    assert_eq!(points_of(&contract, alice()), 50);
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this collection or an authorized account can consume points")]
+fn unauthorized_account_cannot_consume_points() {
+    let (mut contract, _) = setup_staking();
+
+    set_caller(bob(), 0);
+    contract.consume_staking_points("Game".to_string(), alice(), U128(0));
+}
+
+#[test]
+#[should_panic(expected = "Not enough points")]
+fn cannot_consume_more_points_than_balance() {
+    let (mut contract, _) = setup_staking();
+
+    set_caller(owner(), 0);
+    contract.consume_staking_points("Game".to_string(), alice(), U128(1));
+}
@@ -0,0 +1,190 @@
+// Token-gated Drop Sales: the claimer must hold tokens of a Template, Schema or Collection
+mod common;
+
+use common::*;
+use near_sdk::AccountId;
+use nft_contract::*;
+
+// Drop Sale 0 of Template 0, gated by access_rule
+fn create_gated_drop(contract: &mut NFTContract, access_rule: DropAccessRule) {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_drop(
+        "Game".to_string(),
+        DropSaleArgs {
+            access_rule: Some(access_rule),
+            ..drop_args(vec![0], 1_000)
+        },
+    );
+}
+
+fn rule(requirement: DropTokenRequirement, min_amount: u32, is_single_use: bool) -> DropAccessRule {
+    DropAccessRule {
+        requirement,
+        min_amount,
+        is_single_use,
+    }
+}
+
+// Mint `mint_number` NFTs of a Template to receiver_id, return their token ids
+fn mint_template(
+    contract: &mut NFTContract,
+    template_id: TemplateId,
+    receiver_id: AccountId,
+    mint_number: u32,
+) -> Vec<TokenId> {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_mint("Game".to_string(), 0, template_id, Some(mint_number), receiver_id);
+    contract
+        .nft_tokens_for_template(template_id, None, None)
+        .items
+        .into_iter()
+        .map(|token| token.token_id.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn holder_of_the_template_can_claim() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    mint_template(&mut contract, pass, alice(), 1);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, false));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+
+    assert_eq!(contract.get_drop_by_id(0).issued_supply, 1);
+}
+
+#[test]
+#[should_panic(expected = "Claimer must hold 1 tokens required by this Drop Sale")]
+fn account_without_the_token_cannot_claim() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    mint_template(&mut contract, pass, alice(), 1);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, false));
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Claimer must hold 2 tokens required by this Drop Sale")]
+fn claimer_must_hold_the_minimum_amount() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 1);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Schema { schema_id: 0 }, 2, false));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Claimer must hold 1 tokens required by this Drop Sale")]
+fn tokens_of_another_collection_do_not_match() {
+    let mut contract = setup_contract();
+    mint(&mut contract, alice(), 1);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_collection(
+        "Other".to_string(),
+        0.05,
+        CollectionExtraData {
+            name: "Other".to_string(),
+            img: None,
+        },
+    );
+    create_gated_drop(
+        &mut contract,
+        rule(
+            DropTokenRequirement::Collection {
+                collection_name: "Other".to_string(),
+            },
+            1,
+            false,
+        ),
+    );
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Collection does not exist")]
+fn rule_of_an_unknown_collection_is_rejected() {
+    let mut contract = setup_contract();
+    create_gated_drop(
+        &mut contract,
+        rule(
+            DropTokenRequirement::Collection {
+                collection_name: "Unknown".to_string(),
+            },
+            1,
+            false,
+        ),
+    );
+}
+
+#[test]
+fn reusable_token_can_be_used_for_many_claims() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    let pass_tokens = mint_template(&mut contract, pass, alice(), 1);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, false));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 3);
+
+    assert_eq!(contract.get_drop_by_id(0).issued_supply, 4);
+    assert!(!contract.drop_is_token_used(0, pass_tokens[0]));
+}
+
+#[test]
+fn single_use_tokens_are_marked_as_used() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    let pass_tokens = mint_template(&mut contract, pass, alice(), 3);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, true));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 2);
+
+    let used = pass_tokens
+        .iter()
+        .filter(|token_id| contract.drop_is_token_used(0, **token_id))
+        .count();
+    assert_eq!(used, 2);
+}
+
+#[test]
+#[should_panic(expected = "Claimer must hold 1 tokens required by this Drop Sale")]
+fn single_use_tokens_cannot_be_used_twice() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    mint_template(&mut contract, pass, alice(), 2);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, true));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 2);
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Claimer must hold 1 tokens required by this Drop Sale")]
+fn used_token_stays_used_after_a_transfer() {
+    let mut contract = setup_contract();
+    let pass = create_template(&mut contract, "Pass", 10);
+    let pass_tokens = mint_template(&mut contract, pass, alice(), 1);
+    create_gated_drop(&mut contract, rule(DropTokenRequirement::Template { template_id: pass }, 1, true));
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), pass_tokens[0].to_string(), None, None);
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.claim_drop(0, 1);
+}
@@ -0,0 +1,90 @@
+// Indexes of the NFTs of each Template / Collection and the holders of each Collection
+mod common;
+
+use common::*;
+use near_sdk::AccountId;
+use nft_contract::*;
+
+fn holders(contract: &NFTContract) -> Vec<(AccountId, u32)> {
+    let mut holders: Vec<(AccountId, u32)> = contract
+        .collection_holders("Game".to_string(), None, None)
+        .items
+        .into_iter()
+        .map(|holder| (holder.account_id, holder.token_count))
+        .collect();
+    holders.sort();
+    holders
+}
+
+#[test]
+fn mint_adds_tokens_to_template_and_collection() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 3);
+
+    assert_eq!(contract.nft_supply_for_template(0).0, 3);
+    assert_eq!(contract.nft_supply_for_collection("Game".to_string()).0, 3);
+
+    let template_tokens: Vec<String> = contract
+        .nft_tokens_for_template(0, Some(token_ids[0].clone()), Some(2))
+        .items
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect();
+    assert_eq!(template_tokens, token_ids[1..].to_vec());
+
+    let collection_tokens = contract
+        .nft_tokens_for_collection("Game".to_string(), None, None)
+        .items;
+    assert_eq!(collection_tokens.len(), 3);
+    assert!(collection_tokens
+        .iter()
+        .all(|token| token.owner_id == alice()));
+}
+
+#[test]
+fn transfers_move_the_holder_counts() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 2);
+    mint(&mut contract, bob(), 1);
+    assert_eq!(holders(&contract), vec![(alice(), 2), (bob(), 1)]);
+
+    set_caller(alice(), 1);
+    contract.nft_transfer(bob(), token_ids[0].clone(), None, None);
+    assert_eq!(holders(&contract), vec![(alice(), 1), (bob(), 2)]);
+
+    contract.nft_transfer(bob(), token_ids[1].clone(), None, None);
+    assert_eq!(holders(&contract), vec![(bob(), 3)]);
+}
+
+#[test]
+fn burned_tokens_leave_the_indexes() {
+    let mut contract = setup_contract();
+    let token_ids = mint(&mut contract, alice(), 2);
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_blend(
+        "Game".to_string(),
+        vec![
+            BlendIngredient::Template { template_id: 0 },
+            BlendIngredient::Template { template_id: 0 },
+        ],
+        vec![0],
+        None,
+        None,
+        None,
+    );
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.blend(
+        0,
+        token_ids
+            .iter()
+            .map(|token_id| token_id.parse().unwrap())
+            .collect(),
+    );
+
+    // 2 NFTs burned, 1 NFT minted
+    assert_eq!(contract.nft_supply_for_template(0).0, 1);
+    assert_eq!(contract.nft_supply_for_collection("Game".to_string()).0, 1);
+    assert_eq!(holders(&contract), vec![(alice(), 1)]);
+}
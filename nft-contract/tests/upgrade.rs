@@ -0,0 +1,166 @@
+// Upgrade rules: 1 NFT of from_template + cost -> the same NFT becomes an NFT of to_template
+mod common;
+
+use common::*;
+use near_sdk::json_types::U128;
+use nft_contract::*;
+
+const DAY: u64 = 86_400_000; // milliseconds
+
+// Template of Schema 0 with a validity duration
+fn create_timed_template(contract: &mut NFTContract, name: &str, validity_duration: Option<u64>) -> TemplateId {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract
+        .create_template(
+            "Game".to_string(),
+            TemplateArgs {
+                schema_id: 0,
+                transferable: true,
+                burnable: true,
+                max_supply: 10,
+                immutable_data: ImmutableData {
+                    name: name.to_string(),
+                    img: None,
+                    rarity: None,
+                    extra_immutable_data: None,
+                    img_hash: None,
+                    reference: None,
+                    reference_hash: None,
+                },
+                validity_duration,
+            },
+        )
+        .template_id
+}
+
+// Upgrade rule from_template_id -> to_template_id for 1_000 yoctoNEAR
+fn create_rule(contract: &mut NFTContract, from_template_id: TemplateId, to_template_id: TemplateId) -> UpgradeRuleId {
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract
+        .create_upgrade_rule(
+            "Game".to_string(),
+            from_template_id,
+            to_template_id,
+            Some(U128(1_000)),
+            None,
+        )
+        .rule_id
+}
+
+fn first_token(contract: &mut NFTContract) -> TokenId {
+    mint(contract, alice(), 1)[0].parse().unwrap()
+}
+
+#[test]
+fn upgrade_moves_the_token_to_the_new_template() {
+    let mut contract = setup_contract();
+    let token_id = first_token(&mut contract);
+    let sword = create_template(&mut contract, "Sword", 10);
+    let rule_id = create_rule(&mut contract, 0, sword);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    let token = contract.upgrade_token(token_id, rule_id, None);
+
+    assert_eq!(token.token_id, token_id);
+    assert_eq!(token.template_id, sword);
+    assert_eq!(token.metadata.title, Some("Sword".to_string()));
+    assert_eq!(contract.nft_supply_for_template(sword).0, 1);
+    assert_eq!(contract.nft_supply_for_template(0).0, 0);
+}
+
+#[test]
+#[should_panic(expected = "is locked")]
+fn locked_token_cannot_be_upgraded() {
+    let mut contract = setup_contract();
+    let token_id = first_token(&mut contract);
+    let sword = create_template(&mut contract, "Sword", 10);
+    let rule_id = create_rule(&mut contract, 0, sword);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.nft_lock(vec![token_id], DAY);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.upgrade_token(token_id, rule_id, None);
+}
+
+#[test]
+#[should_panic(expected = "Collection Game is frozen")]
+fn token_of_a_frozen_collection_cannot_be_upgraded() {
+    let mut contract = setup_contract();
+    let token_id = first_token(&mut contract);
+    let sword = create_template(&mut contract, "Sword", 10);
+    let rule_id = create_rule(&mut contract, 0, sword);
+
+    set_caller(owner(), 1);
+    contract.set_collection_frozen("Game".to_string(), true, None);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.upgrade_token(token_id, rule_id, None);
+}
+
+#[test]
+#[should_panic(expected = "feature is paused")]
+fn token_cannot_be_upgraded_while_mint_is_paused() {
+    let mut contract = setup_contract();
+    let token_id = first_token(&mut contract);
+    let sword = create_template(&mut contract, "Sword", 10);
+    let rule_id = create_rule(&mut contract, 0, sword);
+
+    set_caller(owner(), 1);
+    contract.pause(vec![PauseFeature::Mint], None);
+
+    set_caller(alice(), STORAGE_DEPOSIT);
+    contract.upgrade_token(token_id, rule_id, None);
+}
+
+#[test]
+fn upgrade_keeps_the_validity_window_of_the_token() {
+    let mut contract = setup_contract();
+    let pass = create_timed_template(&mut contract, "Pass", Some(DAY));
+    let gold_pass = create_timed_template(&mut contract, "Gold pass", Some(30 * DAY));
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.nft_mint("Game".to_string(), 0, pass, Some(1), alice());
+    let token_id: TokenId = contract.nft_tokens_for_owner(alice(), None, None)[0]
+        .token_id
+        .parse()
+        .unwrap();
+    let rule_id = create_rule(&mut contract, pass, gold_pass);
+
+    // Upgrade half a day later: the token still expires 1 day after it was minted
+    set_caller_at(alice(), STORAGE_DEPOSIT, DAY / 2);
+    let token = contract.upgrade_token(token_id, rule_id, None);
+
+    assert_eq!(token.metadata.starts_at, Some(0));
+    assert_eq!(token.metadata.expires_at, Some(DAY));
+    assert_eq!(token.metadata.updated_at, Some(DAY / 2));
+}
+
+#[test]
+fn upgrade_to_a_timed_template_starts_the_validity_window() {
+    let mut contract = setup_contract();
+    let token_id = first_token(&mut contract);
+    let pass = create_timed_template(&mut contract, "Pass", Some(DAY));
+    let rule_id = create_rule(&mut contract, 0, pass);
+
+    set_caller_at(alice(), STORAGE_DEPOSIT, 5 * DAY);
+    let token = contract.upgrade_token(token_id, rule_id, None);
+
+    assert_eq!(token.metadata.starts_at, Some(5 * DAY));
+    assert_eq!(token.metadata.expires_at, Some(6 * DAY));
+}
+
+#[test]
+fn upgrade_rules_are_listed_per_collection() {
+    let mut contract = setup_contract();
+    let sword = create_template(&mut contract, "Sword", 10);
+    let shield = create_template(&mut contract, "Shield", 10);
+    create_rule(&mut contract, 0, sword);
+    create_rule(&mut contract, 0, shield);
+
+    let page = contract.get_all_upgrade_rules_by_collection("Game".to_string(), None, Some(1));
+    assert_eq!(page.items[0].to_template_id, sword);
+
+    let page = contract.get_all_upgrade_rules_by_collection("Game".to_string(), page.next_cursor, None);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].to_template_id, shield);
+}
@@ -0,0 +1,146 @@
+// Time-limited NFTs: Template validity_duration -> starts_at, expires_at (milliseconds)
+mod common;
+
+use common::*;
+use nft_contract::*;
+
+const DAY: u64 = 86_400_000; // milliseconds
+
+// Template 1 with a 30-day validity, 1 NFT of it minted to alice at `mint_time`
+fn setup_pass(mint_time: u64) -> (NFTContract, String) {
+    let mut contract = setup_contract();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    let template_id = contract
+        .create_template(
+            "Game".to_string(),
+            TemplateArgs {
+                schema_id: 0,
+                transferable: true,
+                burnable: true,
+                max_supply: 10,
+                immutable_data: ImmutableData {
+                    name: "30-day pass".to_string(),
+                    img: None,
+                    rarity: None,
+                    extra_immutable_data: None,
+                    img_hash: None,
+                    reference: None,
+                    reference_hash: None,
+                },
+                validity_duration: Some(30 * DAY),
+            },
+        )
+        .template_id;
+    assert_eq!(template_id, 1);
+
+    set_caller_at(owner(), STORAGE_DEPOSIT, mint_time);
+    contract.nft_mint("Game".to_string(), 0, template_id, Some(1), alice());
+    let token_id = contract
+        .nft_tokens_for_template(template_id, None, None)
+        .items[0]
+        .token_id
+        .clone();
+
+    (contract, token_id)
+}
+
+#[test]
+fn minted_token_is_valid_for_the_template_duration() {
+    let (contract, token_id) = setup_pass(DAY);
+
+    let metadata = contract.nft_token(token_id.clone()).unwrap().metadata;
+    assert_eq!(metadata.issued_at, Some(DAY));
+    assert_eq!(metadata.starts_at, Some(DAY));
+    assert_eq!(metadata.expires_at, Some(31 * DAY));
+
+    let token_id: TokenId = token_id.parse().unwrap();
+    set_caller_at(alice(), 0, DAY);
+    assert!(contract.nft_is_valid(token_id));
+    set_caller_at(alice(), 0, 31 * DAY - 1);
+    assert!(contract.nft_is_valid(token_id));
+    set_caller_at(alice(), 0, 31 * DAY);
+    assert!(!contract.nft_is_valid(token_id));
+}
+
+#[test]
+fn token_without_validity_duration_never_expires() {
+    let mut contract = setup_contract();
+    let token_id: TokenId = mint(&mut contract, alice(), 1)[0].parse().unwrap();
+
+    let metadata = contract.nft_token(token_id.to_string()).unwrap().metadata;
+    assert_eq!(metadata.starts_at, None);
+    assert_eq!(metadata.expires_at, None);
+
+    set_caller_at(alice(), 0, 10_000 * DAY);
+    assert!(contract.nft_is_valid(token_id));
+}
+
+#[test]
+#[should_panic(expected = "Validity duration must be greater than 0")]
+fn validity_duration_cannot_be_zero() {
+    let mut contract = setup_contract();
+
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.create_template(
+        "Game".to_string(),
+        TemplateArgs {
+            schema_id: 0,
+            transferable: true,
+            burnable: true,
+            max_supply: 10,
+            immutable_data: ImmutableData {
+                name: "Broken pass".to_string(),
+                img: None,
+                rarity: None,
+                extra_immutable_data: None,
+                img_hash: None,
+                reference: None,
+                reference_hash: None,
+            },
+            validity_duration: Some(0),
+        },
+    );
+}
+
+#[test]
+fn expired_token_can_be_transferred_by_default() {
+    let (mut contract, token_id) = setup_pass(0);
+
+    set_caller_at(alice(), 1, 40 * DAY);
+    contract.nft_transfer(bob(), token_id.clone(), None, None);
+    assert_eq!(contract.nft_token(token_id).unwrap().owner_id, bob());
+}
+
+#[test]
+#[should_panic(expected = "Token has expired")]
+fn expired_token_cannot_be_transferred_when_collection_disallows_it() {
+    let (mut contract, token_id) = setup_pass(0);
+
+    set_caller(owner(), 1);
+    contract.set_collection_allow_expired_transfer("Game".to_string(), false);
+
+    set_caller_at(alice(), 1, 30 * DAY);
+    contract.nft_transfer(bob(), token_id, None, None);
+}
+
+#[test]
+fn valid_token_can_be_transferred_when_collection_disallows_expired_transfer() {
+    let (mut contract, token_id) = setup_pass(0);
+
+    set_caller(owner(), 1);
+    contract.set_collection_allow_expired_transfer("Game".to_string(), false);
+
+    set_caller_at(alice(), 1, 30 * DAY - 1);
+    contract.nft_transfer(bob(), token_id.clone(), None, None);
+    assert_eq!(contract.nft_token(token_id).unwrap().owner_id, bob());
+}
+
+#[test]
+#[should_panic(expected = "Only owner of this collection can change this option")]
+fn only_collection_owner_can_disallow_expired_transfer() {
+    let (mut contract, _) = setup_pass(0);
+
+    set_caller(alice(), 1);
+    contract.set_collection_allow_expired_transfer("Game".to_string(), false);
+}
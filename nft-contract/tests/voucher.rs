@@ -0,0 +1,126 @@
+// Mint Vouchers: signature, expiry and nonce replay
+mod common;
+
+use common::*;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::{Base58PublicKey, Base64VecU8, U128};
+use nft_contract::*;
+
+fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+// Contract "Game" accepting the vouchers signed by keypair(1)
+fn setup_voucher_contract() -> NFTContract {
+    let mut contract = setup_contract();
+
+    // ed25519 curve type + 32 bytes of key
+    let mut public_key = vec![0];
+    public_key.extend_from_slice(keypair(1).public.as_bytes());
+    set_caller(owner(), STORAGE_DEPOSIT);
+    contract.set_voucher_public_key("Game".to_string(), Some(Base58PublicKey(public_key)));
+
+    contract
+}
+
+fn voucher(nonce: u64) -> MintVoucher {
+    MintVoucher {
+        template_id: 0,
+        receiver_id: alice(),
+        price: U128(1_000),
+        expires_at: 10_000,
+        nonce,
+    }
+}
+
+fn sign(keypair: &Keypair, contract_id: &str, collection_name: &str, voucher: &MintVoucher) -> Base64VecU8 {
+    let message = MintVoucherPayload {
+        contract_id: contract_id.to_string(),
+        collection_name: collection_name.to_string(),
+        voucher: voucher.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+    Base64VecU8(keypair.sign(&message).to_bytes().to_vec())
+}
+
+#[test]
+fn redeem_a_signed_voucher() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "nft.testnet", "Game", &voucher(1));
+
+    set_caller_at(bob(), STORAGE_DEPOSIT, 5_000);
+    let token_id = contract.redeem_voucher(voucher(1), signature);
+
+    assert_eq!(contract.nft_token_detail(token_id).unwrap().owner_id, alice());
+    assert!(contract.is_voucher_redeemed("Game".to_string(), 1));
+}
+
+#[test]
+#[should_panic(expected = "Invalid voucher signature")]
+fn voucher_signed_by_another_key_is_rejected() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(2), "nft.testnet", "Game", &voucher(1));
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(voucher(1), signature);
+}
+
+#[test]
+#[should_panic(expected = "Invalid voucher signature")]
+fn voucher_signed_for_another_contract_is_rejected() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "other-nft.testnet", "Game", &voucher(1));
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(voucher(1), signature);
+}
+
+#[test]
+#[should_panic(expected = "Invalid voucher signature")]
+fn voucher_signed_for_another_collection_is_rejected() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "nft.testnet", "Other", &voucher(1));
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(voucher(1), signature);
+}
+
+#[test]
+#[should_panic(expected = "Invalid voucher signature")]
+fn tampered_voucher_is_rejected() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "nft.testnet", "Game", &voucher(1));
+
+    let tampered = MintVoucher {
+        price: U128(0),
+        ..voucher(1)
+    };
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(tampered, signature);
+}
+
+#[test]
+#[should_panic(expected = "Voucher has expired")]
+fn expired_voucher_is_rejected() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "nft.testnet", "Game", &voucher(1));
+
+    set_caller_at(bob(), STORAGE_DEPOSIT, 10_000);
+    contract.redeem_voucher(voucher(1), signature);
+}
+
+#[test]
+#[should_panic(expected = "Voucher has already been redeemed")]
+fn voucher_cannot_be_redeemed_twice() {
+    let mut contract = setup_voucher_contract();
+    let signature = sign(&keypair(1), "nft.testnet", "Game", &voucher(1));
+
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(voucher(1), signature.clone());
+    set_caller(bob(), STORAGE_DEPOSIT);
+    contract.redeem_voucher(voucher(1), signature);
+}